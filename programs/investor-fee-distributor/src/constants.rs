@@ -1,17 +1,206 @@
+use anchor_lang::prelude::*;
+
 pub const VAULT_SEED: &[u8] = b"vault";
 pub const INVESTOR_FEE_POS_OWNER_SEED: &[u8] = b"investor_fee_pos_owner";
 pub const POLICY_SEED: &[u8] = b"policy";
 pub const PROGRESS_SEED: &[u8] = b"progress";
 pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const SNAPSHOT_SEED: &[u8] = b"snapshot";
+pub const ACCRUAL_SEED: &[u8] = b"accrual";
+pub const SHARE_OVERRIDES_SEED: &[u8] = b"share_overrides";
+pub const COORDINATOR_SEED: &[u8] = b"coordinator";
+pub const WEIGHT_TABLE_SEED: &[u8] = b"weight_table";
+pub const OPT_IN_SEED: &[u8] = b"opt_in";
+pub const DAY_SIMULATION_SEED: &[u8] = b"day_simulation";
+pub const DAY_RECORD_SEED: &[u8] = b"day_record";
+
+/// Seed, under an external distributor program, of the PDA owning its escrow
+pub const DISTRIBUTOR_ESCROW_SEED: &[u8] = b"distributor_escrow";
+
+/// Anchor discriminator of an external distributor's `pay_investor` instruction,
+/// `sha256("global:pay_investor")[..8]`
+pub const PAY_INVESTOR_DISCRIMINATOR: [u8; 8] = [197, 161, 112, 90, 15, 102, 66, 104];
 
 /// Time constants
 pub const SECONDS_PER_DAY: i64 = 86_400;
 
+/// Longest allowed distribution interval
+pub const MAX_DISTRIBUTION_INTERVAL: i64 = 30 * SECONDS_PER_DAY;
+
+/// Furthest ahead a read-only preview may evaluate stream locked amounts (`as_of_ts`)
+pub const MAX_AS_OF_HORIZON: i64 = 365 * SECONDS_PER_DAY;
+
 /// Basis points
 pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
 
+/// Governance ceiling on investor_fee_share_bps so the creator is never starved.
+/// Adjust per deployment; must be <= BASIS_POINTS_DIVISOR
+pub const MAX_INVESTOR_FEE_SHARE_BPS: u16 = 9_000;
+
+/// Maximum number of investors with a negotiated share multiplier
+pub const MAX_SHARE_OVERRIDES: usize = 32;
+
+/// Maximum number of vaults sharing one treasury coordinator
+pub const MAX_COORDINATOR_MEMBERS: usize = 8;
+
+/// Fixed-point scale of USD prices (micro-USD per whole token)
+pub const USD_PRICE_SCALE: u64 = 1_000_000;
+
+/// Default oldest oracle price a USD-weighted snapshot or weight table may start from
+pub const DEFAULT_MAX_ORACLE_STALENESS_SECONDS: u32 = 60;
+
+/// Default widest oracle confidence interval accepted, relative to the price (2%)
+pub const DEFAULT_MAX_ORACLE_CONFIDENCE_BPS: u16 = 200;
+
+/// Argument `force_reset_progress` requires before it wipes the progress account
+pub const FORCE_RESET_PROGRESS_CONFIRMATION: [u8; 16] = *b"RESET PROGRESS!!";
+
+/// Largest per-investor share multiplier (5x their pro-rata weight)
+pub const MAX_SHARE_MULTIPLIER_BPS: u16 = 50_000;
+
+/// Largest minimum payout accepted, in whole quote tokens; anything above it is
+/// almost certainly scaled by the quote decimals twice
+pub const MAX_MIN_PAYOUT_WHOLE_TOKENS: u64 = 1_000_000;
+
 /// Default minimum payout threshold (0.001 SOL equivalent in lamports)
 pub const DEFAULT_MIN_PAYOUT_LAMPORTS: u64 = 1_000_000;
 
+/// Byte offset of the `COption<Pubkey>` delegate in an SPL Token or Token-2022 account
+pub const TOKEN_ACCOUNT_DELEGATE_OFFSET: usize = 72;
+
+/// Byte offset of the u64 amount in an SPL Token or Token-2022 account
+pub const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Token-2022 program, whose mints may take a fee on every transfer
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Byte offset of the account type in a Token-2022 mint or account with extensions;
+/// the extension TLVs follow it
+pub const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = 165;
+
+/// Extension type of Token-2022's `TransferFeeConfig` mint extension
+pub const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+
+/// Meteora DAMM v2 (cp-amm) program, which owns the pools fees are claimed from
+pub const DAMM_V2_PROGRAM_ID: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHswqLq7xxk5W2Y5Z");
+
+/// Byte offset of the u8 `pool_status` in a DAMM v2 `Pool`, after its discriminator
+pub const DAMM_POOL_STATUS_OFFSET: usize = 481;
+
+/// `pool_status` of a DAMM v2 pool that accepts fee claims; any other value is disabled
+pub const DAMM_POOL_STATUS_ENABLED: u8 = 0;
+
+/// Widest `reconciliation_tolerance_lamports` a policy may allow; rounding and
+/// transfer fees lose a few lamports, anything beyond is drift worth reporting
+pub const MAX_RECONCILIATION_TOLERANCE_LAMPORTS: u64 = 10_000;
+
 /// Maximum page size for investor distribution
-pub const MAX_PAGE_SIZE: u8 = 50;
\ No newline at end of file
+pub const MAX_PAGE_SIZE: u8 = 50;
+
+/// Largest investor set a `top_n_investors` policy can rank. The day-start page reads
+/// and ranks the whole ledger within one transaction's 32 KiB heap and compute budget.
+pub const MAX_TOP_N_INVESTOR_SET: u32 = 512;
+
+/// Maximum page size while payout memos are attached (each payout adds a Memo CPI)
+pub const MAX_MEMO_PAGE_SIZE: u8 = 20;
+
+/// Maximum page size while payouts are converted (each payout adds a swap CPI)
+pub const MAX_SWAP_PAGE_SIZE: u8 = 8;
+
+/// Largest page `preview_page_breakdown` and `preview_policy_change` return (their
+/// lines must fit in 1024 bytes of return data)
+pub const MAX_PREVIEW_PAGE_SIZE: u8 = 40;
+
+/// Default slippage bound on payout conversion swaps (0.5%)
+pub const DEFAULT_MAX_SWAP_SLIPPAGE_BPS: u16 = 50;
+
+/// Largest slippage bound a policy may allow on payout conversion swaps (10%)
+pub const MAX_SWAP_SLIPPAGE_BPS: u16 = 1_000;
+
+/// Fixed-point scale of `settlement_rate`: payout-mint units per 1e9 quote units
+pub const SETTLEMENT_RATE_SCALE: u64 = 1_000_000_000;
+
+/// Largest share of each claim a policy may route to the protocol fee wallet (5%)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 500;
+
+// The compute figures below are estimates, not measurements of this program. They
+// are built from the runtime's fixed costs (1,000 CU per CPI invoke, 1,500 CU per
+// `create_program_address`) and SPL Token's transfer (about 4,500 CU), rounded up for
+// account deserialization, checks and logging. Re-measure them from transaction logs
+// before relying on a tight budget.
+
+/// Approximate compute of one distribute_fees call before any investor is processed
+pub const CRANK_BASE_CU: u64 = 25_000;
+
+/// Approximate compute per investor in a distribute_fees page (stream read + payout)
+pub const CRANK_PER_INVESTOR_CU: u64 = 12_000;
+
+/// Approximate extra compute per pushed payout when memos are attached
+pub const CRANK_MEMO_CU: u64 = 6_000;
+
+/// Approximate extra compute per pushed payout when payouts are converted (DEX swap
+/// CPI plus the payout-mint transfer)
+pub const CRANK_SWAP_CU: u64 = 60_000;
+
+/// Approximate extra compute per pushed payout when deliveries are confirmed (two
+/// balance reads of the destination, no CPI)
+pub const CRANK_DELIVERY_CHECK_CU: u64 = 1_000;
+
+/// Approximate extra compute of the second transfer of a split payout
+pub const CRANK_SPLIT_TRANSFER_CU: u64 = 5_000;
+
+/// Approximate extra compute per payout when a share is withheld (one more transfer,
+/// in Pull mode too)
+pub const CRANK_WITHHOLDING_TRANSFER_CU: u64 = 5_000;
+
+/// Approximate extra compute of each installment after a payout's first transfer under
+/// `max_transfer_size` (one more token transfer CPI)
+pub const CRANK_INSTALLMENT_TRANSFER_CU: u64 = 5_000;
+
+/// Approximate extra compute per payout handed to an external distributor: the
+/// `pay_investor` invoke and its instruction, on top of the escrow transfer that takes
+/// the direct transfer's place. The distributor's own compute isn't included
+pub const CRANK_DISTRIBUTOR_CPI_CU: u64 = 3_000;
+
+/// Approximate compute of deriving the distributor's escrow authority, once per page
+/// (`find_program_address`, budgeted for two `create_program_address` attempts)
+pub const CRANK_FIND_PDA_CU: u64 = 3_000;
+
+/// Most investor transfers one distribute_fees page may make, installments and split
+/// legs included, so a page under `max_transfer_size` stays within compute
+pub const MAX_PAGE_TRANSFERS: u32 = 64;
+
+/// Approximate compute of a creator transfer (a PerPage slice or the final remainder)
+pub const CRANK_CREATOR_LEG_CU: u64 = 15_000;
+
+/// Approximate compute per investor in a snapshot_page call
+pub const SNAPSHOT_PER_INVESTOR_CU: u64 = 3_000;
+
+/// Headroom `recommended_compute_units` adds on top of the approximate page compute
+pub const RECOMMENDED_CU_MARGIN_BPS: u64 = 2_000;
+
+/// Most compute units one transaction may request
+pub const MAX_TRANSACTION_CU: u64 = 1_400_000;
+
+/// Base fee charged per transaction signature
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Default weight of the newest day's claim in the claim moving average (20%)
+pub const DEFAULT_CLAIM_EMA_ALPHA_BPS: u16 = 2_000;
+
+/// `max_claim_growth_bps` that never throttles a day's claim
+pub const UNLIMITED_CLAIM_GROWTH_BPS: u32 = u32::MAX;
+
+/// Claims folded into the moving average before the circuit breaker may trip
+pub const CIRCUIT_BREAKER_WARMUP_DAYS: u32 = 3;
+
+/// Maximum length of the policy's payout memo reference, in bytes
+pub const MAX_MEMO_REFERENCE_LEN: usize = 32;
+
+/// Most progress accounts one `batch_status` call reads, so the packed statuses fit in
+/// the 1024 bytes of return data
+pub const MAX_BATCH_STATUS_VAULTS: usize = 11;
+
+/// Most day records one `audit_history` call reads, so the records, vault and fee
+/// payer fit in a legacy transaction's account list
+pub const MAX_AUDIT_HISTORY_DAYS: usize = 24;
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use crate::constants::{DISTRIBUTOR_ESCROW_SEED, PAY_INVESTOR_DISCRIMINATOR};
+
+/// An external program the policy hands pushed investor payouts to, in place of the
+/// built-in transfer to the investor's ATA.
+///
+/// Each payout is first moved from the treasury into the distributor's escrow, a quote
+/// token account owned by the distributor's `[DISTRIBUTOR_ESCROW_SEED, vault]` PDA.
+/// The distributor is then called with `pay_investor` and delivers the escrowed
+/// amount however it likes (staking, vesting, ...). The treasury authority never
+/// signs for it, so it can't reach more than the payout it was given.
+///
+/// `pay_investor` takes these accounts, in order:
+/// 0. `[writable]` escrow, holding the payout
+/// 1. `[]` escrow authority PDA
+/// 2. `[writable]` investor's quote ATA
+/// 3. `[]` investor's stream
+/// 4. `[]` vault
+/// 5. `[]` token program
+///
+/// and `PAY_INVESTOR_DISCRIMINATOR` followed by the Borsh-encoded `PayInvestorArgs`.
+pub struct ExternalDistributor<'info> {
+    pub program: AccountInfo<'info>,
+    pub escrow: AccountInfo<'info>,
+    pub escrow_authority: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Arguments of the distributor's `pay_investor` instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PayInvestorArgs {
+    pub investor_index: u32,
+    /// Quote lamports escrowed for the investor
+    pub amount: u64,
+}
+
+impl PayInvestorArgs {
+    /// Instruction data: discriminator, then the arguments
+    pub fn data(&self) -> Result<Vec<u8>> {
+        let mut data = PAY_INVESTOR_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// The PDA of `program` that must own a vault's escrow
+pub fn escrow_authority(program: &Pubkey, vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[DISTRIBUTOR_ESCROW_SEED, vault.as_ref()], program).0
+}
+
+impl<'info> ExternalDistributor<'info> {
+    /// Have the distributor deliver `amount`, already escrowed, to the investor; a
+    /// failing distributor fails the whole page, so no payout is half-recorded
+    pub fn pay_investor(
+        &self,
+        investor_ata: AccountInfo<'info>,
+        stream: AccountInfo<'info>,
+        args: PayInvestorArgs,
+    ) -> Result<()> {
+        let instruction = Instruction {
+            program_id: self.program.key(),
+            accounts: vec![
+                AccountMeta::new(self.escrow.key(), false),
+                AccountMeta::new_readonly(self.escrow_authority.key(), false),
+                AccountMeta::new(investor_ata.key(), false),
+                AccountMeta::new_readonly(stream.key(), false),
+                AccountMeta::new_readonly(self.vault.key(), false),
+                AccountMeta::new_readonly(self.token_program.key(), false),
+            ],
+            data: args.data()?,
+        };
+
+        invoke(
+            &instruction,
+            &[
+                self.escrow.clone(),
+                self.escrow_authority.clone(),
+                investor_ata,
+                stream,
+                self.vault.clone(),
+                self.token_program.clone(),
+                self.program.clone(),
+            ],
+        )
+        .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pay_investor_data_is_the_discriminator_then_little_endian_args() {
+        let args = PayInvestorArgs { investor_index: 7, amount: 1_000 };
+        let data = args.data().unwrap();
+
+        assert_eq!(data.len(), 8 + 4 + 8);
+        assert_eq!(data[..8], PAY_INVESTOR_DISCRIMINATOR);
+        assert_eq!(data[8..12], 7u32.to_le_bytes());
+        assert_eq!(data[12..], 1_000u64.to_le_bytes());
+        assert_eq!(PayInvestorArgs::try_from_slice(&data[8..]).unwrap(), args);
+    }
+}
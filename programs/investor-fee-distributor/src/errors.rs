@@ -26,7 +26,7 @@ pub enum FeeDistributorError {
     #[msg("Total locked amount is zero - no investors to distribute to")]
     NoLockedTokens,
     
-    #[msg("Invalid investor page size - exceeds maximum")]
+    #[msg("Invalid investor page size - must be at least 1")]
     InvalidPageSize,
     
     #[msg("Daily cap exceeded")]
@@ -35,6 +35,9 @@ pub enum FeeDistributorError {
     #[msg("Streamflow stream account invalid or not locked")]
     InvalidStreamAccount,
     
+    /// Unused: an invalid investor ATA is redirected to the fallback wallet or skipped
+    /// with `InvalidInvestorAtaSkipped`, never failed; kept so the later error codes
+    /// don't shift
     #[msg("Investor ATA does not match expected quote mint")]
     InvalidInvestorATA,
     
@@ -46,4 +49,346 @@ pub enum FeeDistributorError {
     
     #[msg("Invalid basis points value - must be <= 10000")]
     InvalidBasisPoints,
-}
\ No newline at end of file
+    
+    #[msg("Signer is not the policy authority")]
+    Unauthorized,
+    
+    #[msg("Cannot change the policy while a day's distribution is in progress")]
+    DayInProgress,
+    
+    #[msg("Fallback quote account does not belong to the policy's fallback wallet")]
+    InvalidFallbackAccount,
+    
+    #[msg("Fallback quote account required to redirect an invalid investor ATA")]
+    MissingFallbackAccount,
+    
+    #[msg("Locked snapshot for this day is not complete")]
+    SnapshotIncomplete,
+    
+    #[msg("Locked snapshot for this day is already complete")]
+    SnapshotAlreadyComplete,
+    
+    #[msg("Stream account does not match the snapshot entry for this investor")]
+    SnapshotMismatch,
+    
+    #[msg("Locked snapshot account required when distributing from a snapshot")]
+    MissingSnapshotAccount,
+    
+    #[msg("Accrual account is not this investor's accrual for the vault")]
+    InvalidAccrualAccount,
+    
+    #[msg("No accrued payout to claim")]
+    NothingToClaim,
+    
+    #[msg("Investor fee share exceeds the configured maximum")]
+    InvestorShareTooHigh,
+    
+    #[msg("Distribution interval must be between one and thirty days")]
+    InvalidDistributionInterval,
+    
+    #[msg("Fees were already claimed within the last 24 hours")]
+    ClaimNotDue,
+    
+    #[msg("Not enough remaining accounts for the page")]
+    MissingInvestorAccounts,
+    
+    #[msg("Instruction is only available in test builds")]
+    TestFeatureDisabled,
+    
+    #[msg("EqualFixed distribution requires a non-zero fixed payout")]
+    InvalidFixedPayout,
+    
+    #[msg("Day can't be closed until every investor page has been distributed")]
+    DayNotReadyToClose,
+    
+    #[msg("Maximum claim age can't be negative")]
+    InvalidClaimAge,
+    
+    #[msg("Share multiplier must be between 1 and MAX_SHARE_MULTIPLIER_BPS")]
+    InvalidShareMultiplier,
+    
+    #[msg("Too many share overrides")]
+    TooManyShareOverrides,
+    
+    #[msg("Share overrides account required while overrides are set")]
+    MissingShareOverrides,
+    
+    #[msg("Previous day was abandoned mid-pagination - settle it with force_close_day")]
+    AbandonedDayBlocked,
+    
+    #[msg("Day is not abandoned - finish it with distribute_fees or close_day")]
+    DayNotAbandoned,
+    
+    #[msg("Memo reference exceeds MAX_MEMO_REFERENCE_LEN bytes")]
+    MemoReferenceTooLong,
+    
+    #[msg("Memo program required while payout memos are enabled")]
+    MissingMemoProgram,
+    
+    #[msg("Depositor is not the policy fee source")]
+    UnauthorizedFeeSource,
+    
+    #[msg("Deposit amount must be greater than zero")]
+    InvalidDepositAmount,
+    
+    #[msg("Creator quote ATA mint does not match the policy quote mint")]
+    CreatorAtaMintMismatch,
+    
+    #[msg("Fee claiming is paused")]
+    ClaimingPaused,
+    
+    #[msg("Locked amounts and total investor allocation are in different units")]
+    AllocationUnitMismatch,
+    
+    #[msg("Payout granularity must be at least 1 lamport")]
+    InvalidPayoutGranularity,
+    
+    #[msg("Day cannot be cancelled once an investor page has been distributed")]
+    DayNotCancellable,
+    
+    #[msg("Back-pay needs DailySnapshot weights to detect newly active investors")]
+    BackpayRequiresSnapshot,
+    
+    #[msg("Treasury is no longer owned by the treasury authority PDA")]
+    TreasuryAuthorityMismatch,
+    
+    #[msg("Policy has a yield reserve but it was not provided")]
+    MissingYieldReserve,
+    
+    #[msg("Yield reserve does not match the policy's or isn't held by the treasury authority")]
+    YieldReserveMismatch,
+    
+    #[msg("Yield reserve cannot be changed while it holds principal")]
+    YieldPrincipalOutstanding,
+    
+    #[msg("Payout already claimed this distribution period")]
+    ClaimCooldownActive,
+    
+    #[msg("Daily cap or minimum payout is implausible for the quote mint's decimals")]
+    ImplausibleQuoteAmounts,
+    
+    #[msg("RemainderMode::ToInvestors requires DailySnapshot or OnChainTable weights")]
+    ToInvestorsRequiresSnapshot,
+    
+    #[msg("as_of_ts must be between now and MAX_AS_OF_HORIZON ahead")]
+    InvalidAsOfTimestamp,
+    
+    #[msg("Vault key must not be the quote mint, investor mint, creator wallet or program ID")]
+    InvalidVaultKey,
+    
+    #[msg("Policy and progress belong to different vaults")]
+    StateVaultMismatch,
+    
+    #[msg("Member vault's quote mint does not match the coordinator's")]
+    InvalidCoordinatorMember,
+    
+    #[msg("Too many coordinator members")]
+    TooManyCoordinatorMembers,
+    
+    #[msg("Coordinator member shares must add up to 100% before splitting")]
+    CoordinatorSharesIncomplete,
+    
+    #[msg("Member treasury missing or out of order")]
+    MissingCoordinatorMember,
+    
+    #[msg("No locked snapshot is stored")]
+    SnapshotEpochUnavailable,
+    
+    #[msg("DAMM pool is not accepting fee claims")]
+    DammClaimUnavailable,
+    
+    #[msg("Payout conversion needs a swap pool, a payout mint other than the quote mint and slippage within MAX_SWAP_SLIPPAGE_BPS")]
+    InvalidPayoutConversion,
+    
+    #[msg("Payout conversion requires Push distribution")]
+    PayoutConversionRequiresPush,
+    
+    #[msg("Payout treasury and swap pool accounts required to convert payouts")]
+    MissingPayoutConversionAccounts,
+    
+    #[msg("Payout treasury or swap pool does not match the policy")]
+    PayoutConversionAccountMismatch,
+    
+    #[msg("Payout conversion swap returned less than the slippage bound allows")]
+    SwapSlippageExceeded,
+    
+    #[msg("Quote mint can only be migrated with an empty treasury and nothing owed, pending or held in reserve")]
+    QuoteMintMigrationBlocked,
+    
+    #[msg("Policy rejects payouts into token accounts with a delegate")]
+    DelegatedAtaRejected,
+    
+    #[msg("Weight table account required for OnChainTable weights")]
+    MissingWeightTable,
+    
+    #[msg("Weight table is not fully written for this day")]
+    WeightTableStale,
+    
+    #[msg("Stream account does not match the weight table entry for this investor")]
+    WeightTableMismatch,
+    
+    #[msg("Every investor page has run; the deferred day is closed by close_day")]
+    DayAwaitingClose,
+    
+    #[msg("Circuit breaker tripped on an anomalous claim; the authority must reset it")]
+    CircuitBreakerTripped,
+    
+    #[msg("Circuit breaker multiple must be 0 or above 1x, and the EMA weight 1-10000")]
+    InvalidCircuitBreaker,
+    
+    #[msg("Circuit breaker is not tripped")]
+    CircuitBreakerNotTripped,
+    
+    #[msg("top_n_investors needs DailySnapshot or OnChainTable weights")]
+    TopNRequiresDayLevelWeights,
+    
+    #[msg("Opt-in account is not the investor's opt-in PDA")]
+    InvalidOptInAccount,
+    
+    #[msg("Investor has not opted in to distributions")]
+    NotOptedIn,
+    
+    #[msg("Force reset confirmation does not match")]
+    InvalidResetConfirmation,
+    
+    #[msg("Vault reached its lifetime distribution cap and is finalized")]
+    VaultFinalized,
+    
+    #[msg("Vault is not finalized")]
+    VaultNotFinalized,
+    
+    #[msg("Price oracle account does not match the policy or holds no valid price")]
+    InvalidPriceOracle,
+    
+    #[msg("Oracle price is older than the policy's max_oracle_staleness_seconds")]
+    StaleOraclePrice,
+    
+    #[msg("Oracle price confidence interval is too wide")]
+    OraclePriceUncertain,
+    
+    #[msg("USD weighting requires DailySnapshot or OnChainTable weights")]
+    UsdWeightRequiresDayLevelWeights,
+    
+    #[msg("Governance creator deposits require end-of-day creator payouts")]
+    GovernanceDepositRequiresEndOfDay,
+    
+    #[msg("Governance creator deposits require a realm and a treasury")]
+    InvalidGovernanceAccounts,
+    
+    #[msg("Payout destination balance did not rise by the amount sent")]
+    DeliveryNotConfirmed,
+    
+    #[msg("Rebuilt progress owes more than the treasury holds")]
+    ProgressExceedsTreasury,
+    
+    #[msg("Payout split needs a second ATA and a share between 1 and 9999 bps")]
+    InvalidPayoutSplit,
+    
+    #[msg("Vault was finalized with finalize_vault; only rent recovery is accepted")]
+    VaultArchived,
+    
+    #[msg("Treasury cannot be closed while pull-mode accruals are unclaimed")]
+    AccrualsOutstanding,
+    
+    #[msg("Time-weighted, blended and deposited-amount weights require DailySnapshot weights")]
+    TimeWeightingRequiresSnapshot,
+    
+    #[msg("Snapshot weighting cannot change while a snapshot for the upcoming day is being taken")]
+    SnapshotInProgress,
+    
+    #[msg("batch_status takes at most MAX_BATCH_STATUS_VAULTS progress accounts")]
+    TooManyVaults,
+    
+    #[msg("Protocol fee exceeds MAX_PROTOCOL_FEE_BPS or has no fee wallet")]
+    InvalidProtocolFee,
+    
+    #[msg("Protocol fee account missing or not a quote account of the protocol fee wallet")]
+    InvalidProtocolFeeAccount,
+    
+    #[msg("Snapshot was taken before the investor set last changed; retake it")]
+    StaleSnapshot,
+    
+    #[msg("Reconciliation tolerance exceeds the maximum")]
+    InvalidReconciliationTolerance,
+    
+    #[msg("Page needs more investor transfers than MAX_PAGE_TRANSFERS; use a smaller page")]
+    TooManyPageTransfers,
+    
+    #[msg("Too few of the page's streams are the ones the weight table lists at their index")]
+    PageQuorumNotMet,
+    
+    #[msg("Oracle staleness bound must be non-zero")]
+    InvalidOracleStaleness,
+    
+    #[msg("Withholding requires a withholding wallet")]
+    MissingWithholdingWallet,
+    
+    #[msg("Withholding wallet's quote account is required to withhold from payouts")]
+    MissingWithholdingAccount,
+    
+    #[msg("Withholding account must be a quote account of the withholding wallet")]
+    InvalidWithholdingAccount,
+    
+    #[msg("distribute_on_close requires DailySnapshot or OnChainTable weights")]
+    DistributeOnCloseRequiresDayLevelWeights,
+    
+    #[msg("Last day's weights no longer match the investor set; the residual can't be distributed on close")]
+    CloseLedgerUnavailable,
+    
+    #[msg("Vault's close distribution to investors isn't finished")]
+    CloseDistributionInProgress,
+    
+    #[msg("The policy requires the creator wallet to sign distribute_fees")]
+    CreatorSignatureRequired,
+    
+    #[msg("Withdrawal grace requires DailySnapshot weights")]
+    WithdrawalGraceRequiresSnapshot,
+    
+    #[msg("deterministic_index_order requires AccountOrder payouts, DailySnapshot or OnChainTable weights and no dust_to_last_investor")]
+    DeterministicOrderUnsupported,
+    
+    #[msg("TreasuryDraw settlement requires a non-zero settlement_rate")]
+    InvalidSettlementRate,
+    
+    #[msg("Payout treasury holds too little to settle the payout at settlement_rate")]
+    InsufficientSettlementLiquidity,
+    
+    #[msg("Governance deposit accounts are missing or not the policy's realm and treasury")]
+    InvalidGovernanceDepositAccounts,
+    
+    #[msg("Stream already holds another investor index of the snapshot")]
+    DuplicateInvestorStream,
+    
+    #[msg("External distributor accounts are missing or not the policy's program, escrow and escrow authority")]
+    InvalidDistributorAccounts,
+    
+    #[msg("top_n_investors can only rank a set of at most MAX_TOP_N_INVESTOR_SET investors")]
+    TopNInvestorSetTooLarge,
+    
+    #[msg("Burn mode needs the policy's quote mint, writable, as burn_mint")]
+    InvalidBurnMint,
+    
+    #[msg("DAMM pool account is missing or not the policy's DAMM v2 pool")]
+    InvalidDammPool,
+    
+    #[msg("Quote mint has zero supply; nothing can be distributed against it")]
+    QuoteMintInvalid,
+    
+    #[msg("Page would create more investor ATAs, or fund more of their rent, than the policy allows")]
+    AtaCreationLimitExceeded,
+    
+    #[msg("ATA creation needs at most MAX_PAGE_SIZE creations per page and a non-zero funding cap")]
+    InvalidAtaCreationLimits,
+    
+    #[msg("Creating an investor ATA needs the associated token program and the recipient wallet")]
+    MissingAtaCreationAccounts,
+    
+    #[msg("Closing day's record is missing, for another day or already written")]
+    MissingDayRecord,
+    
+    #[msg("Day records must be consecutive closed days of the vault, starting at from_epoch")]
+    DayHistoryNotContiguous,
+    
+    #[msg("Too many day records for one audit_history call")]
+    TooManyDayRecords,
+}
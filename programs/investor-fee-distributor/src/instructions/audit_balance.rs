@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Reconcile the treasury balance against the progress counters - authority only,
+/// read-only
+#[derive(Accounts)]
+pub struct AuditBalance<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+}
+
+/// Where the treasury balance should come from, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BalanceAudit {
+    /// Actual treasury balance
+    pub treasury_balance: u64,
+    /// Sum of the components below: what the counters say the treasury holds
+    pub expected_balance: u64,
+    /// `treasury_balance - expected_balance`; positive for unrecognized funds (fees
+    /// not claimed yet, direct transfers), negative for missing ones
+    pub discrepancy: i128,
+    /// Current day's claim plus the deferred pool folded into it
+    pub day_claimed: u64,
+    /// Paid or allocated to investors today, catch-up included
+    pub day_distributed_investors: u64,
+    pub day_distributed_creator: u64,
+    pub day_burned: u64,
+    /// What the open day still holds; includes `carry_over_dust`. Once completed, only
+    /// the dust held for the next day under `persist_dust_across_days`
+    pub open_day_remaining: u64,
+    /// Page dust carried within the open day, for reference
+    pub carry_over_dust: u64,
+    /// Reserved: pull-mode payouts allocated but not claimed
+    pub pull_outstanding: u64,
+    /// Reserved: claims awaiting the next distribution
+    pub pending_claim: u64,
+    /// Reserved: investor pool held back for the next day
+    pub deferred_investor_pool: u64,
+    pub day_completed: bool,
+}
+
+pub(crate) fn handler(ctx: Context<AuditBalance>) -> Result<BalanceAudit> {
+    Ok(BalanceAudit::from_progress(
+        &ctx.accounts.progress,
+        ctx.accounts.treasury.amount,
+    ))
+}
+
+impl BalanceAudit {
+    /// Break `treasury_balance` down against the progress counters
+    pub fn from_progress(progress: &DistributionProgress, treasury_balance: u64) -> Self {
+        // The same reconciliation the stale-claim drift check uses
+        let expected_balance = progress.recognized_treasury_balance();
+        let open_day_remaining = if progress.day_completed {
+            progress.persisted_dust
+        } else {
+            progress.creator_remainder()
+        };
+        
+        BalanceAudit {
+            treasury_balance,
+            expected_balance,
+            discrepancy: treasury_balance as i128 - expected_balance as i128,
+            day_claimed: progress.current_day_claimed.saturating_add(progress.current_day_deferred),
+            day_distributed_investors: progress
+                .current_day_distributed_investors
+                .saturating_add(progress.current_day_backpay),
+            day_distributed_creator: progress.current_day_distributed_creator,
+            day_burned: progress.current_day_burned,
+            open_day_remaining,
+            carry_over_dust: progress.carry_over_dust,
+            pull_outstanding: progress.pull_outstanding,
+            pending_claim: progress.current_period_claimed,
+            deferred_investor_pool: progress.deferred_investor_pool,
+            day_completed: progress.day_completed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::progress_after_day;
+
+    #[test]
+    fn balance_audit_breaks_a_mid_day_treasury_down_and_signs_the_discrepancy() {
+        let mut progress = progress_after_day(1_000_000, 300_000);
+        progress.day_completed = false;
+        progress.current_day_distributed_creator = 100_000;
+        progress.carry_over_dust = 3;
+        progress.pull_outstanding = 40_000;
+        progress.current_period_claimed = 25_000;
+
+        let audit = BalanceAudit::from_progress(&progress, 665_000);
+        assert_eq!(audit.open_day_remaining, 600_000);
+        assert_eq!(
+            audit.expected_balance,
+            audit.open_day_remaining + audit.pull_outstanding + audit.pending_claim + audit.deferred_investor_pool
+        );
+        assert_eq!(audit.expected_balance, 665_000);
+        assert_eq!(audit.discrepancy, 0);
+
+        // Missing funds show as a negative discrepancy, unrecognized ones as positive
+        assert_eq!(BalanceAudit::from_progress(&progress, 600_000).discrepancy, -65_000);
+        assert_eq!(BalanceAudit::from_progress(&progress, 700_000).discrepancy, 35_000);
+
+        // A completed day holds nothing but the reserves
+        progress.day_completed = true;
+        let audit = BalanceAudit::from_progress(&progress, 65_000);
+        assert_eq!(audit.open_day_remaining, 0);
+        assert_eq!(audit.discrepancy, 0);
+    }
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Reconcile a run of closed days from their day records - read-only
+#[derive(Accounts)]
+pub struct AuditHistory<'info> {
+    /// The vault identifier
+    /// CHECK: Only matched against the records' vault
+    pub vault: UncheckedAccount<'info>,
+    
+    // Remaining accounts:
+    // Up to MAX_AUDIT_HISTORY_DAYS DayRecord accounts of the vault, for consecutive
+    // closed days starting at `from_epoch`
+}
+
+/// Totals of a run of days, returned via return data. They always satisfy
+/// `claimed + carried_in == distributed_investors + distributed_creator + burned +
+/// carried_out + discrepancy`. Runs chain: the next call starts at `next_epoch`, and
+/// the fields of all calls add up.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HistoryAudit {
+    /// First day audited
+    pub from_epoch: u64,
+    /// Day after the last one audited; where the next call starts
+    pub next_epoch: u64,
+    pub days: u32,
+    /// Claims the days started with
+    pub claimed: u64,
+    /// Brought into each day by the day before it
+    pub carried_in: u64,
+    pub distributed_investors: u64,
+    pub distributed_creator: u64,
+    pub burned: u64,
+    /// Left by each day for the day after it
+    pub carried_out: u64,
+    /// Sum of each day's `DayRecord::discrepancy`: what came in and was neither paid,
+    /// burned nor carried, or the reverse when negative
+    pub discrepancy: i128,
+    /// First day whose own totals don't balance
+    pub first_unbalanced_epoch: Option<u64>,
+}
+
+impl HistoryAudit {
+    /// Add the next record of the run. It must be the vault's, closed and for
+    /// `next_epoch`.
+    pub fn add(&mut self, vault: &Pubkey, record: &DayRecord) -> Result<()> {
+        require!(
+            record.vault == *vault && record.closed && record.day_epoch == self.next_epoch,
+            FeeDistributorError::DayHistoryNotContiguous
+        );
+        
+        let add = |total: u64, amount: u64| total.checked_add(amount).ok_or(FeeDistributorError::ArithmeticOverflow);
+        self.claimed = add(self.claimed, record.claimed)?;
+        self.carried_in = add(self.carried_in, record.carried_in)?;
+        self.distributed_investors = add(self.distributed_investors, record.distributed_investors)?;
+        self.distributed_creator = add(self.distributed_creator, record.distributed_creator)?;
+        self.burned = add(self.burned, record.burned)?;
+        self.carried_out = add(self.carried_out, record.carried_out)?;
+        
+        let discrepancy = record.discrepancy();
+        if discrepancy != 0 && self.first_unbalanced_epoch.is_none() {
+            self.first_unbalanced_epoch = Some(record.day_epoch);
+        }
+        self.discrepancy += discrepancy;
+        self.days += 1;
+        self.next_epoch = add(self.next_epoch, 1)?;
+        Ok(())
+    }
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AuditHistory<'info>>,
+    from_epoch: u64,
+) -> Result<HistoryAudit> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_AUDIT_HISTORY_DAYS,
+        FeeDistributorError::TooManyDayRecords
+    );
+    
+    let vault = ctx.accounts.vault.key();
+    let mut audit = HistoryAudit {
+        from_epoch,
+        next_epoch: from_epoch,
+        ..Default::default()
+    };
+    // Owner and discriminator are checked, so only this program's records are read
+    for info in ctx.remaining_accounts {
+        let record = Account::<DayRecord>::try_from(info)?;
+        audit.add(&vault, &record)?;
+    }
+    
+    Ok(audit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::progress_after_day;
+
+    fn record_for(vault: Pubkey, day_epoch: u64, progress: &DistributionProgress, carried_in: u64) -> DayRecord {
+        let mut record = DayRecord {
+            vault,
+            day_epoch,
+            closed: false,
+            claimed: 0,
+            carried_in: 0,
+            distributed_investors: 0,
+            distributed_creator: 0,
+            burned: 0,
+            carried_out: 0,
+            closed_ts: 0,
+            bump: 0,
+        };
+        record.record(progress, carried_in, 0, 1_000).unwrap();
+        record
+    }
+
+    #[test]
+    fn history_audit_sums_day_records_and_flags_the_first_short_day() {
+        let vault = Pubkey::new_unique();
+        // Day 1 pays 300 to investors and the 700 left to the creator
+        let mut progress = progress_after_day(1_000, 300);
+        progress.current_day_distributed_creator = 700;
+        let first = record_for(vault, 1, &progress, 0);
+        assert_eq!(first.discrepancy(), 0);
+
+        // Day 2 defers its 400 pool below the minimum and pays the creator 600
+        progress = progress_after_day(1_000, 0);
+        progress.deferred_investor_pool = 400;
+        progress.current_day_distributed_creator = 600;
+        let second = record_for(vault, 2, &progress, 0);
+        assert_eq!(second.carried_out, 400);
+        assert_eq!(second.discrepancy(), 0);
+
+        // Day 3 starts with the deferred 400 and pays 1,300 of its 1,400; the treasury
+        // was 100 short of the creator's remainder
+        progress = progress_after_day(1_000, 500);
+        progress.current_day_deferred = 400;
+        progress.current_day_distributed_creator = 800;
+        let third = record_for(vault, 3, &progress, progress.day_carried_in());
+        assert_eq!(third.carried_in, 400);
+        assert_eq!(third.discrepancy(), 100);
+
+        let mut audit = HistoryAudit { from_epoch: 1, next_epoch: 1, ..Default::default() };
+        for record in [&first, &second, &third] {
+            audit.add(&vault, record).unwrap();
+        }
+        assert_eq!(audit.days, 3);
+        assert_eq!(audit.next_epoch, 4);
+        assert_eq!(audit.claimed, 3_000);
+        assert_eq!(audit.distributed_investors + audit.distributed_creator, 2_900);
+        // Day 2's carry out is day 3's carry in
+        assert_eq!((audit.carried_in, audit.carried_out), (400, 400));
+        assert_eq!(audit.discrepancy, 100);
+        assert_eq!(audit.first_unbalanced_epoch, Some(3));
+
+        // A chained call picks up where this one stopped
+        let mut chained = HistoryAudit { from_epoch: 1, next_epoch: 1, ..Default::default() };
+        chained.add(&vault, &first).unwrap();
+        chained.add(&vault, &second).unwrap();
+        let next = chained.next_epoch;
+        let mut rest = HistoryAudit { from_epoch: next, next_epoch: next, ..Default::default() };
+        rest.add(&vault, &third).unwrap();
+        assert_eq!(chained.claimed + rest.claimed, audit.claimed);
+        assert_eq!(chained.discrepancy + rest.discrepancy, audit.discrepancy);
+
+        // A gap, a repeat, another vault's day or an unwritten record breaks the run
+        let mut gapped = HistoryAudit { from_epoch: 1, next_epoch: 1, ..Default::default() };
+        gapped.add(&vault, &first).unwrap();
+        for broken in [
+            third,
+            first,
+            record_for(Pubkey::new_unique(), 2, &progress, 0),
+            DayRecord { closed: false, ..second },
+        ] {
+            assert_eq!(
+                gapped.add(&vault, &broken).unwrap_err(),
+                FeeDistributorError::DayHistoryNotContiguous.into()
+            );
+        }
+    }
+
+    #[test]
+    fn a_recording_close_needs_the_running_days_unwritten_record() {
+        use crate::instructions::distribute::write_day_record;
+        use crate::test_fixtures::policy_with_min_payout;
+
+        let mut policy = policy_with_min_payout(5_000, 0);
+        let mut progress = progress_after_day(1_000, 1_000);
+        progress.latest_epoch = 5;
+        // Without the flag a close needs no record
+        write_day_record(&policy, &progress, None, 0, 0, 1_000).unwrap();
+
+        policy.record_day_history = true;
+        let missing = write_day_record(&policy, &progress, None, 0, 0, 1_000);
+        assert_eq!(missing.unwrap_err(), FeeDistributorError::MissingDayRecord.into());
+        let mut other_day = record_for(Pubkey::default(), 4, &progress, 0);
+        other_day.closed = false;
+        let wrong = write_day_record(&policy, &progress, Some(&mut other_day), 0, 0, 1_000);
+        assert_eq!(wrong.unwrap_err(), FeeDistributorError::MissingDayRecord.into());
+
+        let mut record = DayRecord { day_epoch: 5, ..other_day };
+        write_day_record(&policy, &progress, Some(&mut record), 0, 0, 1_000).unwrap();
+        assert!(record.closed);
+        assert_eq!(record.distributed_investors, 1_000);
+        // A record is written once
+        let rewrite = write_day_record(&policy, &progress, Some(&mut record), 0, 0, 2_000);
+        assert_eq!(rewrite.unwrap_err(), FeeDistributorError::MissingDayRecord.into());
+    }
+}
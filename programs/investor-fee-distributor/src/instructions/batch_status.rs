@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Read the key status fields of several vaults at once - read-only
+#[derive(Accounts)]
+pub struct BatchStatus<'info> {
+    /// Never invoked; gives the accounts a lifetime, which the generated CPI client
+    /// requires, and resolves without the caller passing it
+    pub system_program: Program<'info, System>,
+    
+    // Remaining accounts:
+    // Up to MAX_BATCH_STATUS_VAULTS DistributionProgress accounts, of any vaults
+}
+
+/// One vault's status, packed into return data in the order the progress accounts
+/// were passed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VaultStatus {
+    pub vault: Pubkey,
+    /// Most recently started day
+    pub latest_epoch: u64,
+    pub last_distribution_ts: i64,
+    pub last_claim_ts: i64,
+    /// Next investor index of the current day
+    pub pagination_cursor: u32,
+    pub total_investors: u32,
+    pub day_completed: bool,
+    pub vault_finalized: bool,
+    pub circuit_breaker: CircuitBreakerState,
+    /// Pull-mode payouts allocated but not claimed
+    pub pull_outstanding: u64,
+    /// Claims awaiting the next distribution
+    pub pending_claim: u64,
+    pub lifetime_distributed: u64,
+}
+
+impl VaultStatus {
+    /// Borsh size of one packed status
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 4 + 4 + 1 + 1 + 1 + 8 + 8 + 8;
+    
+    pub fn from_progress(progress: &DistributionProgress) -> Self {
+        VaultStatus {
+            vault: progress.vault,
+            latest_epoch: progress.latest_epoch,
+            last_distribution_ts: progress.last_distribution_ts,
+            last_claim_ts: progress.last_claim_ts,
+            pagination_cursor: progress.pagination_cursor,
+            total_investors: progress.total_investors,
+            day_completed: progress.day_completed,
+            vault_finalized: progress.vault_finalized,
+            circuit_breaker: progress.circuit_breaker,
+            pull_outstanding: progress.pull_outstanding,
+            pending_claim: progress.current_period_claimed,
+            lifetime_distributed: progress.lifetime_distributed,
+        }
+    }
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchStatus<'info>>,
+) -> Result<Vec<VaultStatus>> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_BATCH_STATUS_VAULTS,
+        FeeDistributorError::TooManyVaults
+    );
+    
+    // Owner and discriminator are checked, so only this program's progress accounts
+    // are read
+    ctx.remaining_accounts
+        .iter()
+        .map(|info| {
+            let progress = Account::<DistributionProgress>::try_from(info)?;
+            Ok(VaultStatus::from_progress(&progress))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::progress_after_day;
+
+    #[test]
+    fn full_status_batch_fits_in_return_data() {
+        use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+
+        let mut progress = progress_after_day(1_000, 400);
+        progress.circuit_breaker = CircuitBreakerState::Tripped;
+        let status = VaultStatus::from_progress(&progress);
+        assert_eq!(status.try_to_vec().unwrap().len(), VaultStatus::LEN);
+
+        let batch = vec![status; MAX_BATCH_STATUS_VAULTS];
+        let packed = batch.try_to_vec().unwrap();
+        assert_eq!(packed.len(), 4 + MAX_BATCH_STATUS_VAULTS * VaultStatus::LEN);
+        assert!(packed.len() <= MAX_RETURN_DATA);
+        // One more wouldn't fit
+        assert!(packed.len() + VaultStatus::LEN > MAX_RETURN_DATA);
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Drop the claim awaiting distribution before any investor is paid - authority only
+#[derive(Accounts)]
+pub struct CancelDay<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+pub(crate) fn handler(ctx: Context<CancelDay>) -> Result<()> {
+    let progress = &mut ctx.accounts.progress;
+    
+    // Once a page has run, investors may hold part of the day's claim
+    require!(!progress.is_mid_day(), FeeDistributorError::DayNotCancellable);
+    
+    // The funds stay in the treasury unrecognized, so the next claim picks them up
+    let reclaimed_amount = progress.cancel_pending_claim();
+    
+    emit!(DayCancelled {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        reclaimed_amount,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct DayCancelled {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Claimed amount returned to the treasury's unrecognized balance
+    pub reclaimed_amount: u64,
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{
+        claim_fees_from_damm, collect_protocol_fee, damm_claim_available, realize_reserve_yield,
+    },
+    state::*,
+};
+
+/// Claim the day's fees into the treasury without distributing - permissionless
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    /// Permissionless caller
+    pub caller: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA (signs yield redemptions)
+    /// CHECK: PDA that owns the treasury and the yield reserve
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    /// Yield-bearing reserve (required while the policy has one)
+    #[account(mut)]
+    pub yield_reserve: Option<Account<'info, TokenAccount>>,
+    
+    /// Protocol fee wallet's quote token account (required when the policy takes a
+    /// protocol fee and a claim is made)
+    #[account(
+        mut,
+        token::mint = policy.quote_mint,
+        constraint = protocol_fee_ata.owner == policy.protocol_fee_wallet
+            @ FeeDistributorError::InvalidProtocolFeeAccount,
+    )]
+    pub protocol_fee_ata: Option<Account<'info, TokenAccount>>,
+    
+    /// The policy's DAMM v2 pool, read for its status (required while the policy names one)
+    /// CHECK: Matched against the policy's pool and the DAMM v2 program
+    pub damm_pool: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handler(ctx: Context<ClaimFees>) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    
+    require!(!ctx.accounts.policy.claim_paused, FeeDistributorError::ClaimingPaused);
+    require!(
+        damm_claim_available(&ctx.accounts.policy, ctx.accounts.damm_pool.as_deref())?,
+        FeeDistributorError::DammClaimUnavailable
+    );
+    require!(
+        ctx.accounts.progress.is_claim_due(current_ts),
+        FeeDistributorError::ClaimNotDue
+    );
+    
+    // Claimed fees wait in the treasury until the next distribution day starts.
+    // The DAMM claim is read before yield lands in the treasury so it isn't counted twice
+    let claim = claim_fees_from_damm(
+        &ctx.accounts.policy,
+        &mut ctx.accounts.treasury,
+        &mut ctx.accounts.progress,
+    )?;
+    let claimed_amount = collect_protocol_fee(
+        &ctx.accounts.policy,
+        &ctx.accounts.treasury,
+        ctx.accounts.treasury_authority.to_account_info(),
+        ctx.accounts.protocol_fee_ata.as_ref(),
+        ctx.accounts.token_program.to_account_info(),
+        claim.amount,
+    )?;
+    let claimed_amount = realize_reserve_yield(
+        &ctx.accounts.policy,
+        &mut ctx.accounts.progress,
+        &ctx.accounts.treasury,
+        ctx.accounts.treasury_authority.to_account_info(),
+        ctx.accounts.yield_reserve.as_ref(),
+        ctx.accounts.token_program.to_account_info(),
+        claimed_amount,
+    )?;
+    
+    ctx.accounts.progress.record_claim(claimed_amount, current_ts)?;
+    
+    emit!(claim.into_event(ctx.accounts.vault.key(), claimed_amount, current_ts));
+    
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::treasury_authority_seeds,
+    state::*,
+};
+
+/// Withdraw an investor's accrued pull-mode payouts
+#[derive(Accounts)]
+pub struct ClaimMyPayout<'info> {
+    /// Investor recorded as the accrual's owner
+    pub investor: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Investor accrual PDA
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), ACCRUAL_SEED, accrual.stream.as_ref()],
+        bump = accrual.bump,
+        has_one = vault,
+        constraint = accrual.owner == investor.key() @ FeeDistributorError::Unauthorized,
+    )]
+    pub accrual: Account<'info, InvestorAccrual>,
+    
+    /// Investor's opt-in PDA (required while the policy requires opt-in)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), OPT_IN_SEED, accrual.stream.as_ref()],
+        bump = opt_in.bump,
+        has_one = vault,
+    )]
+    pub opt_in: Option<Account<'info, OptInRegistry>>,
+    
+    /// Program's quote treasury
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA, checked against the bump the policy recorded
+    /// CHECK: PDA that owns the treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump = policy.treasury_authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    /// Investor's quote token account receiving the payout
+    #[account(
+        mut,
+        token::mint = policy.quote_mint,
+        token::authority = investor,
+    )]
+    pub investor_quote_ata: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handler(ctx: Context<ClaimMyPayout>) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let period_start = ctx.accounts.progress.last_distribution_ts;
+    
+    if ctx.accounts.policy.pull_claim_cooldown {
+        require!(
+            !ctx.accounts.accrual.is_cooling_down(
+                period_start,
+                current_ts,
+                ctx.accounts.policy.distribution_interval,
+            ),
+            FeeDistributorError::ClaimCooldownActive
+        );
+    }
+    
+    // Accruals keep building up before the opt-in; they just can't be withdrawn
+    require!(
+        !ctx.accounts.policy.require_opt_in || ctx.accounts.opt_in.is_some(),
+        FeeDistributorError::NotOptedIn
+    );
+    
+    let amount = ctx.accounts.accrual.claimable;
+    require!(amount > 0, FeeDistributorError::NothingToClaim);
+    // The investor signs here, so they can clear the delegate and retry
+    require!(
+        !ctx.accounts.policy.reject_delegated_atas
+            || ctx.accounts.investor_quote_ata.delegate.is_none(),
+        FeeDistributorError::DelegatedAtaRejected
+    );
+    // The transfer is signed by the PDA, so it must still own the treasury
+    require_keys_eq!(
+        ctx.accounts.treasury.owner,
+        ctx.accounts.treasury_authority.key(),
+        FeeDistributorError::TreasuryAuthorityMismatch
+    );
+    
+    let vault_key = ctx.accounts.vault.key();
+    let bump = [ctx.accounts.policy.treasury_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+    
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.investor_quote_ata.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    
+    let accrual = &mut ctx.accounts.accrual;
+    accrual.claimable = 0;
+    accrual.total_claimed = accrual
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    // Recorded whether or not the cooldown is on, so enabling it takes effect at once
+    accrual.last_claim_ts = current_ts;
+    accrual.last_claim_period = period_start;
+    
+    let progress = &mut ctx.accounts.progress;
+    progress.pull_outstanding = progress.pull_outstanding.saturating_sub(amount);
+    
+    emit!(PayoutClaimed {
+        vault: vault_key,
+        investor: ctx.accounts.investor.key(),
+        stream: accrual.stream,
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct PayoutClaimed {
+    pub vault: Pubkey,
+    pub investor: Pubkey,
+    pub stream: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,256 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{
+        announce_day_close, burn_from_treasury, close_day_amounts, treasury_authority_seeds,
+        creator_remainder_destination, emit_day_summary, settle_residual, write_day_record, AmountBurned,
+        CreatorDepositedToGovernance, CreatorPayoutDayClosed, CreatorPayoutPage,
+    },
+    state::*,
+};
+
+/// Pay the creator remainder and complete the day after the final investor page -
+/// permissionless, used when the policy defers the creator close
+#[derive(Accounts)]
+pub struct CloseDay<'info> {
+    /// Permissionless caller
+    pub caller: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = quote_mint,
+        has_one = creator_wallet,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Quote token mint
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// Program's quote treasury
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = quote_mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA, checked against the bump the policy recorded
+    /// CHECK: PDA that owns the treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump = policy.treasury_authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    /// Creator's quote token account (receives remainder)
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = creator_wallet,
+    )]
+    pub creator_quote_ata: Account<'info, TokenAccount>,
+    
+    /// Creator wallet
+    /// CHECK: Validated in policy
+    pub creator_wallet: UncheckedAccount<'info>,
+    
+    /// The quote mint again, writable, for burning the undistributable residual
+    /// (required in `Burn` mode when the day closes with a residual); kept separate so
+    /// other days don't write-lock a shared mint
+    #[account(
+        mut,
+        address = policy.quote_mint @ FeeDistributorError::InvalidBurnMint,
+    )]
+    pub burn_mint: Option<Account<'info, Mint>>,
+    
+    /// SPL Governance program that owns the policy's realm (required for a
+    /// `Governance` creator deposit)
+    /// CHECK: Must own the policy's realm and be executable
+    pub governance_program: Option<UncheckedAccount<'info>>,
+    
+    /// The DAO's realm
+    /// CHECK: Matched against the policy's governance realm
+    pub governance_realm: Option<UncheckedAccount<'info>>,
+    
+    /// The DAO treasury's quote token account the remainder is deposited into
+    #[account(mut)]
+    pub governance_treasury: Option<Account<'info, TokenAccount>>,
+    
+    /// The closing day's record (required when the policy sets
+    /// `record_day_history`)
+    #[account(mut, has_one = vault)]
+    pub day_record: Option<Account<'info, DayRecord>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseDay>) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let progress = &ctx.accounts.progress;
+    
+    require!(!progress.day_completed, FeeDistributorError::DayAlreadyCompleted);
+    require!(progress.is_awaiting_close(), FeeDistributorError::DayNotReadyToClose);
+    
+    settle_day(ctx.accounts, current_ts)?;
+    
+    Ok(())
+}
+
+/// Burn the residual, pay the creator whatever the day still owes and mark it completed.
+/// Investors not reached yet (a `force_close_day`) are covered by the remainder too.
+/// Returns the creator amount paid by this call.
+pub(crate) fn settle_day(accounts: &mut CloseDay, current_ts: i64) -> Result<u64> {
+    // Same runtime checks as distribute_fees
+    require_keys_eq!(
+        accounts.treasury.owner,
+        accounts.treasury_authority.key(),
+        FeeDistributorError::TreasuryAuthorityMismatch
+    );
+    require_keys_eq!(
+        accounts.creator_quote_ata.mint,
+        accounts.policy.quote_mint,
+        FeeDistributorError::CreatorAtaMintMismatch
+    );
+    
+    let carried_in = accounts.progress.day_carried_in();
+    let close = close_day_amounts(
+        &accounts.policy,
+        &mut accounts.progress,
+        accounts.treasury.amount,
+        current_ts,
+    )?;
+    announce_day_close(&accounts.policy, &accounts.progress, &close);
+    let (remainder, burned) = (close.remainder, close.burned);
+    
+    if burned > 0 {
+        let burn_mint = accounts.burn_mint.as_ref().ok_or(FeeDistributorError::InvalidBurnMint)?;
+        burn_from_treasury(
+            accounts.token_program.to_account_info(),
+            burn_mint.to_account_info(),
+            accounts.treasury.to_account_info(),
+            accounts.treasury_authority.to_account_info(),
+            &accounts.vault.key(),
+            accounts.policy.treasury_authority_bump,
+            burned,
+        )?;
+        
+        emit!(AmountBurned {
+            vault: accounts.vault.key(),
+            amount: burned,
+        });
+    }
+    
+    if remainder > 0 {
+        let vault_key = accounts.vault.key();
+        let bump = [accounts.policy.treasury_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+        let (destination, to_governance) = creator_remainder_destination(
+            &accounts.policy,
+            accounts.governance_program.as_deref(),
+            accounts.governance_realm.as_deref(),
+            accounts.governance_treasury.as_ref(),
+            accounts.creator_quote_ata.to_account_info(),
+        )?;
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: accounts.treasury.to_account_info(),
+                    to: destination,
+                    authority: accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            remainder,
+        )?;
+        
+        if to_governance {
+            emit!(CreatorDepositedToGovernance {
+                vault: vault_key,
+                realm: accounts.policy.governance_realm,
+                amount: remainder,
+            });
+        }
+    }
+    
+    let progress = &mut accounts.progress;
+    progress.current_day_distributed_creator = progress
+        .current_day_distributed_creator
+        .checked_add(remainder)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    progress.current_day_burned = progress
+        .current_day_burned
+        .checked_add(burned)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    progress.day_completed = true;
+    
+    if accounts.policy.creator_payout_mode == CreatorPayoutMode::PerPage {
+        emit!(CreatorPayoutPage {
+            creator: accounts.policy.creator_wallet,
+            page_start: progress.pagination_cursor,
+            page_end: progress.total_investors,
+            amount: remainder,
+            cumulative_amount: progress.current_day_distributed_creator,
+        });
+    }
+    
+    emit!(CreatorPayoutDayClosed {
+        creator: accounts.policy.creator_wallet,
+        amount: progress.current_day_distributed_creator,
+        day_timestamp: current_ts,
+        rounding_held: close.rounding_held,
+    });
+    emit_day_summary(&accounts.policy, progress, accounts.vault.key());
+    write_day_record(
+        &accounts.policy,
+        progress,
+        accounts.day_record.as_deref_mut(),
+        carried_in,
+        close.rounding_held,
+        current_ts,
+    )?;
+    
+    // The remainder and any burn left the treasury above
+    accounts.treasury.reload()?;
+    let swept = settle_residual(&accounts.policy, &mut accounts.progress, accounts.treasury.amount)?;
+    if swept > 0 {
+        let vault_key = accounts.vault.key();
+        let bump = [accounts.policy.treasury_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: accounts.treasury.to_account_info(),
+                    to: accounts.creator_quote_ata.to_account_info(),
+                    authority: accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept,
+        )?;
+    }
+    
+    Ok(remainder)
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Close a simulation scratch account, returning its rent - owner only
+#[derive(Accounts)]
+pub struct CloseDaySimulation<'info> {
+    /// The simulation's owner; receives the rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// The owner's simulation scratch PDA
+    #[account(
+        mut,
+        close = owner,
+        seeds = [VAULT_SEED, vault.key().as_ref(), DAY_SIMULATION_SEED, owner.key().as_ref()],
+        bump = day_simulation.bump,
+        has_one = vault,
+        has_one = owner @ FeeDistributorError::Unauthorized,
+    )]
+    pub day_simulation: Account<'info, DaySimulation>,
+}
+
+pub(crate) fn handler(_ctx: Context<CloseDaySimulation>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Deposit externally collected fees into the treasury and recognize them as the
+/// day's claim - policy `fee_source` only
+#[derive(Accounts)]
+pub struct DepositAndRecognize<'info> {
+    /// Authorized fee source
+    pub depositor: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        constraint = policy.fee_source == Some(depositor.key())
+            @ FeeDistributorError::UnauthorizedFeeSource,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Depositor's quote token account the fees are taken from
+    #[account(
+        mut,
+        token::mint = policy.quote_mint,
+        token::authority = depositor,
+    )]
+    pub depositor_quote_ata: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handler(ctx: Context<DepositAndRecognize>, amount: u64) -> Result<()> {
+    require!(amount > 0, FeeDistributorError::InvalidDepositAmount);
+    
+    let current_ts = Clock::get()?.unix_timestamp;
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.depositor_quote_ata.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    // Recorded like a DAMM claim: it waits for the next distribution day, and the
+    // day's DAMM claim is skipped because the claim is no longer due
+    ctx.accounts.progress.record_claim(amount, current_ts)?;
+    
+    emit!(FeesRecognized {
+        source: ctx.accounts.depositor.key(),
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct FeesRecognized {
+    pub source: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::clamp_page_size,
+    math::*,
+    state::*,
+};
+
+/// Estimate the cost of cranking a full day - read-only
+#[derive(Accounts)]
+pub struct EstimateCrankCost<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+/// Approximate cost of one full day, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CrankCostEstimate {
+    /// `distribute_fees` calls needed at the (clamped) page size
+    pub num_pages: u64,
+    /// Every transaction of the day: pages, plus snapshot pages and `close_day` if used
+    pub num_transactions: u64,
+    /// Approximate compute across all of those transactions
+    pub estimated_total_cu: u64,
+    /// Signature fees plus the priority fee at the given compute unit price
+    pub approx_lamports: u64,
+}
+
+pub(crate) fn handler(
+    ctx: Context<EstimateCrankCost>,
+    page_size: u8,
+    compute_unit_price_micro_lamports: u64,
+) -> Result<CrankCostEstimate> {
+    day_crank_estimate(
+        &ctx.accounts.policy,
+        &ctx.accounts.progress,
+        page_size,
+        compute_unit_price_micro_lamports,
+    )
+}
+
+/// The cost of cranking the next (or running) day for the vault's investor set under
+/// the policy's current settings
+pub(crate) fn day_crank_estimate(
+    policy: &DistributionPolicy,
+    progress: &DistributionProgress,
+    page_size: u8,
+    compute_unit_price_micro_lamports: u64,
+) -> Result<CrankCostEstimate> {
+    let total_investors = progress.total_investors as u64;
+    
+    // Same clamping as distribute_fees, so the estimate matches what the crank will run
+    let page_size = clamp_page_size(policy, page_size)? as u64;
+    let num_pages = pages_needed(total_investors, page_size);
+    
+    let split_transfers = if policy.distribution_mode == DistributionMode::Push {
+        policy.payout_split_count as u64
+    } else {
+        0
+    };
+    let creator_legs = match policy.creator_payout_mode {
+        CreatorPayoutMode::EndOfDay => 1,
+        CreatorPayoutMode::PerPage => num_pages,
+    };
+    
+    let installment_transfers = day_installment_transfers(policy, progress, num_pages)?;
+    
+    let mut num_transactions = num_pages;
+    let mut estimated_total_cu = [
+        num_pages.checked_mul(page_base_cu(policy)),
+        total_investors.checked_mul(per_investor_cu(policy)),
+        creator_legs.checked_mul(CRANK_CREATOR_LEG_CU),
+        split_transfers.checked_mul(split_transfer_cu(policy)),
+        installment_transfers.checked_mul(CRANK_INSTALLMENT_TRANSFER_CU),
+    ]
+    .into_iter()
+    .try_fold(0u64, |total, cu| total.checked_add(cu?))
+    .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    
+    if policy.weight_source == WeightSource::DailySnapshot {
+        // snapshot_page reads streams in pages of the same size
+        num_transactions += num_pages;
+        estimated_total_cu = num_pages
+            .checked_mul(CRANK_BASE_CU)
+            .and_then(|base| base.checked_add(total_investors.checked_mul(SNAPSHOT_PER_INVESTOR_CU)?))
+            .and_then(|snapshot_cu| estimated_total_cu.checked_add(snapshot_cu))
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    }
+    
+    if policy.defer_creator_close {
+        // The creator leg moves to close_day rather than being added
+        num_transactions += 1;
+        estimated_total_cu = estimated_total_cu
+            .checked_add(CRANK_BASE_CU)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    }
+    
+    let approx_lamports = num_transactions
+        .checked_mul(LAMPORTS_PER_SIGNATURE)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        .checked_add(priority_fee_lamports(
+            estimated_total_cu,
+            compute_unit_price_micro_lamports,
+        )?)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    
+    Ok(CrankCostEstimate {
+        num_pages,
+        num_transactions,
+        estimated_total_cu,
+        approx_lamports,
+    })
+}
+
+/// Installment transfers the day adds beyond each payout's first: at most one per
+/// `max_transfer_size` of the day's investor pool, and no more than its pages can
+/// make within `MAX_PAGE_TRANSFERS`
+fn day_installment_transfers(
+    policy: &DistributionPolicy,
+    progress: &DistributionProgress,
+    num_pages: u64,
+) -> Result<u64> {
+    if !pays_in_installments(policy) {
+        return Ok(0);
+    }
+    
+    // The running day's claim, or the pending one the next day starts from
+    let claimed = if progress.is_mid_day() {
+        progress.current_day_claimed
+    } else {
+        progress.current_period_claimed
+    };
+    let investor_pool = apply_bps(claimed, policy.effective_investor_share_bps() as u64)?;
+    let page_room = num_pages
+        .checked_mul(MAX_PAGE_TRANSFERS as u64)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        .saturating_sub(progress.total_investors as u64);
+    Ok(std::cmp::min(investor_pool.div_ceil(policy.max_transfer_size), page_room))
+}
+
+/// Whether pushed quote payouts above `max_transfer_size` are split into installments;
+/// converted and delegated payouts are always a single transfer
+pub(crate) fn pays_in_installments(policy: &DistributionPolicy) -> bool {
+    policy.max_transfer_size > 0
+        && policy.distribution_mode == DistributionMode::Push
+        && policy.payout_mint.is_none()
+        && policy.distributor_program.is_none()
+}
+
+/// Whether pushed payouts are handed to an external distributor
+fn delegates_payouts(policy: &DistributionPolicy) -> bool {
+    policy.distributor_program.is_some()
+        && policy.distribution_mode == DistributionMode::Push
+        && policy.payout_mint.is_none()
+}
+
+/// Approximate compute of a distribute_fees page before any investor: the call itself,
+/// plus deriving the distributor's escrow authority when payouts are delegated
+pub(crate) fn page_base_cu(policy: &DistributionPolicy) -> u64 {
+    let distributor_cu = if delegates_payouts(policy) { CRANK_FIND_PDA_CU } else { 0 };
+    
+    CRANK_BASE_CU + distributor_cu
+}
+
+/// Approximate compute of one investor in a distribute_fees page under the policy's
+/// current settings: the stream read and payout, plus the memo, swap, distributor CPI,
+/// delivery check and withholding transfer each payout carries
+pub(crate) fn per_investor_cu(policy: &DistributionPolicy) -> u64 {
+    let push = policy.distribution_mode == DistributionMode::Push;
+    let memo_cu = if policy.attach_memo && push { CRANK_MEMO_CU } else { 0 };
+    let swap_cu = if policy.payout_mint.is_some() && push { CRANK_SWAP_CU } else { 0 };
+    let distributor_cu = if delegates_payouts(policy) { CRANK_DISTRIBUTOR_CPI_CU } else { 0 };
+    let withholding_cu = if policy.withholding_bps > 0 {
+        CRANK_WITHHOLDING_TRANSFER_CU
+    } else {
+        0
+    };
+    
+    CRANK_PER_INVESTOR_CU + memo_cu + swap_cu + distributor_cu + delivery_check_cu(policy) + withholding_cu
+}
+
+/// Approximate compute of a split payout's second transfer, its delivery check included
+pub(crate) fn split_transfer_cu(policy: &DistributionPolicy) -> u64 {
+    CRANK_SPLIT_TRANSFER_CU + delivery_check_cu(policy)
+}
+
+fn delivery_check_cu(policy: &DistributionPolicy) -> u64 {
+    if policy.confirm_deliveries && policy.distribution_mode == DistributionMode::Push {
+        CRANK_DELIVERY_CHECK_CU
+    } else {
+        0
+    }
+}
@@ -0,0 +1,415 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{
+        day_ledger, is_valid_investor_ata, read_streamflow_recipient, treasury_authority_seeds,
+        InvalidInvestorAtaSkipped,
+    },
+    math::*,
+    state::*,
+};
+
+/// Archive a vault for good, sweeping what it doesn't owe investors to the creator and
+/// optionally closing its progress and treasury - authority only
+///
+/// Once finalized the vault accepts no further day, claim or policy change. Pull-mode
+/// accruals stay claimable; calling again with `close_accounts` once they are all
+/// claimed returns the remaining rent.
+///
+/// Under `distribute_on_close` the residual is first paid to investors by the last
+/// day's weights: the first call sets it aside, and each following call pays the next
+/// page of investors (remaining accounts) until the set is done. Only then is what is
+/// left swept to the creator and may the accounts be closed.
+#[derive(Accounts)]
+pub struct FinalizeVault<'info> {
+    /// Policy authority; receives the rent of closed accounts
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy; kept, so the vault stays archived under its address
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        has_one = creator_wallet,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA, checked against the bump the policy recorded
+    /// CHECK: PDA that owns the treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump = policy.treasury_authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    /// Creator's quote ATA, receiving the residual treasury balance
+    #[account(
+        mut,
+        token::mint = policy.quote_mint,
+        token::authority = creator_wallet,
+    )]
+    pub creator_quote_ata: Account<'info, TokenAccount>,
+    
+    /// CHECK: Matched against the policy's creator wallet
+    pub creator_wallet: UncheckedAccount<'info>,
+    
+    /// Locked snapshot ledger the close distribution weighs investors by (required
+    /// under `distribute_on_close` with DailySnapshot weights)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SNAPSHOT_SEED],
+        bump = locked_snapshot.bump,
+        has_one = vault,
+    )]
+    pub locked_snapshot: Option<Account<'info, LockedSnapshot>>,
+    
+    /// Committed weight table the close distribution weighs investors by (required
+    /// under `distribute_on_close` with OnChainTable weights)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump = weight_table.bump,
+        has_one = vault,
+    )]
+    pub weight_table: Option<Account<'info, WeightTable>>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    // Remaining accounts, while a close distribution is under way:
+    // [investor_quote_ata, stream_account] pairs from close_cursor, up to MAX_PAGE_SIZE
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeVault<'info>>,
+    close_accounts: bool,
+) -> Result<()> {
+    let already_finalized = ctx.accounts.policy.finalized;
+    let was_closing = ctx.accounts.progress.close_distribution_pending();
+    if already_finalized {
+        // An archived vault only has its close distribution and its rent left
+        require!(close_accounts || was_closing, FeeDistributorError::VaultArchived);
+    } else {
+        // A day in flight still owes its remaining pages; funds in the yield reserve
+        // would be stranded outside the treasury
+        let progress = &ctx.accounts.progress;
+        require!(
+            progress.day_completed || progress.last_distribution_ts == 0,
+            FeeDistributorError::DayInProgress
+        );
+        require!(
+            progress.yield_principal == 0,
+            FeeDistributorError::YieldPrincipalOutstanding
+        );
+    }
+    
+    let vault_key = ctx.accounts.vault.key();
+    let bump = [ctx.accounts.policy.treasury_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+    
+    // Accruals stay claimable; everything else has no investor day left to go to
+    let residual = ctx
+        .accounts
+        .treasury
+        .amount
+        .saturating_sub(ctx.accounts.progress.pull_outstanding);
+    if !already_finalized && ctx.accounts.policy.distribute_on_close && residual > 0 && has_close_weights(&ctx)? {
+        let progress = &mut ctx.accounts.progress;
+        progress.close_pool = residual;
+        progress.close_cursor = 0;
+        progress.close_distributed = 0;
+        
+        emit!(CloseDistributionStarted {
+            vault: vault_key,
+            pool: residual,
+            total_investors: progress.total_investors,
+        });
+    } else if was_closing {
+        let (page_end, distributed) = pay_close_page(&ctx, signer_seeds)?;
+        let progress = &mut ctx.accounts.progress;
+        progress.close_distributed = progress
+            .close_distributed
+            .checked_add(distributed)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        progress.close_cursor = page_end;
+        ctx.accounts.treasury.reload()?;
+    }
+    
+    // The residual goes to the creator once no investor is left to pay it to
+    let closing = ctx.accounts.progress.close_distribution_pending();
+    let swept = if closing {
+        0
+    } else {
+        ctx.accounts
+            .treasury
+            .amount
+            .saturating_sub(ctx.accounts.progress.pull_outstanding)
+    };
+    if swept > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.creator_quote_ata.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept,
+        )?;
+    }
+    
+    let progress = &mut ctx.accounts.progress;
+    progress.current_period_claimed = 0;
+    progress.deferred_investor_pool = 0;
+    progress.vault_finalized = true;
+    ctx.accounts.policy.finalized = true;
+    
+    if was_closing && !closing {
+        emit!(CloseDistributionCompleted {
+            vault: vault_key,
+            pool: progress.close_pool,
+            distributed: progress.close_distributed,
+            swept,
+        });
+    }
+    
+    if !already_finalized {
+        emit!(VaultFinalized {
+            vault: vault_key,
+            authority: ctx.accounts.authority.key(),
+            lifetime_distributed: progress.lifetime_distributed,
+            total_yield_realized: progress.total_yield_realized,
+            latest_epoch: progress.latest_epoch,
+            swept,
+            pull_outstanding: progress.pull_outstanding,
+            accounts_closed: close_accounts,
+        });
+    }
+    
+    if close_accounts {
+        // Rent only comes back once the treasury has been emptied to investors
+        require!(!closing, FeeDistributorError::CloseDistributionInProgress);
+        // The treasury can only close empty, so every accrual must be claimed first
+        require!(
+            ctx.accounts.progress.pull_outstanding == 0,
+            FeeDistributorError::AccrualsOutstanding
+        );
+        let treasury_rent = ctx.accounts.treasury.to_account_info().lamports();
+        let progress_rent = ctx.accounts.progress.to_account_info().lamports();
+        
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.treasury.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: ctx.accounts.treasury_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        ctx.accounts
+            .progress
+            .close(ctx.accounts.authority.to_account_info())?;
+        
+        emit!(VaultRentRecovered {
+            vault: vault_key,
+            authority: ctx.accounts.authority.key(),
+            swept: if already_finalized { swept } else { 0 },
+            lamports: treasury_rent.saturating_add(progress_rent),
+        });
+    }
+    
+    Ok(())
+}
+
+/// Whether the last day's weights can be paid by: a day-level ledger of the current
+/// investor set with some weight in it. Without any weight the residual goes straight
+/// to the creator, as without `distribute_on_close`.
+fn has_close_weights(ctx: &Context<FinalizeVault>) -> Result<bool> {
+    let (policy, progress) = (&ctx.accounts.policy, &ctx.accounts.progress);
+    if progress.total_investors == 0 || progress.total_weight_today == 0 {
+        return Ok(false);
+    }
+    
+    // A snapshot for an upcoming day overwrites the entries, and one taken before the
+    // set changed no longer covers it
+    let current = match policy.weight_source {
+        WeightSource::DailySnapshot => {
+            ctx.accounts.locked_snapshot.is_some()
+                && !progress.has_upcoming_snapshot()
+                && progress.snapshot_set_version == progress.set_version
+        }
+        WeightSource::OnChainTable => ctx
+            .accounts
+            .weight_table
+            .as_ref()
+            .is_some_and(|table| table.is_committed_for(progress.latest_epoch, progress.total_investors)),
+        WeightSource::LiveStreams => false,
+    };
+    require!(current, FeeDistributorError::CloseLedgerUnavailable);
+    Ok(true)
+}
+
+/// Pay the next page of the close distribution: each investor's share of `close_pool`
+/// by their weight in the last day's ledger. Returns the page end and what was paid.
+///
+/// An investor whose ATA can't receive is skipped, and their share is swept to the
+/// creator with the rest once the set is done.
+fn pay_close_page<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, FinalizeVault<'info>>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<(u32, u64)> {
+    let (policy, progress) = (&ctx.accounts.policy, &ctx.accounts.progress);
+    let from_table = policy.weight_source == WeightSource::OnChainTable;
+    let ledger = day_ledger(policy, ctx.accounts.locked_snapshot.as_ref(), ctx.accounts.weight_table.as_ref())
+        .ok_or(if from_table {
+            FeeDistributorError::MissingWeightTable
+        } else {
+            FeeDistributorError::MissingSnapshotAccount
+        })?;
+    
+    let (start_idx, end_idx) = page_bounds(
+        progress.close_cursor,
+        std::cmp::min(ctx.remaining_accounts.len() / 2, MAX_PAGE_SIZE as usize) as u8,
+        progress.total_investors,
+    )?;
+    require!(start_idx < end_idx, FeeDistributorError::MissingInvestorAccounts);
+    
+    let mut distributed = 0u64;
+    let mut investors_paid = 0u32;
+    for (i, pair) in ctx.remaining_accounts[..(end_idx - start_idx) * 2].chunks(2).enumerate() {
+        let (investor_ata, stream) = (&pair[0], &pair[1]);
+        let index = start_idx + i;
+        let weight = if from_table {
+            let entry = WeightTable::read_entry(&ledger, index)?;
+            require_keys_eq!(entry.stream, stream.key(), FeeDistributorError::WeightTableMismatch);
+            entry.weight
+        } else {
+            let entry = LockedSnapshot::read_entry(&ledger, index)?;
+            require_keys_eq!(entry.stream, stream.key(), FeeDistributorError::SnapshotMismatch);
+            entry.weight
+        };
+        
+        let share = pro_rata(progress.close_pool, weight, progress.total_weight_today)?;
+        if share == 0 {
+            continue;
+        }
+        
+        let recipient = read_streamflow_recipient(stream)?;
+        if !is_valid_investor_ata(investor_ata, &policy.quote_mint, &ctx.accounts.treasury.key(), &recipient) {
+            emit!(InvalidInvestorAtaSkipped {
+                investor_index: to_investor_index(index)?,
+                investor_quote_ata: investor_ata.key(),
+            });
+            continue;
+        }
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: investor_ata.clone(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            share,
+        )?;
+        distributed = distributed
+            .checked_add(share)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        investors_paid += 1;
+    }
+    
+    let page_end = to_investor_index(end_idx)?;
+    emit!(CloseDistributionPage {
+        vault: ctx.accounts.vault.key(),
+        page_start: to_investor_index(start_idx)?,
+        page_end,
+        distributed,
+        investors_paid,
+    });
+    Ok((page_end, distributed))
+}
+
+#[event]
+pub struct VaultFinalized {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Investor distributions over the vault's lifetime
+    pub lifetime_distributed: u64,
+    pub total_yield_realized: u64,
+    /// Last day the vault started
+    pub latest_epoch: u64,
+    /// Moved from the treasury to the creator's quote ATA
+    pub swept: u64,
+    /// Accruals left in the treasury for investors to claim
+    pub pull_outstanding: u64,
+    pub accounts_closed: bool,
+}
+
+#[event]
+pub struct VaultRentRecovered {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Residual balance swept to the creator by this call, beyond what `VaultFinalized`
+    /// reported
+    pub swept: u64,
+    /// Rent returned to the authority from the treasury and the progress
+    pub lamports: u64,
+}
+
+#[event]
+pub struct CloseDistributionStarted {
+    pub vault: Pubkey,
+    /// Residual set aside for investors, by the last day's weights
+    pub pool: u64,
+    pub total_investors: u32,
+}
+
+#[event]
+pub struct CloseDistributionPage {
+    pub vault: Pubkey,
+    pub page_start: u32,
+    pub page_end: u32,
+    pub distributed: u64,
+    pub investors_paid: u32,
+}
+
+#[event]
+pub struct CloseDistributionCompleted {
+    pub vault: Pubkey,
+    pub pool: u64,
+    /// Paid to investors out of `pool`
+    pub distributed: u64,
+    /// Left over (flooring dust, skipped investors, funds received since) and swept to
+    /// the creator
+    pub swept: u64,
+}
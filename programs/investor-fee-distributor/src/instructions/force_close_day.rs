@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+// Glob import: the nested `CloseDay` needs the modules its derive generates
+use crate::{errors::FeeDistributorError, instructions::close_day::*};
+
+/// Close a day abandoned mid-pagination once the next day is due - authority only
+#[derive(Accounts)]
+pub struct ForceCloseDay<'info> {
+    /// Same accounts as `close_day`
+    pub close: CloseDay<'info>,
+    
+    /// Policy authority
+    #[account(
+        constraint = authority.key() == close.policy.authority @ FeeDistributorError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ForceCloseDay>) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let accounts = &mut ctx.accounts.close;
+    
+    require!(!accounts.progress.day_completed, FeeDistributorError::DayAlreadyCompleted);
+    // Only a day that has started pages and run past its interval can be abandoned;
+    // anything else can still be finished with distribute_fees or close_day
+    require!(
+        accounts.progress.is_mid_day()
+            && accounts
+                .progress
+                .is_new_day(current_ts, accounts.policy.distribution_interval),
+        FeeDistributorError::DayNotAbandoned
+    );
+    
+    let investors_processed = accounts.progress.pagination_cursor;
+    let creator_amount = settle_day(accounts, current_ts)?;
+    
+    emit!(DayForceClosed {
+        vault: accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        investors_processed,
+        total_investors: accounts.progress.total_investors,
+        creator_amount,
+        day_timestamp: accounts.progress.last_distribution_ts,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct DayForceClosed {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Investors paid before the day was abandoned
+    pub investors_processed: u32,
+    pub total_investors: u32,
+    /// Paid to the creator by the force close, including unreached investors' share
+    pub creator_amount: u64,
+    /// Start of the abandoned day
+    pub day_timestamp: i64,
+}
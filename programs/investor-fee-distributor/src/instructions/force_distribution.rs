@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Let the next day start even though its claim is below `min_claim_to_distribute` -
+/// authority only
+#[derive(Accounts)]
+pub struct ForceDistribution<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress, flagged for the next day start
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+pub(crate) fn handler(ctx: Context<ForceDistribution>) -> Result<()> {
+    // One-shot: the day start that honours it clears the flag again
+    let progress = &mut ctx.accounts.progress;
+    progress.distribution_forced = true;
+    
+    emit!(DistributionForced {
+        vault: progress.vault,
+        authority: ctx.accounts.authority.key(),
+        pending_claim: progress.current_period_claimed,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct DistributionForced {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Claims the forced day will distribute (plus anything recognized at its start)
+    pub pending_claim: u64,
+}
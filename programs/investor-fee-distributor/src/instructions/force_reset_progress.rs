@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Put a corrupted progress account back to its post-initialize state - authority only
+///
+/// Last-resort recovery. Everything the progress tracks about the current day, the
+/// pending claim, deferred pools and the claim history is dropped, including the
+/// claim moving average and a tripped circuit breaker:
+/// - funds claimed or deferred but not yet paid stay in the treasury unrecognized,
+///   so the next day start picks them up as if freshly claimed;
+/// - investors paid earlier in an open day are not remembered, so the next day
+///   pays them again out of what that day left;
+/// - the next day can start at once, and with `DailySnapshot` weights only after a
+///   new snapshot has been taken.
+///
+/// The fields that survive are listed on [`DistributionProgress::force_reset`]. The
+/// policy and the treasury are not touched.
+#[derive(Accounts)]
+pub struct ForceResetProgress<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress to reset
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+pub(crate) fn handler(ctx: Context<ForceResetProgress>, confirmation: [u8; 16]) -> Result<()> {
+    // A typed-out phrase rather than a flag, so no client sends it by accident
+    require!(
+        confirmation == FORCE_RESET_PROGRESS_CONFIRMATION,
+        FeeDistributorError::InvalidResetConfirmation
+    );
+    
+    let progress = &mut ctx.accounts.progress;
+    let previous = (**progress).clone();
+    progress.force_reset();
+    
+    msg!("Distribution progress force-reset by the authority");
+    emit!(ProgressForceReset {
+        vault: progress.vault,
+        authority: ctx.accounts.authority.key(),
+        reset_ts: Clock::get()?.unix_timestamp,
+        previous,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ProgressForceReset {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub reset_ts: i64,
+    /// The whole progress account as it was before the reset, for audit
+    pub previous: DistributionProgress,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::*;
+    use crate::test_fixtures::progress_after_day;
+
+    #[test]
+    fn force_reset_keeps_only_what_the_treasury_still_owes() {
+        let mut progress = progress_after_day(1_000_000, 400_000);
+        progress.day_completed = false;
+        progress.pagination_cursor = 2;
+        progress.current_period_claimed = 50_000;
+        progress.deferred_investor_pool = 7_000;
+        progress.pull_outstanding = 30_000;
+        progress.yield_principal = 20_000;
+        progress.latest_epoch = 12;
+        progress.claim_ema = 900_000;
+        progress.circuit_breaker = CircuitBreakerState::Tripped;
+        let total_investors = progress.total_investors;
+
+        progress.force_reset();
+
+        // Owed balances and the day count survive; the rest is as initialize left it
+        assert_eq!(progress.total_investors, total_investors);
+        assert_eq!(progress.pull_outstanding, 30_000);
+        assert_eq!(progress.yield_principal, 20_000);
+        assert_eq!(progress.latest_epoch, 12);
+        assert_eq!(progress.recognized_treasury_balance(), 30_000);
+        assert!(!progress.is_mid_day());
+        assert_eq!(progress.last_distribution_ts, 0);
+        assert_eq!(progress.claim_ema, 0);
+        assert_eq!(progress.circuit_breaker, CircuitBreakerState::Armed);
+        assert_eq!(progress.top_n_cutoff_index, u32::MAX);
+    }
+}
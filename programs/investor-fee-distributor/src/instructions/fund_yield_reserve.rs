@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Put principal into the policy's yield reserve - authority only
+#[derive(Accounts)]
+pub struct FundYieldReserve<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// The policy's yield reserve
+    #[account(
+        mut,
+        constraint = policy.yield_reserve == Some(yield_reserve.key())
+            @ FeeDistributorError::YieldReserveMismatch,
+    )]
+    pub yield_reserve: Account<'info, TokenAccount>,
+    
+    /// Authority's quote token account the principal is taken from
+    #[account(
+        mut,
+        token::mint = policy.quote_mint,
+        token::authority = authority,
+    )]
+    pub authority_quote_ata: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn handler(ctx: Context<FundYieldReserve>, amount: u64) -> Result<()> {
+    require!(amount > 0, FeeDistributorError::InvalidDepositAmount);
+    
+    // PLACEHOLDER: a real adapter deposits into the lending protocol and keeps the
+    // receipt tokens under the treasury authority; the simulated reserve holds quote
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority_quote_ata.to_account_info(),
+                to: ctx.accounts.yield_reserve.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    // Principal is never distributed; only value above it is realized as yield
+    let progress = &mut ctx.accounts.progress;
+    progress.yield_principal = progress
+        .yield_principal
+        .checked_add(amount)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    
+    emit!(YieldPrincipalFunded {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        amount,
+        principal: progress.yield_principal,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct YieldPrincipalFunded {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    /// Total principal in the reserve after the deposit
+    pub principal: u64,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, math::cap_headroom, state::*};
+
+/// Read how much headroom every active cap has left - read-only
+#[derive(Accounts)]
+pub struct GetCapHeadroom<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+/// One cap's configured limit and what is left under it, in quote lamports
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapLimit {
+    pub limit: u64,
+    pub remaining: u64,
+}
+
+/// The cap leaving the least headroom
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingCap {
+    Daily,
+    Lifetime,
+    ClaimGrowth,
+}
+
+/// Headroom under every cap, returned via return data; `None` for a cap that is off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapHeadroom {
+    /// `daily_cap_lamports` against the current day's investor distributions (the next
+    /// day's once the current one is completed)
+    pub daily: Option<CapLimit>,
+    /// `lifetime_cap_lamports` against every investor distribution so far
+    pub lifetime: Option<CapLimit>,
+    /// `max_single_investor_bps` of the current day's investor pool; payouts aren't
+    /// tracked per investor, so this is what one investor may receive of it
+    pub per_investor: Option<CapLimit>,
+    /// `max_claim_growth_bps` over the prior day's claim, against the claim pending for
+    /// the next day start
+    pub claim_growth: Option<CapLimit>,
+    /// Cap with the least remaining among daily, lifetime and claim growth; the
+    /// per-investor cap passes its excess to other investors, so it never binds
+    pub binding: Option<BindingCap>,
+}
+
+pub(crate) fn handler(ctx: Context<GetCapHeadroom>) -> Result<CapHeadroom> {
+    Ok(vault_cap_headroom(&ctx.accounts.policy, &ctx.accounts.progress))
+}
+
+pub(crate) fn vault_cap_headroom(policy: &DistributionPolicy, progress: &DistributionProgress) -> CapHeadroom {
+    // A completed day is already counted against the lifetime cap, and the next one
+    // starts with the full daily cap
+    let day_distributed = if progress.day_completed {
+        0
+    } else {
+        progress.current_day_distributed_investors
+    };
+    
+    let daily = (policy.daily_cap_lamports > 0).then(|| CapLimit {
+        limit: policy.daily_cap_lamports,
+        remaining: cap_headroom(policy.daily_cap_lamports, day_distributed),
+    });
+    let lifetime = (policy.lifetime_cap_lamports > 0).then(|| CapLimit {
+        limit: policy.lifetime_cap_lamports,
+        remaining: progress
+            .lifetime_headroom(policy.lifetime_cap_lamports)
+            .saturating_sub(day_distributed),
+    });
+    let per_investor = (policy.max_single_investor_bps > 0).then(|| {
+        let limit = (progress.current_day_investor_pool as u128 * policy.max_single_investor_bps as u128
+            / BASIS_POINTS_DIVISOR as u128) as u64;
+        CapLimit { limit, remaining: limit }
+    });
+    let claim_growth = (policy.max_claim_growth_bps != UNLIMITED_CLAIM_GROWTH_BPS && progress.prior_day_claimed > 0)
+        .then(|| {
+            let limit = progress.growth_capped_claim(u64::MAX, policy.max_claim_growth_bps);
+            CapLimit {
+                limit,
+                remaining: limit.saturating_sub(progress.current_period_claimed),
+            }
+        });
+    
+    let binding = [
+        (BindingCap::Daily, daily),
+        (BindingCap::Lifetime, lifetime),
+        (BindingCap::ClaimGrowth, claim_growth),
+    ]
+    .into_iter()
+    .filter_map(|(cap, headroom)| headroom.map(|headroom| (cap, headroom.remaining)))
+    .min_by_key(|(_, remaining)| *remaining)
+    .map(|(cap, _)| cap);
+    
+    CapHeadroom {
+        daily,
+        lifetime,
+        per_investor,
+        claim_growth,
+        binding,
+    }
+}
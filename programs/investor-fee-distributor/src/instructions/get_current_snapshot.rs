@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Read the summary of the locked snapshot currently stored - read-only
+///
+/// The snapshot ledger holds a single day, so this is the only snapshot there is to
+/// read; older days live on only in their `LockedSnapshotPage` events.
+#[derive(Accounts)]
+pub struct GetCurrentSnapshot<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+/// The stored locked snapshot's totals, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SnapshotSummary {
+    /// Day the snapshot belongs to
+    pub day_epoch: u64,
+    /// Most recently started day, the upper end of the range clients can ask about
+    pub latest_epoch: u64,
+    /// Timestamp the snapshot was started at
+    pub snapshot_ts: i64,
+    /// Investors read into the snapshot so far
+    pub investors_read: u32,
+    /// Investors in the distribution set
+    pub total_investors: u32,
+    /// Sum of locked amounts read so far
+    pub total_locked: u64,
+    /// Share-multiplied weights read so far
+    pub total_weight: u64,
+    /// Investors with a non-zero locked amount read so far
+    pub active_investors: u32,
+    /// Every investor has been read
+    pub complete: bool,
+}
+
+pub(crate) fn handler(ctx: Context<GetCurrentSnapshot>) -> Result<SnapshotSummary> {
+    let progress = &ctx.accounts.progress;
+    
+    // The upcoming day's snapshot replaces the running day's as soon as it is started
+    let day_epoch = progress
+        .snapshot_epoch()
+        .ok_or(FeeDistributorError::SnapshotEpochUnavailable)?;
+    
+    Ok(SnapshotSummary {
+        day_epoch,
+        latest_epoch: progress.latest_epoch,
+        snapshot_ts: progress.snapshot_ts,
+        investors_read: progress.snapshot_cursor,
+        total_investors: progress.total_investors,
+        total_locked: progress.total_locked_today,
+        total_weight: progress.total_weight_today,
+        active_investors: progress.active_investors_today,
+        complete: progress.snapshot_cursor == progress.total_investors,
+    })
+}
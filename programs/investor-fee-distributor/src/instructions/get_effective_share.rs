@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::read_streamflow_locked_amount,
+    math::*,
+    state::*,
+};
+
+/// Read the current day's effective investor share - read-only
+#[derive(Accounts)]
+pub struct GetEffectiveShare<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    // Remaining accounts (LiveStreams mode only):
+    // Every investor's stream_account, so the locked total covers the whole set
+}
+
+/// Share resolution for the current day, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct EffectiveShare {
+    /// `min(investor_fee_share_bps, f_locked)`, or 10000 under `RemainderMode::ToInvestors`
+    pub eligible_bps: u64,
+    /// Locked fraction of Y0 in bps
+    pub f_locked: u64,
+    /// Locked total the fraction was computed from
+    pub total_locked: u64,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetEffectiveShare<'info>>,
+    as_of_ts: Option<i64>,
+) -> Result<EffectiveShare> {
+    let policy = &ctx.accounts.policy;
+    let progress = &ctx.accounts.progress;
+    
+    let total_locked = match policy.weight_source {
+        WeightSource::LiveStreams => {
+            let total_investors = usize::try_from(progress.total_investors)
+                .map_err(|_| FeeDistributorError::InvalidPaginationCursor)?;
+            let stream_accounts = ctx
+                .remaining_accounts
+                .get(..total_investors)
+                .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+            
+            let as_of_ts = resolve_as_of_ts(as_of_ts, Clock::get()?.unix_timestamp)?;
+            let mut total_locked: u64 = 0;
+            for stream_account in stream_accounts {
+                total_locked = total_locked
+                    .checked_add(read_streamflow_locked_amount(stream_account, as_of_ts)?)
+                    .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+            }
+            total_locked
+        }
+        WeightSource::DailySnapshot => {
+            // The running day's snapshot, or the completed one for the upcoming day
+            require!(
+                progress.snapshot_cursor == progress.total_investors,
+                FeeDistributorError::SnapshotIncomplete
+            );
+            progress.total_locked_today
+        }
+        // The table totals the latest day started from
+        WeightSource::OnChainTable => progress.total_locked_today,
+    };
+    
+    let f_locked = policy.locked_fraction(total_locked)?;
+    
+    Ok(EffectiveShare {
+        eligible_bps: eligible_bps(
+            policy.effective_investor_share_bps(),
+            policy.pool_locked_fraction(total_locked)?,
+        ),
+        f_locked,
+        total_locked,
+    })
+}
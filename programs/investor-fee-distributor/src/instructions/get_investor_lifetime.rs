@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, state::*};
+
+/// Read an investor's all-time total allocated by the vault - read-only
+#[derive(Accounts)]
+pub struct GetInvestorLifetime<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Investor's Streamflow stream
+    /// CHECK: Used only as the accrual seed
+    pub stream_account: UncheckedAccount<'info>,
+    
+    /// Investor accrual PDA
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), ACCRUAL_SEED, stream_account.key().as_ref()],
+        bump = accrual.bump,
+        has_one = vault,
+    )]
+    pub accrual: Account<'info, InvestorAccrual>,
+}
+
+/// `accrual.lifetime_received`, returned via return data
+pub(crate) fn handler(ctx: Context<GetInvestorLifetime>) -> Result<u64> {
+    Ok(ctx.accounts.accrual.lifetime_received)
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Recreate the progress account of an existing vault, e.g. after it was closed for a
+/// layout migration - authority only
+///
+/// The policy and the treasury are not touched. What the old progress knew about
+/// balances owed out of the treasury can't be read back on-chain, so the authority
+/// supplies it; everything else starts as after `initialize`. Funds in the treasury
+/// beyond `pull_outstanding` are left unrecognized, so the next day start picks them
+/// up as if freshly claimed.
+#[derive(Accounts)]
+pub struct InitProgress<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy of the vault
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress PDA; `init` refuses a progress that still exists
+    #[account(
+        init,
+        payer = authority,
+        space = DistributionProgress::LEN,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA, checked against the bump the policy recorded
+    /// CHECK: PDA that owns the treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump = policy.treasury_authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// What a rebuilt progress carries over from the one it replaces
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct InitProgressParams {
+    pub total_investors: u32,
+    /// Accrued but unclaimed pull-mode payouts; at most the treasury balance
+    pub pull_outstanding: u64,
+    /// Quote value held in the yield reserve
+    pub yield_principal: u64,
+    /// Last started day, so a weight table committed for it can't pass for the next
+    pub latest_epoch: u64,
+    /// Investor distributions so far; at or above `lifetime_cap_lamports` the vault
+    /// starts finalized
+    pub lifetime_distributed: u64,
+}
+
+pub(crate) fn handler(ctx: Context<InitProgress>, params: InitProgressParams) -> Result<()> {
+    // Accruals are paid out of the treasury, so it must still hold them
+    let treasury_balance = ctx.accounts.treasury.amount;
+    require!(
+        params.pull_outstanding <= treasury_balance,
+        FeeDistributorError::ProgressExceedsTreasury
+    );
+    // A top-N day ranks the whole set on its first page
+    require!(
+        ctx.accounts.policy.top_n_investors == 0 || params.total_investors <= MAX_TOP_N_INVESTOR_SET,
+        FeeDistributorError::TopNInvestorSetTooLarge
+    );
+    
+    let progress = &mut ctx.accounts.progress;
+    progress.vault = ctx.accounts.vault.key();
+    progress.total_investors = params.total_investors;
+    progress.pull_outstanding = params.pull_outstanding;
+    progress.yield_principal = params.yield_principal;
+    progress.latest_epoch = params.latest_epoch;
+    progress.lifetime_distributed = params.lifetime_distributed;
+    progress.vault_finalized = ctx.accounts.policy.lifetime_cap_lamports > 0
+        && params.lifetime_distributed >= ctx.accounts.policy.lifetime_cap_lamports;
+    progress.bump = ctx.bumps.progress;
+    // The vault has claimed before, so what the treasury holds isn't pre-funding
+    progress.prefunding_settled = true;
+    // Keeps exactly the fields above and puts everything else at its initial value
+    progress.force_reset();
+    
+    emit!(ProgressInitialized {
+        vault: progress.vault,
+        authority: ctx.accounts.authority.key(),
+        total_investors: progress.total_investors,
+        pull_outstanding: progress.pull_outstanding,
+        latest_epoch: progress.latest_epoch,
+        vault_finalized: progress.vault_finalized,
+        unrecognized_balance: treasury_balance.saturating_sub(progress.recognized_treasury_balance()),
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ProgressInitialized {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub total_investors: u32,
+    pub pull_outstanding: u64,
+    pub latest_epoch: u64,
+    pub vault_finalized: bool,
+    /// Treasury funds the next day start will recognize as claimed
+    pub unrecognized_balance: u64,
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
-use crate::{constants::*, errors::FeeDistributorError, state::*};
+use crate::{constants::*, errors::FeeDistributorError, instructions::update_policy::PolicyUpdated, state::*};
 
 /// Initialize the honorary fee position and distribution policy
 #[derive(Accounts)]
@@ -9,13 +9,17 @@ pub struct Initialize<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    /// The vault identifier (arbitrary key to scope this distribution)
+    /// The vault identifier (arbitrary key to scope this distribution); must not be
+    /// the quote or investor mint, the creator wallet or the program
     /// CHECK: Used only as a seed for PDAs
     pub vault: UncheckedAccount<'info>,
     
     /// Quote token mint (must match pool configuration)
     pub quote_mint: Account<'info, Mint>,
     
+    /// Mint of the token investor streams vest (Y0 is checked against its supply)
+    pub investor_mint: Account<'info, Mint>,
+    
     /// Creator wallet that will receive remainder fees
     /// CHECK: Creator's wallet pubkey, validated by authority
     pub creator_wallet: UncheckedAccount<'info>,
@@ -40,6 +44,26 @@ pub struct Initialize<'info> {
     )]
     pub progress: Account<'info, DistributionProgress>,
     
+    /// Per-investor locked snapshot ledger (grows as snapshot pages are taken)
+    #[account(
+        init,
+        payer = authority,
+        space = LockedSnapshot::space(0),
+        seeds = [VAULT_SEED, vault.key().as_ref(), SNAPSHOT_SEED],
+        bump
+    )]
+    pub locked_snapshot: Account<'info, LockedSnapshot>,
+    
+    /// Per-investor share multipliers (grows as overrides are added)
+    #[account(
+        init,
+        payer = authority,
+        space = ShareOverrides::space(0),
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump
+    )]
+    pub share_overrides: Account<'info, ShareOverrides>,
+    
     /// Program's quote token treasury (PDA owned ATA)
     #[account(
         init,
@@ -64,7 +88,7 @@ pub struct Initialize<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<Initialize>,
     total_investor_allocation: u64,
     investor_fee_share_bps: u16,
@@ -72,21 +96,129 @@ pub fn handler(
     min_payout_lamports: u64,
     total_investors: u32,
 ) -> Result<()> {
+    // The vault key only seeds PDAs; reusing another role's key is a copy-paste mistake
+    let vault_key = ctx.accounts.vault.key();
+    require!(
+        vault_key != ctx.accounts.quote_mint.key()
+            && vault_key != ctx.accounts.investor_mint.key()
+            && vault_key != ctx.accounts.creator_wallet.key()
+            && vault_key != crate::ID,
+        FeeDistributorError::InvalidVaultKey
+    );
+    
     // Validate basis points
     require!(
         investor_fee_share_bps <= crate::constants::BASIS_POINTS_DIVISOR as u16,
         FeeDistributorError::InvalidBasisPoints
     );
+    require!(
+        investor_fee_share_bps <= MAX_INVESTOR_FEE_SHARE_BPS,
+        FeeDistributorError::InvestorShareTooHigh
+    );
     
     // Initialize policy
     let policy = &mut ctx.accounts.policy;
     policy.vault = ctx.accounts.vault.key();
+    policy.authority = ctx.accounts.authority.key();
     policy.quote_mint = ctx.accounts.quote_mint.key();
     policy.creator_wallet = ctx.accounts.creator_wallet.key();
     policy.total_investor_allocation = total_investor_allocation;
     policy.investor_fee_share_bps = investor_fee_share_bps;
     policy.daily_cap_lamports = daily_cap_lamports;
     policy.min_payout_lamports = min_payout_lamports;
+    policy.creator_payout_mode = CreatorPayoutMode::EndOfDay;
+    policy.investor_fallback_wallet = None;
+    policy.weight_source = WeightSource::LiveStreams;
+    policy.distribution_mode = DistributionMode::Push;
+    policy.distribution_interval = SECONDS_PER_DAY;
+    policy.distribution_scheme = DistributionScheme::ProRata;
+    policy.fixed_payout_lamports = 0;
+    policy.fixed_shortfall = FixedShortfall::SplitEvenly;
+    policy.defer_creator_close = false;
+    policy.max_claim_age_secs = 0;
+    policy.share_override_count = 0;
+    policy.remainder_mode = RemainderMode::Creator;
+    policy.quote_decimals = ctx.accounts.quote_mint.decimals;
+    policy.min_payout_unit = MinPayoutUnit::Lamports;
+    policy.attach_memo = false;
+    policy.memo_reference = String::new();
+    policy.fee_source = None;
+    policy.max_single_investor_bps = 0;
+    policy.claim_paused = false;
+    policy.investor_token_decimals = ctx.accounts.investor_mint.decimals;
+    policy.allocation_decimals = ctx.accounts.investor_mint.decimals;
+    policy.defer_below_minimum = false;
+    policy.payout_granularity_lamports = 1;
+    policy.backpay_enabled = false;
+    policy.backpay_lamports = 0;
+    policy.payout_priority = PayoutPriority::AccountOrder;
+    policy.yield_reserve = None;
+    policy.pull_claim_cooldown = false;
+    policy.tolerate_claim_failure = false;
+    policy.damm_pool = None;
+    policy.payout_mint = None;
+    policy.swap_pool = None;
+    policy.max_swap_slippage_bps = DEFAULT_MAX_SWAP_SLIPPAGE_BPS;
+    policy.reject_delegated_atas = false;
+    policy.min_claim_to_distribute = 0;
+    policy.claim_ema_alpha_bps = DEFAULT_CLAIM_EMA_ALPHA_BPS;
+    policy.circuit_breaker_multiple_bps = 0;
+    policy.top_n_investors = 0;
+    policy.require_opt_in = false;
+    policy.min_creator_share_bps = 0;
+    policy.skip_weekends = false;
+    policy.lifetime_cap_lamports = 0;
+    policy.weight_in_usd = false;
+    policy.price_oracle = Pubkey::default();
+    policy.creator_deposit_mode = CreatorDepositMode::Transfer;
+    policy.governance_realm = Pubkey::default();
+    policy.governance_treasury = Pubkey::default();
+    policy.confirm_deliveries = false;
+    policy.creator_granularity_lamports = 1;
+    policy.max_claim_growth_bps = UNLIMITED_CLAIM_GROWTH_BPS;
+    policy.finalized = false;
+    policy.weighting_mode = WeightingMode::Instantaneous;
+    policy.verbose_events = true;
+    policy.residual_mode = ResidualMode::RollToNextDay;
+    policy.protocol_fee_bps = 0;
+    policy.protocol_fee_wallet = Pubkey::default();
+    policy.exclude_creator_from_investors = false;
+    policy.reconciliation_tolerance_lamports = 0;
+    policy.max_transfer_size = 0;
+    policy.page_quorum_bps = 0;
+    policy.max_oracle_staleness_seconds = DEFAULT_MAX_ORACLE_STALENESS_SECONDS;
+    policy.max_oracle_confidence_bps = DEFAULT_MAX_ORACLE_CONFIDENCE_BPS;
+    policy.prefunded_balance_mode = PrefundedBalanceMode::Reserve;
+    policy.weight_blend_bps = 0;
+    policy.max_investors_per_day = 0;
+    policy.distributor_program = None;
+    policy.dust_to_last_investor = false;
+    policy.withholding_bps = 0;
+    policy.withholding_wallet = None;
+    policy.distribute_on_close = false;
+    policy.persist_dust_across_days = false;
+    policy.require_creator_signature = false;
+    policy.withdrawal_handling = WithdrawalHandling::Current;
+    policy.creator_daily_cap_lamports = 0;
+    policy.deterministic_index_order = false;
+    policy.payout_settlement = PayoutSettlement::Swap;
+    policy.settlement_rate = 0;
+    policy.daily_cap_allocation = DailyCapAllocation::PageOrder;
+    policy.max_ata_creations_per_page = 0;
+    policy.max_ata_creation_funding = 0;
+    policy.record_day_history = false;
+    policy.min_payout_override_count = 0;
+    policy.payout_split_count = 0;
+    
+    // Y0 is taken in raw units; more than was ever minted means it was given
+    // in the wrong unit (e.g. scaled by decimals twice)
+    require!(
+        total_investor_allocation <= ctx.accounts.investor_mint.supply,
+        FeeDistributorError::AllocationUnitMismatch
+    );
+    // Raw amounts read very differently for a 0- or 18-decimal quote mint
+    policy.check_quote_amounts()?;
+    policy.treasury_authority_bump = ctx.bumps.treasury_authority;
     policy.bump = ctx.bumps.policy;
     
     // Initialize progress
@@ -100,16 +232,65 @@ pub fn handler(
     progress.pagination_cursor = 0;
     progress.day_completed = false;
     progress.total_investors = total_investors;
+    progress.snapshot_cursor = 0;
+    progress.total_locked_today = 0;
+    progress.active_investors_today = 0;
+    progress.total_weight_today = 0;
+    progress.snapshot_ts = 0;
+    progress.pull_outstanding = 0;
+    progress.last_claim_ts = 0;
+    progress.current_period_claimed = 0;
+    progress.current_day_investor_pool = 0;
+    progress.current_day_burned = 0;
+    progress.deferred_investor_pool = 0;
+    progress.current_day_deferred = 0;
+    progress.current_day_backpay = 0;
+    progress.yield_principal = 0;
+    progress.total_yield_realized = 0;
+    progress.latest_epoch = 0;
+    progress.distribution_forced = false;
+    progress.current_day_investors_paid = 0;
+    progress.claim_ema = 0;
+    progress.claim_ema_days = 0;
+    progress.circuit_breaker = CircuitBreakerState::Armed;
+    progress.top_n_cutoff_locked = 0;
+    progress.top_n_cutoff_index = u32::MAX;
+    progress.lifetime_distributed = 0;
+    progress.vault_finalized = false;
+    progress.usd_weight_price = 0;
+    progress.prior_day_claimed = 0;
+    progress.set_version = 0;
+    progress.snapshot_set_version = 0;
+    progress.prefunded_reserve = 0;
+    progress.prefunding_settled = false;
+    progress.close_pool = 0;
+    progress.close_cursor = 0;
+    progress.close_distributed = 0;
+    progress.persisted_dust = 0;
     progress.bump = ctx.bumps.progress;
     
+    // Initialize snapshot ledger
+    let locked_snapshot = &mut ctx.accounts.locked_snapshot;
+    locked_snapshot.vault = ctx.accounts.vault.key();
+    locked_snapshot.bump = ctx.bumps.locked_snapshot;
+    
+    // Initialize share overrides
+    let share_overrides = &mut ctx.accounts.share_overrides;
+    share_overrides.vault = ctx.accounts.vault.key();
+    share_overrides.entries = Vec::new();
+    share_overrides.bump = ctx.bumps.share_overrides;
+    
     emit!(HonoraryPositionInitialized {
         vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
         quote_mint: ctx.accounts.quote_mint.key(),
         treasury: ctx.accounts.treasury.key(),
         treasury_authority: ctx.accounts.treasury_authority.key(),
         total_investor_allocation,
         investor_fee_share_bps,
     });
+    // Starting point for the policy's PolicyUpdated history
+    emit!(PolicyUpdated::from_policy(&ctx.accounts.policy));
     
     Ok(())
 }
@@ -117,6 +298,7 @@ pub fn handler(
 #[event]
 pub struct HonoraryPositionInitialized {
     pub vault: Pubkey,
+    pub authority: Pubkey,
     pub quote_mint: Pubkey,
     pub treasury: Pubkey,
     pub treasury_authority: Pubkey,
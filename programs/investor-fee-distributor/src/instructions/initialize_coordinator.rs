@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{constants::*, state::*};
+
+/// Create a treasury coordinator that splits one fee pool between several vaults
+#[derive(Accounts)]
+pub struct InitializeCoordinator<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The coordinator identifier (arbitrary key to scope its PDAs)
+    /// CHECK: Used only as a seed for PDAs
+    pub coordinator_id: UncheckedAccount<'info>,
+    
+    /// Quote token mint shared by every member vault
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// Coordinator PDA
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryCoordinator::space(0),
+        seeds = [COORDINATOR_SEED, coordinator_id.key().as_ref()],
+        bump
+    )]
+    pub coordinator: Account<'info, TreasuryCoordinator>,
+    
+    /// Coordinator's quote treasury, where the shared fee pool is claimed into
+    #[account(
+        init,
+        payer = authority,
+        seeds = [COORDINATOR_SEED, coordinator_id.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = quote_mint,
+        token::authority = coordinator
+    )]
+    pub coordinator_treasury: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeCoordinator>) -> Result<()> {
+    let coordinator = &mut ctx.accounts.coordinator;
+    coordinator.coordinator_id = ctx.accounts.coordinator_id.key();
+    coordinator.authority = ctx.accounts.authority.key();
+    coordinator.quote_mint = ctx.accounts.quote_mint.key();
+    coordinator.total_claimed = 0;
+    coordinator.carried_dust = 0;
+    coordinator.members = Vec::new();
+    coordinator.bump = ctx.bumps.coordinator;
+    
+    emit!(CoordinatorInitialized {
+        coordinator: coordinator.key(),
+        authority: coordinator.authority,
+        quote_mint: coordinator.quote_mint,
+        coordinator_treasury: ctx.accounts.coordinator_treasury.key(),
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CoordinatorInitialized {
+    pub coordinator: Pubkey,
+    pub authority: Pubkey,
+    pub quote_mint: Pubkey,
+    pub coordinator_treasury: Pubkey,
+}
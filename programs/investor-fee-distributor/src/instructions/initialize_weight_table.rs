@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Create the vault's weight table for `WeightSource::OnChainTable` - authority only
+#[derive(Accounts)]
+pub struct InitializeWeightTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Weight table PDA, created empty and grown by `write_weight_table`
+    #[account(
+        init,
+        payer = authority,
+        space = WeightTable::space(0),
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump
+    )]
+    pub weight_table: Account<'info, WeightTable>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeWeightTable>) -> Result<()> {
+    let weight_table = &mut ctx.accounts.weight_table;
+    weight_table.vault = ctx.accounts.vault.key();
+    // Epoch 0 is never a distribution day, so nothing reads the table until it is written
+    weight_table.reset(0);
+    weight_table.bump = ctx.bumps.weight_table;
+    
+    Ok(())
+}
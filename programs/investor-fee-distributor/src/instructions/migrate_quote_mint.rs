@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token::{self, CloseAccount, InitializeAccount3, Mint, Token, TokenAccount};
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::treasury_authority_seeds,
+    state::*,
+};
+
+/// Move a vault to a new quote mint, recreating its treasury for it - authority only
+#[derive(Accounts)]
+pub struct MigrateQuoteMint<'info> {
+    /// Policy authority (pays the new treasury's rent, refunded the old one's)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress (must hold no funds in flight)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Quote mint the vault moves to
+    pub new_quote_mint: Account<'info, Mint>,
+    
+    /// Program's quote treasury, closed and recreated at the same address
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA, checked against the bump the policy recorded
+    /// CHECK: PDA that owns the treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump = policy.treasury_authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<MigrateQuoteMint>) -> Result<()> {
+    let old_quote_mint = ctx.accounts.policy.quote_mint;
+    let new_quote_mint = ctx.accounts.new_quote_mint.key();
+    require_keys_neq!(new_quote_mint, old_quote_mint, FeeDistributorError::InvalidQuoteMint);
+    require!(
+        ctx.accounts.policy.payout_mint != Some(new_quote_mint),
+        FeeDistributorError::InvalidPayoutConversion
+    );
+    
+    // Nothing may be left behind in the old mint: no balance, no day in flight and
+    // nothing owed, recognized or held back for later
+    let progress = &ctx.accounts.progress;
+    require!(
+        progress.day_completed || progress.last_distribution_ts == 0,
+        FeeDistributorError::DayInProgress
+    );
+    require!(
+        ctx.accounts.treasury.amount == 0
+            && progress.pull_outstanding == 0
+            && progress.current_period_claimed == 0
+            && progress.deferred_investor_pool == 0
+            && progress.yield_principal == 0
+            && ctx.accounts.policy.yield_reserve.is_none(),
+        FeeDistributorError::QuoteMintMigrationBlocked
+    );
+    
+    let vault_key = ctx.accounts.vault.key();
+    let bump = [ctx.accounts.policy.treasury_authority_bump];
+    let authority_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+    let treasury_seeds: &[&[&[u8]]] = &[&[
+        VAULT_SEED,
+        vault_key.as_ref(),
+        TREASURY_SEED,
+        &[ctx.bumps.treasury],
+    ]];
+    
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.treasury.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        },
+        authority_seeds,
+    ))?;
+    
+    // The Token program hands a closed account back to the System program with no
+    // data, so the same PDA can be created again. Same address, so every other
+    // instruction keeps finding the treasury where it was
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+            treasury_seeds,
+        ),
+        Rent::get()?.minimum_balance(TokenAccount::LEN),
+        TokenAccount::LEN as u64,
+        &token::ID,
+    )?;
+    token::initialize_account3(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        InitializeAccount3 {
+            account: ctx.accounts.treasury.to_account_info(),
+            mint: ctx.accounts.new_quote_mint.to_account_info(),
+            authority: ctx.accounts.treasury_authority.to_account_info(),
+        },
+    ))?;
+    
+    let policy = &mut ctx.accounts.policy;
+    policy.quote_mint = new_quote_mint;
+    policy.quote_decimals = ctx.accounts.new_quote_mint.decimals;
+    // Cap and minimum payout are in quote units; they must still fit the new decimals
+    policy.check_quote_amounts()?;
+    
+    emit!(QuoteMintMigrated {
+        vault: vault_key,
+        authority: ctx.accounts.authority.key(),
+        old: old_quote_mint,
+        new: new_quote_mint,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct QuoteMintMigrated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old: Pubkey,
+    pub new: Pubkey,
+}
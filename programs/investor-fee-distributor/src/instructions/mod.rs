@@ -1,5 +1,95 @@
 pub mod initialize;
 pub mod distribute;
+pub mod update_policy;
+pub mod snapshot;
+pub mod open_accrual;
+pub mod claim_my_payout;
+pub mod claim_fees;
+pub mod set_test_treasury_authority;
+pub mod set_min_payout;
+pub mod close_day;
+pub mod set_share_override;
+pub mod get_effective_share;
+pub mod force_close_day;
+pub mod estimate_crank_cost;
+pub mod recommended_compute_units;
+pub mod deposit_and_recognize;
+pub mod set_claim_paused;
+pub mod cancel_day;
+pub mod fund_yield_reserve;
+pub mod remaining_distributable;
+pub mod initialize_coordinator;
+pub mod set_coordinator_member;
+pub mod split_coordinator_claim;
+pub mod get_current_snapshot;
+pub mod migrate_quote_mint;
+pub mod force_distribution;
+pub mod preview_page_breakdown;
+pub mod preview_policy_change;
+pub mod get_investor_lifetime;
+pub mod initialize_weight_table;
+pub mod write_weight_table;
+pub mod reset_circuit_breaker;
+pub mod opt_in;
+pub mod force_reset_progress;
+pub mod sweep_finalized_treasury;
+pub mod audit_balance;
+pub mod init_progress;
+pub mod set_payout_split;
+pub mod finalize_vault;
+pub mod get_cap_headroom;
+pub mod batch_status;
+pub mod set_investor_count;
+pub mod open_day_simulation;
+pub mod simulate_full_day;
+pub mod close_day_simulation;
+pub mod open_day_record;
+pub mod audit_history;
 
 pub use initialize::*;
-pub use distribute::*;
\ No newline at end of file
+pub use distribute::*;
+pub use update_policy::*;
+pub use snapshot::*;
+pub use open_accrual::*;
+pub use claim_my_payout::*;
+pub use claim_fees::*;
+pub use set_test_treasury_authority::*;
+pub use set_min_payout::*;
+pub use close_day::*;
+pub use set_share_override::*;
+pub use get_effective_share::*;
+pub use force_close_day::*;
+pub use estimate_crank_cost::*;
+pub use recommended_compute_units::*;
+pub use deposit_and_recognize::*;
+pub use set_claim_paused::*;
+pub use cancel_day::*;
+pub use fund_yield_reserve::*;
+pub use remaining_distributable::*;
+pub use initialize_coordinator::*;
+pub use set_coordinator_member::*;
+pub use split_coordinator_claim::*;
+pub use get_current_snapshot::*;
+pub use migrate_quote_mint::*;
+pub use force_distribution::*;
+pub use preview_page_breakdown::*;
+pub use preview_policy_change::*;
+pub use get_investor_lifetime::*;
+pub use initialize_weight_table::*;
+pub use write_weight_table::*;
+pub use reset_circuit_breaker::*;
+pub use opt_in::*;
+pub use force_reset_progress::*;
+pub use sweep_finalized_treasury::*;
+pub use audit_balance::*;
+pub use init_progress::*;
+pub use set_payout_split::*;
+pub use finalize_vault::*;
+pub use get_cap_headroom::*;
+pub use batch_status::*;
+pub use set_investor_count::*;
+pub use open_day_simulation::*;
+pub use simulate_full_day::*;
+pub use close_day_simulation::*;
+pub use open_day_record::*;
+pub use audit_history::*;
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::read_streamflow_recipient,
+    state::*,
+};
+
+/// Open an investor's pull-mode accrual PDA - permissionless
+#[derive(Accounts)]
+pub struct OpenAccrual<'info> {
+    /// Funds the accrual account's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Investor's Streamflow stream
+    /// CHECK: Its recipient must own `investor_quote_ata`
+    pub stream_account: UncheckedAccount<'info>,
+    
+    /// Investor's quote token account; its owner, the stream's recipient, becomes the
+    /// accrual's claimant
+    #[account(token::mint = policy.quote_mint)]
+    pub investor_quote_ata: Account<'info, TokenAccount>,
+    
+    /// Investor accrual PDA
+    #[account(
+        init,
+        payer = payer,
+        space = InvestorAccrual::LEN,
+        seeds = [VAULT_SEED, vault.key().as_ref(), ACCRUAL_SEED, stream_account.key().as_ref()],
+        bump
+    )]
+    pub accrual: Account<'info, InvestorAccrual>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenAccrual>) -> Result<()> {
+    // Anyone may pay the rent, but only the stream's recipient can become the claimant
+    require_stream_recipient(&ctx.accounts.stream_account, &ctx.accounts.investor_quote_ata.owner)?;
+    
+    let accrual = &mut ctx.accounts.accrual;
+    accrual.vault = ctx.accounts.vault.key();
+    accrual.stream = ctx.accounts.stream_account.key();
+    accrual.owner = ctx.accounts.investor_quote_ata.owner;
+    accrual.claimable = 0;
+    accrual.total_claimed = 0;
+    accrual.last_claim_ts = 0;
+    accrual.last_claim_period = 0;
+    accrual.lifetime_received = 0;
+    accrual.bump = ctx.bumps.accrual;
+    
+    emit!(AccrualOpened {
+        vault: accrual.vault,
+        stream: accrual.stream,
+        owner: accrual.owner,
+    });
+    
+    Ok(())
+}
+
+/// Fail with `Unauthorized` unless `owner` is the stream's recipient
+fn require_stream_recipient(stream_account: &AccountInfo, owner: &Pubkey) -> Result<()> {
+    require_keys_eq!(
+        read_streamflow_recipient(stream_account)?,
+        *owner,
+        FeeDistributorError::Unauthorized
+    );
+    Ok(())
+}
+
+#[event]
+pub struct AccrualOpened {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_stream_recipient_can_claim_an_accrual() {
+        let (stream_key, recipient, stranger) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![0u8; 56];
+        data[16..48].copy_from_slice(recipient.as_ref());
+        let mut lamports = 0u64;
+        let owner = Pubkey::default();
+        let stream = AccountInfo::new(&stream_key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        require_stream_recipient(&stream, &recipient).unwrap();
+        // Someone else's quote ATA can't make them the claimant of this stream's payouts
+        assert_eq!(
+            require_stream_recipient(&stream, &stranger).unwrap_err(),
+            FeeDistributorError::Unauthorized.into()
+        );
+    }
+}
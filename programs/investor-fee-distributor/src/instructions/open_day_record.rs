@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Open the record a day's close writes its totals into under `record_day_history` -
+/// permissionless
+#[derive(Accounts)]
+#[instruction(day_epoch: u64)]
+pub struct OpenDayRecord<'info> {
+    /// Funds the record's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// The day's record PDA
+    #[account(
+        init,
+        payer = payer,
+        space = DayRecord::LEN,
+        seeds = [VAULT_SEED, vault.key().as_ref(), DAY_RECORD_SEED, &day_epoch.to_le_bytes()],
+        bump
+    )]
+    pub day_record: Account<'info, DayRecord>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenDayRecord>, day_epoch: u64) -> Result<()> {
+    // The running day or a later one; a closed day's totals are gone from the progress
+    let progress = &ctx.accounts.progress;
+    require!(
+        day_epoch > progress.latest_epoch || (day_epoch == progress.latest_epoch && !progress.day_completed),
+        FeeDistributorError::DayAlreadyCompleted
+    );
+    
+    let day_record = &mut ctx.accounts.day_record;
+    day_record.vault = ctx.accounts.vault.key();
+    day_record.day_epoch = day_epoch;
+    day_record.bump = ctx.bumps.day_record;
+    
+    Ok(())
+}
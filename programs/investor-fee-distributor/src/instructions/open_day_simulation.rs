@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{constants::*, state::*};
+
+/// Open a scratch account to simulate the current day into with `simulate_full_day` -
+/// permissionless
+#[derive(Accounts)]
+pub struct OpenDaySimulation<'info> {
+    /// Funds the scratch account's rent and drives the simulation
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// The owner's simulation scratch PDA
+    #[account(
+        init,
+        payer = owner,
+        space = DaySimulation::space(0),
+        seeds = [VAULT_SEED, vault.key().as_ref(), DAY_SIMULATION_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub day_simulation: Account<'info, DaySimulation>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenDaySimulation>) -> Result<()> {
+    let day_simulation = &mut ctx.accounts.day_simulation;
+    day_simulation.vault = ctx.accounts.vault.key();
+    day_simulation.owner = ctx.accounts.owner.key();
+    day_simulation.restart(&ctx.accounts.progress, ctx.accounts.treasury.amount);
+    day_simulation.bump = ctx.bumps.day_simulation;
+    
+    Ok(())
+}
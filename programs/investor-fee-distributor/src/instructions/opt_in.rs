@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::read_streamflow_recipient,
+    state::*,
+};
+
+/// Record an investor's consent to receive distributions - stream recipient only
+#[derive(Accounts)]
+pub struct OptIn<'info> {
+    /// The stream's recipient; funds the opt-in account's rent
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Investor's Streamflow stream
+    /// CHECK: Its recipient must be the signing investor
+    pub stream_account: UncheckedAccount<'info>,
+    
+    /// Investor opt-in PDA
+    #[account(
+        init,
+        payer = investor,
+        space = OptInRegistry::LEN,
+        seeds = [VAULT_SEED, vault.key().as_ref(), OPT_IN_SEED, stream_account.key().as_ref()],
+        bump
+    )]
+    pub opt_in: Account<'info, OptInRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OptIn>) -> Result<()> {
+    // Only the investor can consent, never a cranker on their behalf
+    let recipient = read_streamflow_recipient(&ctx.accounts.stream_account)?;
+    require_keys_eq!(
+        recipient,
+        ctx.accounts.investor.key(),
+        FeeDistributorError::Unauthorized
+    );
+    
+    let opt_in = &mut ctx.accounts.opt_in;
+    opt_in.vault = ctx.accounts.vault.key();
+    opt_in.stream = ctx.accounts.stream_account.key();
+    opt_in.investor = recipient;
+    opt_in.opted_in_ts = Clock::get()?.unix_timestamp;
+    opt_in.bump = ctx.bumps.opt_in;
+    
+    emit!(InvestorOptedIn {
+        vault: opt_in.vault,
+        stream: opt_in.stream,
+        investor: opt_in.investor,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct InvestorOptedIn {
+    pub vault: Pubkey,
+    pub stream: Pubkey,
+    pub investor: Pubkey,
+}
@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{
+        clamp_page_size, page_inputs, page_ledger, payout_order, require_weight_table, DistributionResult,
+        PageInputs,
+    },
+    math::*,
+    state::*,
+};
+
+/// Read what the next page of the current day would pay each of its investors -
+/// read-only
+#[derive(Accounts)]
+pub struct PreviewPageBreakdown<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Locked snapshot ledger (required when the policy distributes from a snapshot)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SNAPSHOT_SEED],
+        bump = locked_snapshot.bump,
+        has_one = vault,
+    )]
+    pub locked_snapshot: Option<Account<'info, LockedSnapshot>>,
+    
+    /// Share multipliers and minimums (required in live-stream mode while the policy
+    /// has overrides, and in every mode while one carries a minimum payout)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+    )]
+    pub share_overrides: Option<Account<'info, ShareOverrides>>,
+    
+    /// Committed weight table (required when the policy distributes from one)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump = weight_table.bump,
+        has_one = vault,
+    )]
+    pub weight_table: Option<Account<'info, WeightTable>>,
+    
+    // Remaining accounts:
+    // The page's [investor_quote_ata or accrual, stream_account] pairs from
+    // pagination_cursor, exactly as distribute_fees would receive them
+}
+
+/// One investor's line of a page preview, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PagePayoutPreview {
+    pub investor_index: u32,
+    /// Locked amount the page weighs the investor by
+    pub locked: u64,
+    /// The investor's share of the page's (or day's) total weight
+    pub weight_bps: u16,
+    /// What the investor would receive, catch-up included; 0 when skipped
+    pub payout: u64,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PreviewPageBreakdown<'info>>,
+    page_size: u8,
+) -> Result<Vec<PagePayoutPreview>> {
+    let policy = &ctx.accounts.policy;
+    let progress = &ctx.accounts.progress;
+    
+    // The next crank starts a new day from a claim that can't be seen yet
+    require!(
+        !progress.day_completed && progress.last_distribution_ts > 0,
+        FeeDistributorError::DayAlreadyCompleted
+    );
+    if policy.weight_source == WeightSource::OnChainTable {
+        require_weight_table(ctx.accounts.weight_table.as_deref(), progress, progress.latest_epoch)?;
+    }
+    
+    // The crank's own page, cut down to what return data can hold
+    let page_size = std::cmp::min(clamp_page_size(policy, page_size)?, MAX_PREVIEW_PAGE_SIZE);
+    let (start_idx, end_idx) = page_bounds(progress.pagination_cursor, page_size, progress.total_investors)?;
+    require!(start_idx < end_idx, FeeDistributorError::InvalidPaginationCursor);
+    
+    let page_accounts = (end_idx - start_idx) * 2;
+    let investor_accounts = ctx
+        .remaining_accounts
+        .get(..page_accounts)
+        .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+    
+    let inputs = page_inputs(
+        policy,
+        progress,
+        page_ledger(
+            policy,
+            ctx.accounts.locked_snapshot.as_ref(),
+            ctx.accounts.weight_table.as_ref(),
+        ),
+        ctx.accounts.share_overrides.as_deref(),
+        investor_accounts,
+        start_idx,
+        Clock::get()?.unix_timestamp,
+    )?;
+    
+    match inputs {
+        Some(inputs) => preview_payouts(policy, &inputs, start_idx),
+        // Nothing locked: the page pays no one
+        None => (start_idx..end_idx)
+            .map(|index| {
+                Ok(PagePayoutPreview {
+                    investor_index: to_investor_index(index)?,
+                    locked: 0,
+                    weight_bps: 0,
+                    payout: 0,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Lines of a page preview, in account order (see `page_payout_walk`)
+pub(crate) fn preview_payouts(
+    policy: &DistributionPolicy,
+    inputs: &PageInputs,
+    start_idx: usize,
+) -> Result<Vec<PagePayoutPreview>> {
+    let (payouts, _) = page_payout_walk(policy, inputs)?;
+    payout_lines(inputs, payouts, start_idx)
+}
+
+/// Pair each investor of the page with their payout and weight share
+pub(crate) fn payout_lines(
+    inputs: &PageInputs,
+    payouts: Vec<u64>,
+    start_idx: usize,
+) -> Result<Vec<PagePayoutPreview>> {
+    inputs
+        .locked_amounts
+        .iter()
+        .zip(payouts)
+        .enumerate()
+        .map(|(i, (&(locked, weight), payout))| {
+            let weight_bps = (weight as u128 * BASIS_POINTS_DIVISOR as u128)
+                .checked_div(inputs.weight_total as u128)
+                .unwrap_or(0);
+            Ok(PagePayoutPreview {
+                investor_index: to_investor_index(start_idx + i)?,
+                locked,
+                weight_bps: u16::try_from(weight_bps).map_err(|_| FeeDistributorError::ArithmeticOverflow)?,
+                payout,
+            })
+        })
+        .collect()
+}
+
+/// Walk the page in payout order the way `distribute_fees` does, stopping at the
+/// first payout the remaining pool can't cover (passing over it in deterministic index
+/// order). Returns each investor's payout, catch-up
+/// included, in account order, and the page's result as the crank would record it.
+///
+/// Assumes every investor account can receive: a payout the crank redirects to the
+/// fallback or skips for its ATA still counts here. Payouts are gross of withholding.
+pub(crate) fn page_payout_walk(
+    policy: &DistributionPolicy,
+    inputs: &PageInputs,
+) -> Result<(Vec<u64>, DistributionResult)> {
+    let mut scheme_payouts = inputs.scheme_payouts(policy)?;
+    inputs.absorb_last_investor_dust(policy, &mut scheme_payouts)?;
+    let mut payouts = vec![0u64; inputs.locked_amounts.len()];
+    let mut total_distributed = 0u64;
+    let mut total_backpay = 0u64;
+    let mut total_withheld = 0u64;
+    let mut investors_paid = 0u8;
+    
+    let mut available = inputs.available;
+    for i in payout_order(policy.payout_priority, &inputs.locked_amounts) {
+        if inputs.locked_amounts[i].0 == 0 {
+            continue;
+        }
+        
+        let backpay = inputs.backpays[i];
+        let payout = match scheme_payouts[i] {
+            Some(payout) => payout,
+            None if backpay > 0 => 0,
+            None => continue,
+        };
+        if inputs.investor_slots.is_some_and(|slots| u32::from(investors_paid) >= slots) {
+            continue;
+        }
+        if payout > available {
+            if policy.deterministic_index_order {
+                continue;
+            }
+            break;
+        }
+        
+        available = available.saturating_sub(payout);
+        payouts[i] = payout
+            .checked_add(backpay)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        total_distributed = total_distributed
+            .checked_add(payout)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        total_backpay = total_backpay
+            .checked_add(backpay)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        total_withheld += withholding_split(payouts[i], policy.withholding_bps)?.1;
+        investors_paid += 1;
+    }
+    
+    Ok((
+        payouts,
+        DistributionResult {
+            total_distributed,
+            remaining_dust: available,
+            investors_paid,
+            investor_pool: inputs.distributable,
+            total_backpay,
+            total_withheld,
+        },
+    ))
+}
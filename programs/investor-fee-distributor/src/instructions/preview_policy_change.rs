@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::{
+        distribute::{clamp_page_size, page_inputs, page_ledger, require_weight_table},
+        preview_page_breakdown::page_payout_walk,
+        update_policy::{apply_policy_params, UpdatePolicyParams},
+    },
+    math::*,
+    state::*,
+};
+
+/// Read what the next page of the current day would pay under a proposed policy
+/// change, next to what it pays under the live policy - read-only
+///
+/// The proposal goes through `update_policy`'s own validation on a copy of the
+/// policy, so a change it would reject fails here too; nothing is written.
+#[derive(Accounts)]
+pub struct PreviewPolicyChange<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy the proposal is applied to
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Locked snapshot ledger (required when either policy distributes from a snapshot)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SNAPSHOT_SEED],
+        bump = locked_snapshot.bump,
+        has_one = vault,
+    )]
+    pub locked_snapshot: Option<Account<'info, LockedSnapshot>>,
+    
+    /// Share multipliers and minimums, as `distribute_fees` requires them under either
+    /// policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+    )]
+    pub share_overrides: Option<Account<'info, ShareOverrides>>,
+    
+    /// Committed weight table (required when either policy distributes from one)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump = weight_table.bump,
+        has_one = vault,
+    )]
+    pub weight_table: Option<Account<'info, WeightTable>>,
+    
+    // Remaining accounts:
+    // The page's [investor_quote_ata or accrual, stream_account] pairs from
+    // pagination_cursor, exactly as distribute_fees would receive them
+}
+
+/// A page's totals under one policy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProjectedPage {
+    /// The page's (live streams) or day's (snapshot) investor pool
+    pub investor_pool: u64,
+    pub total_distributed: u64,
+    pub total_backpay: u64,
+    pub investors_paid: u8,
+    /// Left unpaid out of what the page may pay, carry-over dust included
+    pub remaining_dust: u64,
+}
+
+/// One investor's payout under the live and the proposed policy, catch-up included
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolicyChangeLine {
+    pub investor_index: u32,
+    pub current_payout: u64,
+    pub proposed_payout: u64,
+}
+
+/// The page under both policies, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PolicyChangePreview {
+    pub current: ProjectedPage,
+    pub proposed: ProjectedPage,
+    /// In account order
+    pub lines: Vec<PolicyChangeLine>,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PreviewPolicyChange<'info>>,
+    page_size: u8,
+    params: UpdatePolicyParams,
+) -> Result<PolicyChangePreview> {
+    let policy = &ctx.accounts.policy;
+    let progress = &ctx.accounts.progress;
+    
+    // The next crank starts a new day from a claim that can't be seen yet
+    require!(
+        !progress.day_completed && progress.last_distribution_ts > 0,
+        FeeDistributorError::DayAlreadyCompleted
+    );
+    
+    let mut proposed = (**policy).clone();
+    apply_policy_params(&mut proposed, progress, params)?;
+    
+    // Both policies see the same investors: the smaller of their clamped pages, cut
+    // down to what return data can hold
+    let page_size = clamp_page_size(policy, page_size)?
+        .min(clamp_page_size(&proposed, page_size)?)
+        .min(MAX_PREVIEW_PAGE_SIZE);
+    let (start_idx, end_idx) = page_bounds(progress.pagination_cursor, page_size, progress.total_investors)?;
+    require!(start_idx < end_idx, FeeDistributorError::InvalidPaginationCursor);
+    
+    let page_accounts = (end_idx - start_idx) * 2;
+    let investor_accounts = ctx
+        .remaining_accounts
+        .get(..page_accounts)
+        .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+    let current_ts = Clock::get()?.unix_timestamp;
+    
+    let (current_payouts, current) = project_page(&ctx, policy, investor_accounts, start_idx, current_ts)?;
+    let (proposed_payouts, proposed) = project_page(&ctx, &proposed, investor_accounts, start_idx, current_ts)?;
+    
+    let lines = current_payouts
+        .into_iter()
+        .zip(proposed_payouts)
+        .enumerate()
+        .map(|(i, (current_payout, proposed_payout))| {
+            Ok(PolicyChangeLine {
+                investor_index: to_investor_index(start_idx + i)?,
+                current_payout,
+                proposed_payout,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    
+    Ok(PolicyChangePreview { current, proposed, lines })
+}
+
+/// The page's payouts and totals under `policy`, through the crank's own page inputs
+/// and payout walk
+fn project_page<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, PreviewPolicyChange<'info>>,
+    policy: &DistributionPolicy,
+    investor_accounts: &[AccountInfo<'info>],
+    start_idx: usize,
+    current_ts: i64,
+) -> Result<(Vec<u64>, ProjectedPage)> {
+    let progress = &ctx.accounts.progress;
+    if policy.weight_source == WeightSource::OnChainTable {
+        require_weight_table(ctx.accounts.weight_table.as_deref(), progress, progress.latest_epoch)?;
+    }
+    
+    let inputs = page_inputs(
+        policy,
+        progress,
+        page_ledger(
+            policy,
+            ctx.accounts.locked_snapshot.as_ref(),
+            ctx.accounts.weight_table.as_ref(),
+        ),
+        ctx.accounts.share_overrides.as_deref(),
+        investor_accounts,
+        start_idx,
+        current_ts,
+    )?;
+    
+    // Nothing locked: the page pays no one and passes the dust on
+    let Some(inputs) = inputs else {
+        let projected = ProjectedPage {
+            remaining_dust: progress.carry_over_dust,
+            ..ProjectedPage::default()
+        };
+        return Ok((vec![0; investor_accounts.len() / 2], projected));
+    };
+    
+    let (payouts, result) = page_payout_walk(policy, &inputs)?;
+    Ok((
+        payouts,
+        ProjectedPage {
+            investor_pool: result.investor_pool,
+            total_distributed: result.total_distributed,
+            total_backpay: result.total_backpay,
+            investors_paid: result.investors_paid,
+            remaining_dust: result.remaining_dust,
+        },
+    ))
+}
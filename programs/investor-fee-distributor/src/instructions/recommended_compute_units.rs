@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::{
+        distribute::clamp_page_size,
+        estimate_crank_cost::{page_base_cu, pays_in_installments, per_investor_cu, split_transfer_cu},
+    },
+    math::apply_bps,
+    state::*,
+};
+
+/// Recommend the compute unit limit to request for one distribute_fees page - read-only
+#[derive(Accounts)]
+pub struct RecommendedComputeUnits<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+}
+
+/// Compute budget for one page, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComputeUnitRecommendation {
+    /// Page size the recommendation is for, clamped as distribute_fees clamps it
+    pub page_size: u8,
+    /// Units to request with `SetComputeUnitLimit`, at most `MAX_TRANSACTION_CU`
+    pub compute_units: u32,
+    /// Whether the page fits one transaction's compute, margin included; when not,
+    /// crank a smaller page
+    pub fits_transaction: bool,
+}
+
+pub(crate) fn handler(ctx: Context<RecommendedComputeUnits>, page_size: u8) -> Result<ComputeUnitRecommendation> {
+    page_compute_recommendation(&ctx.accounts.policy, page_size)
+}
+
+/// The worst-case page of `page_size` investors under the policy's current settings:
+/// every investor paid, as many split payouts as the page can hold, installments up to
+/// `MAX_PAGE_TRANSFERS`, and a creator transfer, plus `RECOMMENDED_CU_MARGIN_BPS` of
+/// headroom
+pub(crate) fn page_compute_recommendation(
+    policy: &DistributionPolicy,
+    page_size: u8,
+) -> Result<ComputeUnitRecommendation> {
+    let page_size = clamp_page_size(policy, page_size)?;
+    let investors = page_size as u64;
+    let split_transfers = if policy.distribution_mode == DistributionMode::Push {
+        std::cmp::min(investors, policy.payout_split_count as u64)
+    } else {
+        0
+    };
+    // Every transfer the page's limit leaves after each payout's first, its split legs
+    // and withheld shares may be an installment
+    let installment_transfers = if pays_in_installments(policy) {
+        let withheld = if policy.withholding_bps > 0 { investors } else { 0 };
+        (MAX_PAGE_TRANSFERS as u64).saturating_sub(investors + split_transfers + withheld)
+    } else {
+        0
+    };
+    
+    let page_cu = [
+        Some(page_base_cu(policy)),
+        investors.checked_mul(per_investor_cu(policy)),
+        split_transfers.checked_mul(split_transfer_cu(policy)),
+        installment_transfers.checked_mul(CRANK_INSTALLMENT_TRANSFER_CU),
+        Some(CRANK_CREATOR_LEG_CU),
+    ]
+    .into_iter()
+    .try_fold(0u64, |total, cu| total.checked_add(cu?))
+    .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    let recommended = page_cu
+        .checked_add(apply_bps(page_cu, RECOMMENDED_CU_MARGIN_BPS)?)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    
+    Ok(ComputeUnitRecommendation {
+        page_size,
+        compute_units: std::cmp::min(recommended, MAX_TRANSACTION_CU) as u32,
+        fits_transaction: recommended <= MAX_TRANSACTION_CU,
+    })
+}
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{clamp_page_size, read_streamflow_locked_amount},
+    math::*,
+    state::*,
+};
+
+/// Read how much the current day can still distribute to investors - read-only
+#[derive(Accounts)]
+pub struct RemainingDistributable<'info> {
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    // Remaining accounts (LiveStreams mode only):
+    // The stream_account of every investor from pagination_cursor to the end of the set
+}
+
+/// What is left of the current day's investor pool, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RemainingPool {
+    /// What further pages can still pay investors: the unpaid pool, bounded by the
+    /// treasury funds not reserved for anything else
+    pub remaining: u64,
+    /// The day's investor pool after every cap, including what was already paid
+    pub day_pool: u64,
+    /// Already paid or allocated to investors today
+    pub distributed: u64,
+    /// Treasury balance less pull-mode liabilities and claims held for later days
+    pub treasury_available: u64,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemainingDistributable<'info>>,
+    page_size: u8,
+    as_of_ts: Option<i64>,
+) -> Result<RemainingPool> {
+    let policy = &ctx.accounts.policy;
+    let progress = &ctx.accounts.progress;
+    let distributed = progress.current_day_distributed_investors;
+    
+    let reserved = progress
+        .pull_outstanding
+        .saturating_add(progress.current_period_claimed)
+        .saturating_add(progress.deferred_investor_pool);
+    let treasury_available = ctx.accounts.treasury.amount.saturating_sub(reserved);
+    
+    // The next crank starts a new day from a claim that can't be seen yet
+    if progress.day_completed {
+        return Ok(RemainingPool {
+            remaining: 0,
+            day_pool: distributed,
+            distributed,
+            treasury_available,
+        });
+    }
+    
+    let caps = DistributionCaps {
+        investor_fee_share_bps: policy.effective_investor_share_bps(),
+        f_locked: 0,
+        daily_cap_lamports: policy.daily_cap_lamports,
+        deferred_pool: progress.current_day_deferred,
+        daily_distributed: 0,
+        lifetime_headroom: progress.lifetime_headroom(policy.lifetime_cap_lamports),
+        proportional_daily_cap: false,
+    };
+    
+    let unpaid_pool = match policy.weight_source {
+        WeightSource::LiveStreams => {
+            // Same pages as the crank will send, each with its own locked fraction
+            let page_size = usize::from(clamp_page_size(policy, page_size)?);
+            let remaining_investors = usize::try_from(
+                progress.total_investors.saturating_sub(progress.pagination_cursor),
+            )
+            .map_err(|_| FeeDistributorError::InvalidPaginationCursor)?;
+            let stream_accounts = ctx
+                .remaining_accounts
+                .get(..remaining_investors)
+                .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+            
+            let as_of_ts = resolve_as_of_ts(as_of_ts, Clock::get()?.unix_timestamp)?;
+            let mut page_f_locked = Vec::new();
+            for page in stream_accounts.chunks(page_size) {
+                let mut page_locked: u64 = 0;
+                for stream_account in page {
+                    page_locked = page_locked
+                        .checked_add(read_streamflow_locked_amount(stream_account, as_of_ts)?)
+                        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+                }
+                page_f_locked.push(policy.pool_locked_fraction(page_locked)?);
+            }
+            
+            let caps = DistributionCaps {
+                daily_distributed: distributed,
+                proportional_daily_cap: policy.daily_cap_allocation == DailyCapAllocation::Proportional,
+                ..caps
+            };
+            remaining_pages_pool(caps, progress.current_day_claimed, &page_f_locked)?
+                .checked_add(progress.carry_over_dust)
+                .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        }
+        WeightSource::DailySnapshot | WeightSource::OnChainTable => {
+            // One day-level pool, exactly as every page of the day computes it
+            let total_locked = progress.total_locked_today;
+            let day_pool = if total_locked == 0 {
+                0
+            } else {
+                DistributionCaps {
+                    f_locked: policy.pool_locked_fraction(total_locked)?,
+                    ..caps
+                }
+                .distributable(progress.current_day_claimed)?
+            };
+            day_pool
+                .saturating_add(progress.persisted_dust)
+                .saturating_sub(distributed)
+        }
+    };
+    
+    Ok(RemainingPool {
+        remaining: std::cmp::min(unpaid_pool, treasury_available),
+        day_pool: distributed.saturating_add(unpaid_pool),
+        distributed,
+        treasury_available,
+    })
+}
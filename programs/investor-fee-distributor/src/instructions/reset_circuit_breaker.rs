@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Accept the claim the circuit breaker stopped and let days start again - authority
+/// only
+#[derive(Accounts)]
+pub struct ResetCircuitBreaker<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress holding the tripped breaker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+pub(crate) fn handler(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+    let progress = &mut ctx.accounts.progress;
+    require!(
+        progress.circuit_breaker == CircuitBreakerState::Tripped,
+        FeeDistributorError::CircuitBreakerNotTripped
+    );
+    
+    // The next day start lets the pending claim through once; a claim that shouldn't
+    // be distributed is dropped with cancel_day first
+    progress.circuit_breaker = CircuitBreakerState::Cleared;
+    
+    emit!(CircuitBreakerReset {
+        vault: progress.vault,
+        authority: ctx.accounts.authority.key(),
+        pending_claim: progress.current_period_claimed,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CircuitBreakerReset {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Claims the next day start will distribute (plus anything recognized at its start)
+    pub pending_claim: u64,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::instructions::update_policy::UpdatePolicy;
+
+/// Pause or resume DAMM fee claims without stopping distribution.
+///
+/// Days keep starting and paging while claims are paused; they distribute what is
+/// already in the treasury. Claims only happen at the start of a day or through
+/// `claim_fees`, so this may be toggled mid-day.
+pub(crate) fn handler(ctx: Context<UpdatePolicy>, claim_paused: bool) -> Result<()> {
+    let policy = &mut ctx.accounts.policy;
+    let old_claim_paused = policy.claim_paused;
+    policy.claim_paused = claim_paused;
+    
+    emit!(ClaimPauseUpdated {
+        vault: policy.vault,
+        authority: policy.authority,
+        old_claim_paused,
+        claim_paused,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ClaimPauseUpdated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_claim_paused: bool,
+    pub claim_paused: bool,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Add a vault to a coordinator, change its share or remove it - authority only
+#[derive(Accounts)]
+#[instruction(share_bps: u16)]
+pub struct SetCoordinatorMember<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Coordinator, resized to the new member count
+    #[account(
+        mut,
+        seeds = [COORDINATOR_SEED, coordinator.coordinator_id.as_ref()],
+        bump = coordinator.bump,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        realloc = TreasuryCoordinator::space(coordinator.len_after(&member_vault.key(), share_bps)),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub coordinator: Account<'info, TreasuryCoordinator>,
+    
+    /// The member vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub member_vault: UncheckedAccount<'info>,
+    
+    /// The member vault's policy; the coordinator authority must also govern it
+    #[account(
+        seeds = [VAULT_SEED, member_vault.key().as_ref(), POLICY_SEED],
+        bump = member_policy.bump,
+        constraint = member_policy.vault == member_vault.key(),
+        constraint = member_policy.authority == authority.key() @ FeeDistributorError::Unauthorized,
+        constraint = member_policy.quote_mint == coordinator.quote_mint @ FeeDistributorError::InvalidCoordinatorMember,
+    )]
+    pub member_policy: Account<'info, DistributionPolicy>,
+    
+    /// The member vault's treasury, which will receive its share
+    #[account(
+        seeds = [VAULT_SEED, member_vault.key().as_ref(), TREASURY_SEED],
+        bump,
+    )]
+    pub member_treasury: Account<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<SetCoordinatorMember>, share_bps: u16) -> Result<()> {
+    require!(
+        share_bps as u64 <= BASIS_POINTS_DIVISOR,
+        FeeDistributorError::InvalidBasisPoints
+    );
+    
+    let vault = ctx.accounts.member_vault.key();
+    let coordinator = &mut ctx.accounts.coordinator;
+    let old_share_bps = coordinator.share_bps(&vault);
+    
+    // Keep the member's lifetime allocation when only its share changes
+    let total_allocated = coordinator
+        .members
+        .iter()
+        .find(|member| member.vault == vault)
+        .map_or(0, |member| member.total_allocated);
+    coordinator.members.retain(|member| member.vault != vault);
+    
+    if share_bps > 0 {
+        require!(
+            coordinator.members.len() < MAX_COORDINATOR_MEMBERS,
+            FeeDistributorError::TooManyCoordinatorMembers
+        );
+        coordinator.members.push(CoordinatorMember {
+            vault,
+            treasury: ctx.accounts.member_treasury.key(),
+            share_bps,
+            total_allocated,
+        });
+    }
+    
+    // Shares may add up to less than 100% while members are being set up; splits
+    // wait until they add up to exactly 100%
+    require!(
+        coordinator.total_share_bps() <= BASIS_POINTS_DIVISOR,
+        FeeDistributorError::InvalidBasisPoints
+    );
+    
+    emit!(CoordinatorMemberSet {
+        coordinator: coordinator.key(),
+        authority: ctx.accounts.authority.key(),
+        vault,
+        old_share_bps,
+        share_bps,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CoordinatorMemberSet {
+    pub coordinator: Pubkey,
+    pub authority: Pubkey,
+    pub vault: Pubkey,
+    /// Share before the change (0 when the vault wasn't a member)
+    pub old_share_bps: u16,
+    pub share_bps: u16,
+}
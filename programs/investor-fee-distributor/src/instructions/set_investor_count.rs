@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Add investors to the end of the distribution set or prune them from it - authority
+/// only, between days
+///
+/// Investors are identified by index, so the set only changes at its end: growing it
+/// appends new indices, shrinking it drops the last ones. Every change bumps the
+/// progress `set_version`; a snapshot taken under an older version is stale and must
+/// be retaken before the next day starts.
+#[derive(Accounts)]
+pub struct SetInvestorCount<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress holding the set's size and version
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+pub(crate) fn handler(ctx: Context<SetInvestorCount>, total_investors: u32) -> Result<()> {
+    // Pages of one day must all see the same set
+    let progress = &mut ctx.accounts.progress;
+    require!(!progress.is_mid_day(), FeeDistributorError::DayInProgress);
+    
+    let old_total_investors = progress.total_investors;
+    if total_investors == old_total_investors {
+        return Ok(());
+    }
+    
+    // A top-N day ranks the whole set on its first page
+    require!(
+        ctx.accounts.policy.top_n_investors == 0 || total_investors <= MAX_TOP_N_INVESTOR_SET,
+        FeeDistributorError::TopNInvestorSetTooLarge
+    );
+    
+    // A weight table is committed for a set of this size, so it goes stale by itself
+    progress.total_investors = total_investors;
+    progress.set_version = progress.set_version.wrapping_add(1);
+    
+    emit!(InvestorSetChanged {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        old_total_investors,
+        total_investors,
+        set_version: progress.set_version,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct InvestorSetChanged {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old_total_investors: u32,
+    pub total_investors: u32,
+    /// Version a snapshot must be taken under to distribute the next day
+    pub set_version: u64,
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{errors::FeeDistributorError, instructions::update_policy::UpdatePolicy};
+
+/// Change only `min_payout_lamports`, in the policy's `min_payout_unit`.
+///
+/// The dust threshold is operational: it only decides whether tiny payouts are sent
+/// now or left for the creator remainder, and never changes the investor/creator
+/// split. Operators retune it as fee volume moves, so it gets its own single-field
+/// path instead of going through `update_policy` with the governance-critical knobs.
+pub(crate) fn handler(ctx: Context<UpdatePolicy>, min_payout_lamports: u64) -> Result<()> {
+    // Same guard as update_policy: pages of one day must share one threshold
+    require!(
+        !ctx.accounts.progress.is_mid_day(),
+        FeeDistributorError::DayInProgress
+    );
+    
+    let policy = &mut ctx.accounts.policy;
+    let old = policy.min_payout_lamports;
+    policy.min_payout_lamports = min_payout_lamports;
+    policy.check_quote_amounts()?;
+    
+    emit!(MinPayoutUpdated {
+        vault: policy.vault,
+        authority: policy.authority,
+        old,
+        new: min_payout_lamports,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct MinPayoutUpdated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub old: u64,
+    pub new: u64,
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Split an investor's pushed payouts with a second ATA of theirs, or stop splitting
+/// them - authority only
+#[derive(Accounts)]
+#[instruction(stream: Pubkey, payout_split: Option<PayoutSplit>)]
+pub struct SetPayoutSplit<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Share overrides registry, resized to the new entry count
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+        realloc = ShareOverrides::space(share_overrides.len_after_split(&stream, payout_split)),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub share_overrides: Account<'info, ShareOverrides>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<SetPayoutSplit>, stream: Pubkey, payout_split: Option<PayoutSplit>) -> Result<()> {
+    // A split sending nothing or everything to the second ATA is a single destination
+    if let Some(split) = payout_split {
+        require!(
+            split.secondary_bps > 0
+                && split.secondary_bps < BASIS_POINTS_DIVISOR as u16
+                && split.secondary_ata != Pubkey::default(),
+            FeeDistributorError::InvalidPayoutSplit
+        );
+    }
+    
+    // Weights don't change, so snapshots and weight tables stay valid
+    let share_overrides = &mut ctx.accounts.share_overrides;
+    let multiplier_bps = share_overrides.multiplier_bps(&stream);
+    let min_payout_override = share_overrides.min_payout_override(&stream);
+    let old_payout_split = share_overrides.payout_split(&stream);
+    share_overrides.entries.retain(|entry| entry.stream != stream);
+    
+    if ShareOverride::is_kept(multiplier_bps, min_payout_override, payout_split) {
+        require!(
+            share_overrides.entries.len() < MAX_SHARE_OVERRIDES,
+            FeeDistributorError::TooManyShareOverrides
+        );
+        share_overrides.entries.push(ShareOverride {
+            stream,
+            multiplier_bps,
+            min_payout_override,
+            payout_split,
+        });
+    }
+    
+    ctx.accounts.policy.share_override_count = share_overrides.entries.len() as u16;
+    ctx.accounts.policy.payout_split_count = share_overrides.payout_split_count() as u16;
+    
+    emit!(PayoutSplitSet {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        stream,
+        old_payout_split,
+        payout_split,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct PayoutSplitSet {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub stream: Pubkey,
+    pub old_payout_split: Option<PayoutSplit>,
+    pub payout_split: Option<PayoutSplit>,
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, math::check_quote_amounts, state::*};
+
+/// Set an investor's negotiated share multiplier and minimum payout - authority only,
+/// between days
+#[derive(Accounts)]
+#[instruction(stream: Pubkey, multiplier_bps: u16, min_payout_override: Option<u64>)]
+pub struct SetShareOverride<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress (rejects updates mid-day, invalidates a pending snapshot)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Share overrides registry, resized to the new entry count
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+        realloc = ShareOverrides::space(share_overrides.len_after(&stream, multiplier_bps, min_payout_override)),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub share_overrides: Account<'info, ShareOverrides>,
+    
+    /// Weight table (required with `OnChainTable` weights), invalidated like a snapshot
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump = weight_table.bump,
+        has_one = vault,
+    )]
+    pub weight_table: Option<Account<'info, WeightTable>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetShareOverride>,
+    stream: Pubkey,
+    multiplier_bps: u16,
+    min_payout_override: Option<u64>,
+) -> Result<()> {
+    // Weights must not change between pages of one day
+    require!(
+        !ctx.accounts.progress.is_mid_day(),
+        FeeDistributorError::DayInProgress
+    );
+    require!(
+        multiplier_bps > 0 && multiplier_bps <= MAX_SHARE_MULTIPLIER_BPS,
+        FeeDistributorError::InvalidShareMultiplier
+    );
+    // Held to the same plausibility bounds as the policy's own minimum
+    if let Some(min_payout) = min_payout_override {
+        let policy = &ctx.accounts.policy;
+        check_quote_amounts(policy.daily_cap_lamports, min_payout, policy.quote_decimals)?;
+    }
+    
+    let share_overrides = &mut ctx.accounts.share_overrides;
+    let old_multiplier_bps = share_overrides.multiplier_bps(&stream);
+    let old_min_payout_override = share_overrides.min_payout_override(&stream);
+    let payout_split = share_overrides.payout_split(&stream);
+    share_overrides.entries.retain(|entry| entry.stream != stream);
+    
+    // 1x with the policy's minimum and no split is what no entry means
+    if ShareOverride::is_kept(multiplier_bps, min_payout_override, payout_split) {
+        require!(
+            share_overrides.entries.len() < MAX_SHARE_OVERRIDES,
+            FeeDistributorError::TooManyShareOverrides
+        );
+        share_overrides.entries.push(ShareOverride {
+            stream,
+            multiplier_bps,
+            min_payout_override,
+            payout_split,
+        });
+    }
+    
+    ctx.accounts.policy.share_override_count = share_overrides.entries.len() as u16;
+    ctx.accounts.policy.min_payout_override_count = share_overrides.min_payout_override_count() as u16;
+    
+    // A snapshot taken for the upcoming day froze the old weights; retake it
+    ctx.accounts.progress.snapshot_ts = 0;
+    ctx.accounts.progress.snapshot_cursor = 0;
+    // So did a committed weight table
+    match &mut ctx.accounts.weight_table {
+        Some(weight_table) => weight_table.reset(0),
+        None => require!(
+            ctx.accounts.policy.weight_source != WeightSource::OnChainTable,
+            FeeDistributorError::MissingWeightTable
+        ),
+    }
+    
+    emit!(ShareOverrideSet {
+        vault: ctx.accounts.vault.key(),
+        authority: ctx.accounts.authority.key(),
+        stream,
+        old_multiplier_bps,
+        multiplier_bps,
+        old_min_payout_override,
+        min_payout_override,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct ShareOverrideSet {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub stream: Pubkey,
+    /// Multiplier before the change (1x when the stream had no override)
+    pub old_multiplier_bps: u16,
+    pub multiplier_bps: u16,
+    /// Minimum payout before the change (`None` = the policy's)
+    pub old_min_payout_override: Option<u64>,
+    pub min_payout_override: Option<u64>,
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+#[cfg(feature = "test-treasury-authority")]
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType, SetAuthority};
+#[cfg(feature = "test-treasury-authority")]
+use crate::instructions::distribute::treasury_authority_seeds;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Hand the treasury to a user-controlled wallet so tests can inspect and drain it.
+/// The authority change is only compiled with the `test-treasury-authority` feature;
+/// production builds always reject the instruction, so vaults keep the PDA authority.
+#[derive(Accounts)]
+pub struct SetTestTreasuryAuthority<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Program's quote treasury
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+        token::authority = treasury_authority,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// Treasury authority PDA, checked against the bump the policy recorded
+    /// CHECK: PDA that currently owns the treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), INVESTOR_FEE_POS_OWNER_SEED],
+        bump = policy.treasury_authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    
+    /// Wallet that becomes the treasury owner
+    /// CHECK: Any key; the test harness controls it
+    pub new_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg(not(feature = "test-treasury-authority"))]
+pub(crate) fn handler(_ctx: Context<SetTestTreasuryAuthority>) -> Result<()> {
+    err!(FeeDistributorError::TestFeatureDisabled)
+}
+
+#[cfg(feature = "test-treasury-authority")]
+pub(crate) fn handler(ctx: Context<SetTestTreasuryAuthority>) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let bump = [ctx.accounts.policy.treasury_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+    
+    token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.treasury_authority.to_account_info(),
+                account_or_mint: ctx.accounts.treasury.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::AccountOwner,
+        Some(ctx.accounts.new_authority.key()),
+    )?;
+    
+    emit!(TestTreasuryAuthoritySet {
+        vault: vault_key,
+        authority: ctx.accounts.authority.key(),
+        old_owner: ctx.accounts.treasury_authority.key(),
+        new_owner: ctx.accounts.new_authority.key(),
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct TestTreasuryAuthoritySet {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    /// Treasury owner before the change (the treasury authority PDA)
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::{
+        distribute::{
+            clamp_page_size, close_day_amounts, is_stranded_close, page_creator_payout, page_inputs,
+            page_ledger, record_page, require_weight_table, DistributionResult,
+        },
+        preview_page_breakdown::{page_payout_walk, payout_lines, PagePayoutPreview},
+    },
+    math::*,
+    state::*,
+};
+
+/// Simulate the next page of the current day into the owner's scratch account, closing
+/// the simulated day once its last page is in - owner only, moves no funds
+///
+/// Each call takes the page `distribute_fees` would take next from the simulated
+/// cursor, so a client drives it page by page like the crank and then reads the
+/// scratch account for the full table and the creator's settlement. Pages go through
+/// the crank's own pool sizing, payout walk, progress bookkeeping and day close, so
+/// totals and rounding match a real day cranked at the same time with the same page
+/// sizes.
+#[derive(Accounts)]
+#[instruction(page_size: u8)]
+pub struct SimulateFullDay<'info> {
+    /// The simulation's owner; funds the scratch account's growth
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Program's quote treasury
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = policy.quote_mint,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    /// The owner's simulation scratch PDA, grown by the page's lines
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), DAY_SIMULATION_SEED, owner.key().as_ref()],
+        bump = day_simulation.bump,
+        has_one = vault,
+        has_one = owner @ FeeDistributorError::Unauthorized,
+        realloc = DaySimulation::space(day_simulation.len_after_page(
+            &progress,
+            treasury.amount,
+            std::cmp::min(page_size, MAX_PAGE_SIZE),
+        )),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub day_simulation: Account<'info, DaySimulation>,
+    
+    /// Locked snapshot ledger (required when the policy distributes from a snapshot)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SNAPSHOT_SEED],
+        bump = locked_snapshot.bump,
+        has_one = vault,
+    )]
+    pub locked_snapshot: Option<Account<'info, LockedSnapshot>>,
+    
+    /// Share multipliers and minimums, as `distribute_fees` requires them
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+    )]
+    pub share_overrides: Option<Account<'info, ShareOverrides>>,
+    
+    /// Committed weight table (required when the policy distributes from one)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump = weight_table.bump,
+        has_one = vault,
+    )]
+    pub weight_table: Option<Account<'info, WeightTable>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Remaining accounts:
+    // The page's [investor_quote_ata or accrual, stream_account] pairs from the
+    // simulated cursor, exactly as distribute_fees would receive them
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SimulateFullDay<'info>>,
+    page_size: u8,
+) -> Result<()> {
+    let policy = &ctx.accounts.policy;
+    let progress = &ctx.accounts.progress;
+    
+    // The next crank starts a new day from a claim that can't be seen yet
+    require!(
+        !progress.day_completed && progress.last_distribution_ts > 0,
+        FeeDistributorError::DayAlreadyCompleted
+    );
+    if policy.weight_source == WeightSource::OnChainTable {
+        require_weight_table(ctx.accounts.weight_table.as_deref(), progress, progress.latest_epoch)?;
+    }
+    let page_size = clamp_page_size(policy, page_size)?;
+    let current_ts = Clock::get()?.unix_timestamp;
+    
+    // A page cranked since the simulation started makes it stale; it then restarts from
+    // where the real day stands
+    let treasury_balance = ctx.accounts.treasury.amount;
+    let simulation = &mut ctx.accounts.day_simulation;
+    if !simulation.is_current_for(progress, treasury_balance) {
+        simulation.restart(progress, treasury_balance);
+    }
+    require!(simulation.day_close.is_none(), FeeDistributorError::DayAlreadyCompleted);
+    
+    let (start_idx, end_idx) = page_bounds(
+        simulation.progress.pagination_cursor,
+        page_size,
+        simulation.progress.total_investors,
+    )?;
+    
+    if !is_stranded_close(&simulation.progress, start_idx, end_idx) {
+        require!(start_idx < end_idx, FeeDistributorError::InvalidPaginationCursor);
+        
+        let page_accounts = (end_idx - start_idx) * 2;
+        let investor_accounts = ctx
+            .remaining_accounts
+            .get(..page_accounts)
+            .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+        
+        let inputs = page_inputs(
+            policy,
+            &simulation.progress,
+            page_ledger(
+                policy,
+                ctx.accounts.locked_snapshot.as_ref(),
+                ctx.accounts.weight_table.as_ref(),
+            ),
+            ctx.accounts.share_overrides.as_deref(),
+            investor_accounts,
+            start_idx,
+            current_ts,
+        )?;
+        
+        let (lines, result) = match inputs {
+            Some(inputs) => {
+                let (payouts, result) = page_payout_walk(policy, &inputs)?;
+                (payout_lines(&inputs, payouts, start_idx)?, result)
+            }
+            // Nothing locked: the page pays no one and passes the dust on
+            None => {
+                let lines = (start_idx..end_idx)
+                    .map(|index| {
+                        Ok(PagePayoutPreview {
+                            investor_index: to_investor_index(index)?,
+                            locked: 0,
+                            weight_bps: 0,
+                            payout: 0,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let result = DistributionResult {
+                    total_distributed: 0,
+                    remaining_dust: simulation.progress.carry_over_dust,
+                    investors_paid: 0,
+                    investor_pool: 0,
+                    total_backpay: 0,
+                    total_withheld: 0,
+                };
+                (lines, result)
+            }
+        };
+        
+        let page_end = to_investor_index(end_idx)?;
+        record_page(policy, &mut simulation.progress, &result, page_end)?;
+        simulation.payouts.extend(lines.into_iter().map(|line| SimulatedPayout {
+            investor_index: line.investor_index,
+            locked: line.locked,
+            weight_bps: line.weight_bps,
+            payout: line.payout,
+        }));
+        
+        // Pushed payouts leave the treasury; pull-mode ones stay until claimed, short of
+        // what is withheld
+        let paid = if policy.distribution_mode == DistributionMode::Push {
+            result
+                .total_distributed
+                .checked_add(result.total_backpay)
+                .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        } else {
+            result.total_withheld
+        };
+        simulation.treasury_balance = simulation.treasury_balance.saturating_sub(paid);
+        
+        let is_last_page = page_end >= simulation.progress.total_investors;
+        if policy.creator_payout_mode == CreatorPayoutMode::PerPage && !is_last_page {
+            let creator_amount =
+                page_creator_payout(policy, &simulation.progress, page_end, simulation.treasury_balance)?;
+            simulation.treasury_balance -= creator_amount;
+            simulation.progress.current_day_distributed_creator = simulation
+                .progress
+                .current_day_distributed_creator
+                .checked_add(creator_amount)
+                .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        }
+        
+        if !is_last_page {
+            return Ok(());
+        }
+    }
+    
+    // The close distribute_fees runs on the last page, or close_day under a deferred
+    // close. Unrecognized funds it then settles under residual_mode aren't simulated
+    let treasury_balance = simulation.treasury_balance;
+    let close = close_day_amounts(policy, &mut simulation.progress, treasury_balance, current_ts)?;
+    let progress = &mut simulation.progress;
+    progress.current_day_distributed_creator = progress
+        .current_day_distributed_creator
+        .checked_add(close.remainder)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    progress.current_day_burned = progress
+        .current_day_burned
+        .checked_add(close.burned)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    progress.day_completed = true;
+    
+    simulation.treasury_balance = simulation
+        .treasury_balance
+        .saturating_sub(close.remainder)
+        .saturating_sub(close.burned);
+    simulation.day_close = Some(SimulatedDayClose {
+        remainder: close.remainder,
+        burned: close.burned,
+        rounding_held: close.rounding_held,
+    });
+    
+    Ok(())
+}
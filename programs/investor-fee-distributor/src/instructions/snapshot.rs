@@ -0,0 +1,315 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{
+        day_weight, is_excluded_creator_stream, pin_usd_weight_price, read_streamflow_amounts,
+        share_overrides_for, share_weight,
+    },
+    math::{blended_weight, page_bounds, time_weighted_locked, to_investor_index},
+    state::*,
+};
+
+/// Read a page of investor streams into the upcoming day's locked snapshot - authority
+/// only, between days
+///
+/// Nothing else binds a stream to its investor index, so only the authority may say
+/// which stream each index holds. A stream can hold one index of a snapshot at most.
+#[derive(Accounts)]
+#[instruction(page_size: u8)]
+pub struct SnapshotPage<'info> {
+    /// Policy authority (funds snapshot ledger growth)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Locked snapshot ledger, grown to fit this page's entries
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), SNAPSHOT_SEED],
+        bump = locked_snapshot.bump,
+        has_one = vault,
+        realloc = LockedSnapshot::space(page_bounds(
+            progress.snapshot_cursor,
+            std::cmp::min(page_size, MAX_PAGE_SIZE),
+            progress.total_investors,
+        )?.1),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub locked_snapshot: Account<'info, LockedSnapshot>,
+    
+    /// Share multipliers (required while the policy has overrides)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+    )]
+    pub share_overrides: Option<Account<'info, ShareOverrides>>,
+    
+    /// Price oracle (required when a snapshot starts under `weight_in_usd`)
+    /// CHECK: Checked against the policy's price_oracle
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Remaining accounts (passed dynamically):
+    // For each investor in this page, in index order:
+    //   1. stream_account (Streamflow stream)
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SnapshotPage<'info>>,
+    page_size: u8,
+) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    
+    // Oversized pages are clamped like in distribute_fees
+    require!(page_size > 0, FeeDistributorError::InvalidPageSize);
+    let page_size = std::cmp::min(page_size, MAX_PAGE_SIZE);
+    
+    let progress = &mut ctx.accounts.progress;
+    
+    // Snapshots are taken for the upcoming day, never underneath a running one
+    require!(!progress.is_mid_day(), FeeDistributorError::DayInProgress);
+    require!(
+        progress.is_new_day(current_ts, ctx.accounts.policy.distribution_interval),
+        FeeDistributorError::TooSoonToDistribute
+    );
+    
+    // A snapshot started before the last distribution belongs to a previous day, and
+    // one started before the investor set last changed to another set
+    if progress.snapshot_ts <= progress.last_distribution_ts || progress.is_snapshot_stale() {
+        // A new one waits out the weekend so Monday's day is weighted by Monday's
+        // streams; one already under way can still finish
+        require!(
+            !ctx.accounts.policy.holds_for_weekend(current_ts),
+            FeeDistributorError::TooSoonToDistribute
+        );
+        progress.snapshot_cursor = 0;
+        progress.total_locked_today = 0;
+        progress.active_investors_today = 0;
+        progress.total_weight_today = 0;
+        progress.snapshot_ts = current_ts;
+        progress.snapshot_set_version = progress.set_version;
+        // Every page is priced alike, so the price only scales weights, whatever the
+        // price does while the snapshot is taken
+        progress.usd_weight_price = pin_usd_weight_price(
+            &ctx.accounts.policy,
+            ctx.accounts.price_oracle.as_deref(),
+            current_ts,
+        )?;
+    }
+    
+    let (start_idx, end_idx) =
+        page_bounds(progress.snapshot_cursor, page_size, progress.total_investors)?;
+    
+    require!(
+        start_idx < end_idx,
+        FeeDistributorError::SnapshotAlreadyComplete
+    );
+    let (page_start, page_end) = (to_investor_index(start_idx)?, to_investor_index(end_idx)?);
+    let stream_accounts = ctx
+        .remaining_accounts
+        .get(..end_idx - start_idx)
+        .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+    
+    let share_overrides = share_overrides_for(
+        &ctx.accounts.policy,
+        ctx.accounts.share_overrides.as_deref(),
+    )?;
+    
+    let ledger = ctx.accounts.locked_snapshot.to_account_info();
+    let mut page_locked: u64 = 0;
+    let mut page_weight: u64 = 0;
+    let mut page_active: u32 = 0;
+    
+    for (i, stream_account) in stream_accounts.iter().enumerate() {
+        let stream = stream_account.key();
+        // Entries below this one were all written by this snapshot, earlier pages and
+        // this page alike
+        require_unique_stream(&ledger, start_idx + i, &stream)?;
+        let (stream_locked, deposited) = read_streamflow_amounts(stream_account, current_ts)?;
+        // The entry still holds the previous snapshot until it is overwritten here;
+        // slots beyond the old ledger end read as zeroes
+        let previous = LockedSnapshot::read_entry(&ledger, start_idx + i)?;
+        let newly_active = previous.became_active(&stream, stream_locked);
+        let period_start_locked =
+            previous.period_start_for(&stream, stream_locked, progress.last_distribution_ts);
+        // The raw reading is still what the entry records, so the next period starts
+        // from it
+        let policy = &ctx.accounts.policy;
+        if policy.weighting_mode != WeightingMode::DepositedAmount
+            && policy.withdrawal_handling.snapshot_locked(period_start_locked, stream_locked) > stream_locked
+        {
+            emit!(LockedDecreaseGraced {
+                investor_index: to_investor_index(start_idx + i)?,
+                stream,
+                period_start_locked,
+                stream_locked,
+            });
+        }
+        
+        let locked = entry_locked(policy, period_start_locked, stream_locked, deposited);
+        // A blend only moves the weight; the locked fraction still follows what is locked
+        let weighed = match policy.weighting_mode {
+            WeightingMode::Blended => blended_weight(locked, deposited, policy.weight_blend_bps),
+            _ => locked,
+        };
+        // An excluded creator stream is recorded with no weight and isn't active
+        let excluded = is_excluded_creator_stream(&ctx.accounts.policy, stream_account)?;
+        let weight = if excluded {
+            0
+        } else {
+            day_weight(share_weight(share_overrides, &stream, weighed)?, progress.usd_weight_price)?
+        };
+        
+        LockedSnapshot::write_entry(
+            &ledger,
+            start_idx + i,
+            &SnapshotEntry {
+                stream,
+                locked,
+                weight,
+                newly_active,
+                stream_locked,
+                period_start_locked,
+                snapshot_ts: progress.snapshot_ts,
+            },
+        )?;
+        
+        page_locked = page_locked
+            .checked_add(locked)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        page_weight = page_weight
+            .checked_add(weight)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        if locked > 0 && !excluded {
+            page_active += 1;
+        }
+    }
+    
+    progress.total_locked_today = progress
+        .total_locked_today
+        .checked_add(page_locked)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    progress.total_weight_today = progress
+        .total_weight_today
+        .checked_add(page_weight)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    progress.active_investors_today += page_active;
+    progress.snapshot_cursor = page_end;
+    
+    emit!(LockedSnapshotPage {
+        vault: ctx.accounts.vault.key(),
+        page_start,
+        page_end,
+        page_locked,
+        total_locked: progress.total_locked_today,
+        complete: page_end == progress.total_investors,
+    });
+    
+    Ok(())
+}
+
+/// Fail with `DuplicateInvestorStream` if `stream` already holds one of the first
+/// `entries` indices of the snapshot
+fn require_unique_stream(ledger: &AccountInfo, entries: usize, stream: &Pubkey) -> Result<()> {
+    require!(
+        !LockedSnapshot::holds_stream(ledger, entries, stream)?,
+        FeeDistributorError::DuplicateInvestorStream
+    );
+    Ok(())
+}
+
+/// Locked amount a snapshot entry records, given the stream's reading at the previous
+/// snapshot, now, and its deposited amount: the reading after `withdrawal_handling`,
+/// time-weighted under `TimeWeighted`, or the deposit under `DepositedAmount`
+pub(crate) fn entry_locked(
+    policy: &DistributionPolicy,
+    period_start_locked: u64,
+    stream_locked: u64,
+    deposited: u64,
+) -> u64 {
+    let read_locked = policy.withdrawal_handling.snapshot_locked(period_start_locked, stream_locked);
+    match policy.weighting_mode {
+        WeightingMode::Instantaneous | WeightingMode::Blended => read_locked,
+        WeightingMode::TimeWeighted => time_weighted_locked(period_start_locked, read_locked),
+        WeightingMode::DepositedAmount => deposited,
+    }
+}
+
+#[event]
+pub struct LockedDecreaseGraced {
+    pub investor_index: u32,
+    pub stream: Pubkey,
+    /// Reading of the previous snapshot, which the investor is weighed by
+    pub period_start_locked: u64,
+    /// Lower amount read now
+    pub stream_locked: u64,
+}
+
+#[event]
+pub struct LockedSnapshotPage {
+    pub vault: Pubkey,
+    pub page_start: u32,
+    pub page_end: u32,
+    pub page_locked: u64,
+    pub total_locked: u64,
+    pub complete: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_stream_holds_one_index_of_a_snapshot_at_most() {
+        let (ledger_key, first, second) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![0u8; LockedSnapshot::space(3)];
+        let mut lamports = 0u64;
+        let ledger = AccountInfo::new(&ledger_key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let entry = |stream| SnapshotEntry {
+            stream,
+            locked: 1_000,
+            weight: 1_000,
+            newly_active: false,
+            stream_locked: 1_000,
+            period_start_locked: 1_000,
+            snapshot_ts: 1,
+        };
+        LockedSnapshot::write_entry(&ledger, 0, &entry(first)).unwrap();
+        LockedSnapshot::write_entry(&ledger, 1, &entry(second)).unwrap();
+        
+        require_unique_stream(&ledger, 0, &first).unwrap();
+        // Repeating an earlier investor's stream at a later index is rejected
+        assert_eq!(
+            require_unique_stream(&ledger, 2, &first).unwrap_err(),
+            FeeDistributorError::DuplicateInvestorStream.into()
+        );
+        // Entries from the cursor on still hold the previous snapshot and don't count
+        require_unique_stream(&ledger, 1, &second).unwrap();
+    }
+}
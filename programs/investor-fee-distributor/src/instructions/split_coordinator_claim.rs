@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::{constants::*, errors::FeeDistributorError, math::split_by_shares, state::*};
+
+/// Move the coordinator treasury's balance into the member treasuries by share -
+/// permissionless
+#[derive(Accounts)]
+pub struct SplitCoordinatorClaim<'info> {
+    /// Permissionless caller
+    pub caller: Signer<'info>,
+    
+    /// Coordinator
+    #[account(
+        mut,
+        seeds = [COORDINATOR_SEED, coordinator.coordinator_id.as_ref()],
+        bump = coordinator.bump,
+    )]
+    pub coordinator: Account<'info, TreasuryCoordinator>,
+    
+    /// Coordinator's quote treasury
+    #[account(
+        mut,
+        seeds = [COORDINATOR_SEED, coordinator.coordinator_id.as_ref(), TREASURY_SEED],
+        bump,
+        token::mint = coordinator.quote_mint,
+        token::authority = coordinator,
+    )]
+    pub coordinator_treasury: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    // Remaining accounts:
+    // Every member's treasury (writable), in the coordinator's member order
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SplitCoordinatorClaim<'info>>,
+) -> Result<()> {
+    let coordinator = &ctx.accounts.coordinator;
+    
+    // An incomplete member set would strand the unassigned share in the coordinator
+    require!(
+        !coordinator.members.is_empty() && coordinator.total_share_bps() == BASIS_POINTS_DIVISOR,
+        FeeDistributorError::CoordinatorSharesIncomplete
+    );
+    require!(
+        ctx.remaining_accounts.len() >= coordinator.members.len(),
+        FeeDistributorError::MissingCoordinatorMember
+    );
+    
+    // Everything but the carried dust arrived since the last split
+    let balance = ctx.accounts.coordinator_treasury.amount;
+    let claimed = balance.saturating_sub(coordinator.carried_dust);
+    let shares: Vec<u16> = coordinator.members.iter().map(|member| member.share_bps).collect();
+    let (amounts, dust) = split_by_shares(balance, &shares)?;
+    
+    let coordinator_id = coordinator.coordinator_id;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        COORDINATOR_SEED,
+        coordinator_id.as_ref(),
+        &[coordinator.bump],
+    ]];
+    
+    for ((member, member_treasury), amount) in coordinator
+        .members
+        .iter()
+        .zip(ctx.remaining_accounts)
+        .zip(&amounts)
+    {
+        require_keys_eq!(
+            member_treasury.key(),
+            member.treasury,
+            FeeDistributorError::MissingCoordinatorMember
+        );
+        if *amount == 0 {
+            continue;
+        }
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.coordinator_treasury.to_account_info(),
+                    to: member_treasury.to_account_info(),
+                    authority: ctx.accounts.coordinator.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            *amount,
+        )?;
+    }
+    
+    let coordinator = &mut ctx.accounts.coordinator;
+    for (member, amount) in coordinator.members.iter_mut().zip(&amounts) {
+        member.total_allocated = member
+            .total_allocated
+            .checked_add(*amount)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    }
+    coordinator.total_claimed = coordinator
+        .total_claimed
+        .checked_add(claimed)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    coordinator.carried_dust = dust;
+    
+    emit!(CoordinatorClaimSplit {
+        coordinator: coordinator.key(),
+        claimed,
+        amounts,
+        carried_dust: dust,
+        total_claimed: coordinator.total_claimed,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct CoordinatorClaimSplit {
+    pub coordinator: Pubkey,
+    /// Fees that arrived since the previous split
+    pub claimed: u64,
+    /// Moved into each member treasury, in member order
+    pub amounts: Vec<u64>,
+    /// Rounding dust kept for the next split
+    pub carried_dust: u64,
+    pub total_claimed: u64,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+// Glob import: the nested `CloseDay` needs the modules its derive generates
+use crate::{
+    errors::FeeDistributorError,
+    instructions::{close_day::*, distribute::treasury_authority_seeds},
+};
+
+/// Sweep what a finalized vault's treasury holds beyond pull-mode liabilities to the
+/// creator - permissionless
+#[derive(Accounts)]
+pub struct SweepFinalizedTreasury<'info> {
+    /// Same accounts as `close_day`
+    pub close: CloseDay<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SweepFinalizedTreasury>) -> Result<()> {
+    let accounts = &mut ctx.accounts.close;
+    
+    require!(accounts.progress.vault_finalized, FeeDistributorError::VaultNotFinalized);
+    // The residual is owed to investors until finalize_vault has paid every page
+    require!(
+        !accounts.progress.close_distribution_pending(),
+        FeeDistributorError::CloseDistributionInProgress
+    );
+    // Same runtime checks as close_day
+    require_keys_eq!(
+        accounts.treasury.owner,
+        accounts.treasury_authority.key(),
+        FeeDistributorError::TreasuryAuthorityMismatch
+    );
+    require_keys_eq!(
+        accounts.creator_quote_ata.mint,
+        accounts.policy.quote_mint,
+        FeeDistributorError::CreatorAtaMintMismatch
+    );
+    
+    // Accruals stay claimable; everything else (claims since finalization included)
+    // has no investor day left to go to
+    let amount = accounts
+        .treasury
+        .amount
+        .saturating_sub(accounts.progress.pull_outstanding);
+    
+    if amount > 0 {
+        let vault_key = accounts.vault.key();
+        let bump = [accounts.policy.treasury_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&treasury_authority_seeds(&vault_key, &bump)];
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                Transfer {
+                    from: accounts.treasury.to_account_info(),
+                    to: accounts.creator_quote_ata.to_account_info(),
+                    authority: accounts.treasury_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+    
+    let progress = &mut accounts.progress;
+    progress.current_period_claimed = 0;
+    progress.deferred_investor_pool = 0;
+    
+    emit!(FinalizedTreasurySwept {
+        vault: accounts.vault.key(),
+        creator: accounts.policy.creator_wallet,
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct FinalizedTreasurySwept {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    /// Moved from the treasury to the creator's quote ATA
+    pub amount: u64,
+}
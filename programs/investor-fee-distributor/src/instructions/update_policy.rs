@@ -0,0 +1,898 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError, state::*};
+
+/// Update the tunable parameters of an existing distribution policy
+#[derive(Accounts)]
+pub struct UpdatePolicy<'info> {
+    pub authority: Signer<'info>,
+
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+
+    /// Distribution policy
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+
+    /// Distribution progress (read to reject updates mid-day)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+}
+
+/// Fields left as `None` keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdatePolicyParams {
+    pub investor_fee_share_bps: Option<u16>,
+    pub daily_cap_lamports: Option<u64>,
+    pub min_payout_lamports: Option<u64>,
+    pub creator_payout_mode: Option<CreatorPayoutMode>,
+    /// `Pubkey::default()` clears the fallback
+    pub investor_fallback_wallet: Option<Pubkey>,
+    pub weight_source: Option<WeightSource>,
+    pub distribution_mode: Option<DistributionMode>,
+    pub distribution_interval: Option<i64>,
+    pub distribution_scheme: Option<DistributionScheme>,
+    pub fixed_payout_lamports: Option<u64>,
+    pub fixed_shortfall: Option<FixedShortfall>,
+    pub defer_creator_close: Option<bool>,
+    pub max_claim_age_secs: Option<i64>,
+    pub remainder_mode: Option<RemainderMode>,
+    pub min_payout_unit: Option<MinPayoutUnit>,
+    pub attach_memo: Option<bool>,
+    pub memo_reference: Option<String>,
+    /// `Pubkey::default()` clears the fee source
+    pub fee_source: Option<Pubkey>,
+    pub max_single_investor_bps: Option<u16>,
+    /// Decimals Y0 is expressed in, at most the investor token's decimals
+    pub allocation_decimals: Option<u8>,
+    pub defer_below_minimum: Option<bool>,
+    /// Must be at least 1
+    pub payout_granularity_lamports: Option<u64>,
+    /// Requires `DailySnapshot` weights on the resulting policy
+    pub backpay_enabled: Option<bool>,
+    pub backpay_lamports: Option<u64>,
+    pub payout_priority: Option<PayoutPriority>,
+    /// `Pubkey::default()` clears the reserve; only while no principal is in it
+    pub yield_reserve: Option<Pubkey>,
+    pub pull_claim_cooldown: Option<bool>,
+    pub tolerate_claim_failure: Option<bool>,
+    /// `Pubkey::default()` clears the DAMM pool, skipping the pool status check
+    pub damm_pool: Option<Pubkey>,
+    /// `Pubkey::default()` clears the payout mint, paying investors in quote again
+    pub payout_mint: Option<Pubkey>,
+    /// `Pubkey::default()` clears the swap pool
+    pub swap_pool: Option<Pubkey>,
+    pub max_swap_slippage_bps: Option<u16>,
+    pub reject_delegated_atas: Option<bool>,
+    pub min_claim_to_distribute: Option<u64>,
+    /// 1-10000
+    pub claim_ema_alpha_bps: Option<u16>,
+    /// 0 disables the circuit breaker; otherwise above 10000 (1x)
+    pub circuit_breaker_multiple_bps: Option<u32>,
+    /// 0 pays everyone; otherwise requires day-level weights on the resulting policy
+    /// and at most `MAX_TOP_N_INVESTOR_SET`, for both N and the investor set
+    pub top_n_investors: Option<u32>,
+    pub require_opt_in: Option<bool>,
+    /// 0-10000; caps the investor share at `10000 - min_creator_share_bps`
+    pub min_creator_share_bps: Option<u16>,
+    pub skip_weekends: Option<bool>,
+    /// 0 = unlimited; a cap at or below what was already distributed finalizes the
+    /// vault when the current day closes
+    pub lifetime_cap_lamports: Option<u64>,
+    /// Requires day-level weights and a `price_oracle` on the resulting policy
+    pub weight_in_usd: Option<bool>,
+    pub price_oracle: Option<Pubkey>,
+    /// `Governance` requires end-of-day creator payouts and both governance keys on
+    /// the resulting policy
+    pub creator_deposit_mode: Option<CreatorDepositMode>,
+    pub governance_realm: Option<Pubkey>,
+    pub governance_treasury: Option<Pubkey>,
+    pub confirm_deliveries: Option<bool>,
+    /// Must be at least 1
+    pub creator_granularity_lamports: Option<u64>,
+    /// `UNLIMITED_CLAIM_GROWTH_BPS` disables the throttle
+    pub max_claim_growth_bps: Option<u32>,
+    /// `TimeWeighted`, `Blended` and `DepositedAmount` require `DailySnapshot` weights
+    /// on the resulting policy; can't change while a snapshot for the upcoming day is
+    /// being taken
+    pub weighting_mode: Option<WeightingMode>,
+    pub verbose_events: Option<bool>,
+    pub residual_mode: Option<ResidualMode>,
+    /// 0-`MAX_PROTOCOL_FEE_BPS`; a non-zero fee requires a `protocol_fee_wallet` on
+    /// the resulting policy
+    pub protocol_fee_bps: Option<u16>,
+    pub protocol_fee_wallet: Option<Pubkey>,
+    /// Can't change while a snapshot for the upcoming day is being taken
+    pub exclude_creator_from_investors: Option<bool>,
+    /// 0-`MAX_RECONCILIATION_TOLERANCE_LAMPORTS`
+    pub reconciliation_tolerance_lamports: Option<u64>,
+    /// 0 = no limit
+    pub max_transfer_size: Option<u64>,
+    /// 0-10000; only checked under `WeightSource::LiveStreams`
+    pub page_quorum_bps: Option<u16>,
+    /// Non-zero; applies from the next snapshot or weight table priced
+    pub max_oracle_staleness_seconds: Option<u32>,
+    /// 0-10000
+    pub max_oracle_confidence_bps: Option<u16>,
+    /// Only matters until the vault first recognizes treasury funds
+    pub prefunded_balance_mode: Option<PrefundedBalanceMode>,
+    /// 0-10000; can't change while a snapshot for the upcoming day is being taken
+    pub weight_blend_bps: Option<u16>,
+    /// 0 = unlimited; a lower limit takes effect on the next page
+    pub max_investors_per_day: Option<u32>,
+    /// `Pubkey::default()` clears the distributor, paying with the built-in transfer again
+    pub distributor_program: Option<Pubkey>,
+    pub dust_to_last_investor: Option<bool>,
+    /// 0-10000; non-zero requires a withholding wallet
+    pub withholding_bps: Option<u16>,
+    /// `Pubkey::default()` clears the wallet
+    pub withholding_wallet: Option<Pubkey>,
+    /// Requires DailySnapshot or OnChainTable weights
+    pub distribute_on_close: Option<bool>,
+    pub persist_dust_across_days: Option<bool>,
+    pub require_creator_signature: Option<bool>,
+    /// `Grace` requires `DailySnapshot` weights on the resulting policy; can't change
+    /// while a snapshot for the upcoming day is being taken
+    pub withdrawal_handling: Option<WithdrawalHandling>,
+    /// 0 removes the cap
+    pub creator_daily_cap_lamports: Option<u64>,
+    /// Requires `AccountOrder` payouts, DailySnapshot or OnChainTable weights and no
+    /// `dust_to_last_investor` on the resulting policy
+    pub deterministic_index_order: Option<bool>,
+    /// `TreasuryDraw` requires a non-zero `settlement_rate` on the resulting policy
+    pub payout_settlement: Option<PayoutSettlement>,
+    pub settlement_rate: Option<u64>,
+    /// Only changes `LiveStreams` days; takes effect on the next page
+    pub daily_cap_allocation: Option<DailyCapAllocation>,
+    /// At most MAX_PAGE_SIZE; non-zero requires a non-zero `max_ata_creation_funding`
+    /// on the resulting policy
+    pub max_ata_creations_per_page: Option<u8>,
+    pub max_ata_creation_funding: Option<u64>,
+    /// Takes effect from the next day to close
+    pub record_day_history: Option<bool>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdatePolicy>, params: UpdatePolicyParams) -> Result<()> {
+    // Changing the split halfway through a day would break page-to-page accounting
+    require!(
+        !ctx.accounts.progress.is_mid_day(),
+        FeeDistributorError::DayInProgress
+    );
+
+    apply_policy_params(&mut ctx.accounts.policy, &ctx.accounts.progress, params)?;
+    emit!(PolicyUpdated::from_policy(&ctx.accounts.policy));
+
+    Ok(())
+}
+
+/// Apply `params` to `policy` and check the resulting policy as a whole; shared with
+/// `preview_policy_change`, which applies them to a copy
+pub(crate) fn apply_policy_params(
+    policy: &mut DistributionPolicy,
+    progress: &DistributionProgress,
+    params: UpdatePolicyParams,
+) -> Result<()> {
+
+    if let Some(investor_fee_share_bps) = params.investor_fee_share_bps {
+        require!(
+            investor_fee_share_bps <= BASIS_POINTS_DIVISOR as u16,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        require!(
+            investor_fee_share_bps <= MAX_INVESTOR_FEE_SHARE_BPS,
+            FeeDistributorError::InvestorShareTooHigh
+        );
+        policy.investor_fee_share_bps = investor_fee_share_bps;
+    }
+
+    if let Some(daily_cap_lamports) = params.daily_cap_lamports {
+        policy.daily_cap_lamports = daily_cap_lamports;
+    }
+
+    if let Some(min_payout_lamports) = params.min_payout_lamports {
+        policy.min_payout_lamports = min_payout_lamports;
+    }
+
+    if let Some(creator_payout_mode) = params.creator_payout_mode {
+        policy.creator_payout_mode = creator_payout_mode;
+    }
+
+    if let Some(investor_fallback_wallet) = params.investor_fallback_wallet {
+        policy.investor_fallback_wallet =
+            (investor_fallback_wallet != Pubkey::default()).then_some(investor_fallback_wallet);
+    }
+
+    if let Some(weight_source) = params.weight_source {
+        policy.weight_source = weight_source;
+    }
+
+    if let Some(distribution_mode) = params.distribution_mode {
+        policy.distribution_mode = distribution_mode;
+    }
+
+    if let Some(distribution_interval) = params.distribution_interval {
+        require!(
+            (SECONDS_PER_DAY..=MAX_DISTRIBUTION_INTERVAL).contains(&distribution_interval),
+            FeeDistributorError::InvalidDistributionInterval
+        );
+        policy.distribution_interval = distribution_interval;
+    }
+
+    if let Some(distribution_scheme) = params.distribution_scheme {
+        policy.distribution_scheme = distribution_scheme;
+    }
+
+    if let Some(fixed_payout_lamports) = params.fixed_payout_lamports {
+        policy.fixed_payout_lamports = fixed_payout_lamports;
+    }
+
+    if let Some(fixed_shortfall) = params.fixed_shortfall {
+        policy.fixed_shortfall = fixed_shortfall;
+    }
+
+    if let Some(defer_creator_close) = params.defer_creator_close {
+        policy.defer_creator_close = defer_creator_close;
+    }
+
+    if let Some(max_claim_age_secs) = params.max_claim_age_secs {
+        require!(max_claim_age_secs >= 0, FeeDistributorError::InvalidClaimAge);
+        policy.max_claim_age_secs = max_claim_age_secs;
+    }
+
+    if let Some(remainder_mode) = params.remainder_mode {
+        policy.remainder_mode = remainder_mode;
+    }
+
+    if let Some(min_payout_unit) = params.min_payout_unit {
+        policy.min_payout_unit = min_payout_unit;
+    }
+
+    if let Some(attach_memo) = params.attach_memo {
+        policy.attach_memo = attach_memo;
+    }
+
+    if let Some(memo_reference) = params.memo_reference {
+        require!(
+            memo_reference.len() <= MAX_MEMO_REFERENCE_LEN,
+            FeeDistributorError::MemoReferenceTooLong
+        );
+        policy.memo_reference = memo_reference;
+    }
+
+    if let Some(fee_source) = params.fee_source {
+        policy.fee_source = (fee_source != Pubkey::default()).then_some(fee_source);
+    }
+
+    if let Some(max_single_investor_bps) = params.max_single_investor_bps {
+        require!(
+            max_single_investor_bps <= BASIS_POINTS_DIVISOR as u16,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        policy.max_single_investor_bps = max_single_investor_bps;
+    }
+
+    if let Some(allocation_decimals) = params.allocation_decimals {
+        policy.allocation_decimals = allocation_decimals;
+        // Rejects decimals above the investor token's and a Y0 that overflows once scaled
+        policy.allocation_base_units()?;
+    }
+
+    if let Some(defer_below_minimum) = params.defer_below_minimum {
+        policy.defer_below_minimum = defer_below_minimum;
+    }
+    
+    if let Some(payout_granularity_lamports) = params.payout_granularity_lamports {
+        require!(
+            payout_granularity_lamports > 0,
+            FeeDistributorError::InvalidPayoutGranularity
+        );
+        policy.payout_granularity_lamports = payout_granularity_lamports;
+    }
+    
+    if let Some(backpay_enabled) = params.backpay_enabled {
+        policy.backpay_enabled = backpay_enabled;
+    }
+    
+    if let Some(backpay_lamports) = params.backpay_lamports {
+        policy.backpay_lamports = backpay_lamports;
+    }
+    
+    if let Some(payout_priority) = params.payout_priority {
+        policy.payout_priority = payout_priority;
+    }
+    
+    if let Some(yield_reserve) = params.yield_reserve {
+        let yield_reserve = (yield_reserve != Pubkey::default()).then_some(yield_reserve);
+        // Principal is tracked for the configured reserve; moving it would strand it
+        require!(
+            yield_reserve == policy.yield_reserve || progress.yield_principal == 0,
+            FeeDistributorError::YieldPrincipalOutstanding
+        );
+        policy.yield_reserve = yield_reserve;
+    }
+    
+    if let Some(pull_claim_cooldown) = params.pull_claim_cooldown {
+        policy.pull_claim_cooldown = pull_claim_cooldown;
+    }
+    
+    if let Some(tolerate_claim_failure) = params.tolerate_claim_failure {
+        policy.tolerate_claim_failure = tolerate_claim_failure;
+    }
+    
+    if let Some(damm_pool) = params.damm_pool {
+        policy.damm_pool = (damm_pool != Pubkey::default()).then_some(damm_pool);
+    }
+    
+    if let Some(payout_mint) = params.payout_mint {
+        policy.payout_mint = (payout_mint != Pubkey::default()).then_some(payout_mint);
+    }
+    
+    if let Some(swap_pool) = params.swap_pool {
+        policy.swap_pool = (swap_pool != Pubkey::default()).then_some(swap_pool);
+    }
+    
+    if let Some(max_swap_slippage_bps) = params.max_swap_slippage_bps {
+        require!(
+            max_swap_slippage_bps <= MAX_SWAP_SLIPPAGE_BPS,
+            FeeDistributorError::InvalidPayoutConversion
+        );
+        policy.max_swap_slippage_bps = max_swap_slippage_bps;
+    }
+    
+    if let Some(reject_delegated_atas) = params.reject_delegated_atas {
+        policy.reject_delegated_atas = reject_delegated_atas;
+    }
+    
+    if let Some(min_claim_to_distribute) = params.min_claim_to_distribute {
+        policy.min_claim_to_distribute = min_claim_to_distribute;
+    }
+    
+    if let Some(claim_ema_alpha_bps) = params.claim_ema_alpha_bps {
+        require!(
+            claim_ema_alpha_bps > 0 && claim_ema_alpha_bps as u64 <= BASIS_POINTS_DIVISOR,
+            FeeDistributorError::InvalidCircuitBreaker
+        );
+        policy.claim_ema_alpha_bps = claim_ema_alpha_bps;
+    }
+    
+    if let Some(circuit_breaker_multiple_bps) = params.circuit_breaker_multiple_bps {
+        // At or below 1x an ordinary claim above the average would trip it
+        require!(
+            circuit_breaker_multiple_bps == 0 || circuit_breaker_multiple_bps as u64 > BASIS_POINTS_DIVISOR,
+            FeeDistributorError::InvalidCircuitBreaker
+        );
+        policy.circuit_breaker_multiple_bps = circuit_breaker_multiple_bps;
+    }
+    
+    if let Some(top_n_investors) = params.top_n_investors {
+        policy.top_n_investors = top_n_investors;
+    }
+    
+    if let Some(require_opt_in) = params.require_opt_in {
+        policy.require_opt_in = require_opt_in;
+    }
+    
+    if let Some(min_creator_share_bps) = params.min_creator_share_bps {
+        require!(
+            min_creator_share_bps as u64 <= BASIS_POINTS_DIVISOR,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        policy.min_creator_share_bps = min_creator_share_bps;
+    }
+    
+    if let Some(skip_weekends) = params.skip_weekends {
+        policy.skip_weekends = skip_weekends;
+    }
+    
+    if let Some(lifetime_cap_lamports) = params.lifetime_cap_lamports {
+        policy.lifetime_cap_lamports = lifetime_cap_lamports;
+    }
+    
+    if let Some(weight_in_usd) = params.weight_in_usd {
+        policy.weight_in_usd = weight_in_usd;
+    }
+    
+    if let Some(price_oracle) = params.price_oracle {
+        policy.price_oracle = price_oracle;
+    }
+    
+    if let Some(creator_deposit_mode) = params.creator_deposit_mode {
+        policy.creator_deposit_mode = creator_deposit_mode;
+    }
+    
+    if let Some(governance_realm) = params.governance_realm {
+        policy.governance_realm = governance_realm;
+    }
+    
+    if let Some(governance_treasury) = params.governance_treasury {
+        policy.governance_treasury = governance_treasury;
+    }
+    
+    if let Some(confirm_deliveries) = params.confirm_deliveries {
+        policy.confirm_deliveries = confirm_deliveries;
+    }
+    
+    if let Some(creator_granularity_lamports) = params.creator_granularity_lamports {
+        require!(
+            creator_granularity_lamports > 0,
+            FeeDistributorError::InvalidPayoutGranularity
+        );
+        policy.creator_granularity_lamports = creator_granularity_lamports;
+    }
+    
+    if let Some(max_claim_growth_bps) = params.max_claim_growth_bps {
+        policy.max_claim_growth_bps = max_claim_growth_bps;
+    }
+    
+    if let Some(weighting_mode) = params.weighting_mode {
+        // Every page of a snapshot must weigh investors alike
+        require!(
+            weighting_mode == policy.weighting_mode || !progress.has_upcoming_snapshot(),
+            FeeDistributorError::SnapshotInProgress
+        );
+        policy.weighting_mode = weighting_mode;
+    }
+    
+    if let Some(verbose_events) = params.verbose_events {
+        policy.verbose_events = verbose_events;
+    }
+    
+    if let Some(residual_mode) = params.residual_mode {
+        policy.residual_mode = residual_mode;
+    }
+    
+    if let Some(protocol_fee_bps) = params.protocol_fee_bps {
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            FeeDistributorError::InvalidProtocolFee
+        );
+        policy.protocol_fee_bps = protocol_fee_bps;
+    }
+    
+    if let Some(protocol_fee_wallet) = params.protocol_fee_wallet {
+        policy.protocol_fee_wallet = protocol_fee_wallet;
+    }
+    
+    if let Some(exclude_creator_from_investors) = params.exclude_creator_from_investors {
+        // A snapshot's pages must all leave the same streams out of the weights
+        require!(
+            exclude_creator_from_investors == policy.exclude_creator_from_investors
+                || !progress.has_upcoming_snapshot(),
+            FeeDistributorError::SnapshotInProgress
+        );
+        policy.exclude_creator_from_investors = exclude_creator_from_investors;
+    }
+    
+    if let Some(reconciliation_tolerance_lamports) = params.reconciliation_tolerance_lamports {
+        require!(
+            reconciliation_tolerance_lamports <= MAX_RECONCILIATION_TOLERANCE_LAMPORTS,
+            FeeDistributorError::InvalidReconciliationTolerance
+        );
+        policy.reconciliation_tolerance_lamports = reconciliation_tolerance_lamports;
+    }
+    
+    if let Some(max_transfer_size) = params.max_transfer_size {
+        policy.max_transfer_size = max_transfer_size;
+    }
+    
+    if let Some(page_quorum_bps) = params.page_quorum_bps {
+        require!(
+            page_quorum_bps <= BASIS_POINTS_DIVISOR as u16,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        policy.page_quorum_bps = page_quorum_bps;
+    }
+    
+    if let Some(max_oracle_staleness_seconds) = params.max_oracle_staleness_seconds {
+        require!(
+            max_oracle_staleness_seconds > 0,
+            FeeDistributorError::InvalidOracleStaleness
+        );
+        policy.max_oracle_staleness_seconds = max_oracle_staleness_seconds;
+    }
+    
+    if let Some(max_oracle_confidence_bps) = params.max_oracle_confidence_bps {
+        require!(
+            max_oracle_confidence_bps <= BASIS_POINTS_DIVISOR as u16,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        policy.max_oracle_confidence_bps = max_oracle_confidence_bps;
+    }
+    
+    if let Some(prefunded_balance_mode) = params.prefunded_balance_mode {
+        policy.prefunded_balance_mode = prefunded_balance_mode;
+    }
+    
+    if let Some(weight_blend_bps) = params.weight_blend_bps {
+        require!(
+            weight_blend_bps <= BASIS_POINTS_DIVISOR as u16,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        require!(
+            weight_blend_bps == policy.weight_blend_bps || !progress.has_upcoming_snapshot(),
+            FeeDistributorError::SnapshotInProgress
+        );
+        policy.weight_blend_bps = weight_blend_bps;
+    }
+    
+    if let Some(max_investors_per_day) = params.max_investors_per_day {
+        policy.max_investors_per_day = max_investors_per_day;
+    }
+    
+    if let Some(distributor_program) = params.distributor_program {
+        policy.distributor_program = (distributor_program != Pubkey::default()).then_some(distributor_program);
+    }
+    
+    if let Some(dust_to_last_investor) = params.dust_to_last_investor {
+        policy.dust_to_last_investor = dust_to_last_investor;
+    }
+    
+    if let Some(withholding_bps) = params.withholding_bps {
+        require!(
+            withholding_bps <= BASIS_POINTS_DIVISOR as u16,
+            FeeDistributorError::InvalidBasisPoints
+        );
+        policy.withholding_bps = withholding_bps;
+    }
+    
+    if let Some(withholding_wallet) = params.withholding_wallet {
+        policy.withholding_wallet = (withholding_wallet != Pubkey::default()).then_some(withholding_wallet);
+    }
+    
+    if let Some(distribute_on_close) = params.distribute_on_close {
+        policy.distribute_on_close = distribute_on_close;
+    }
+    
+    if let Some(persist_dust_across_days) = params.persist_dust_across_days {
+        policy.persist_dust_across_days = persist_dust_across_days;
+    }
+    
+    if let Some(require_creator_signature) = params.require_creator_signature {
+        policy.require_creator_signature = require_creator_signature;
+    }
+    
+    if let Some(withdrawal_handling) = params.withdrawal_handling {
+        // Every page of a snapshot must weigh investors alike
+        require!(
+            withdrawal_handling == policy.withdrawal_handling || !progress.has_upcoming_snapshot(),
+            FeeDistributorError::SnapshotInProgress
+        );
+        policy.withdrawal_handling = withdrawal_handling;
+    }
+    
+    if let Some(creator_daily_cap_lamports) = params.creator_daily_cap_lamports {
+        policy.creator_daily_cap_lamports = creator_daily_cap_lamports;
+    }
+    
+    if let Some(deterministic_index_order) = params.deterministic_index_order {
+        policy.deterministic_index_order = deterministic_index_order;
+    }
+    
+    if let Some(payout_settlement) = params.payout_settlement {
+        policy.payout_settlement = payout_settlement;
+    }
+    
+    if let Some(settlement_rate) = params.settlement_rate {
+        policy.settlement_rate = settlement_rate;
+    }
+    
+    if let Some(daily_cap_allocation) = params.daily_cap_allocation {
+        policy.daily_cap_allocation = daily_cap_allocation;
+    }
+    
+    if let Some(max_ata_creations_per_page) = params.max_ata_creations_per_page {
+        require!(
+            max_ata_creations_per_page <= MAX_PAGE_SIZE,
+            FeeDistributorError::InvalidAtaCreationLimits
+        );
+        policy.max_ata_creations_per_page = max_ata_creations_per_page;
+    }
+    
+    if let Some(max_ata_creation_funding) = params.max_ata_creation_funding {
+        policy.max_ata_creation_funding = max_ata_creation_funding;
+    }
+    
+    if let Some(record_day_history) = params.record_day_history {
+        policy.record_day_history = record_day_history;
+    }
+
+    // Checked on the resulting policy so scheme and amount can be set in either order
+    require!(
+        policy.distribution_scheme != DistributionScheme::EqualFixed
+            || policy.fixed_payout_lamports > 0,
+        FeeDistributorError::InvalidFixedPayout
+    );
+    // Withheld funds need somewhere to go
+    require!(
+        policy.withholding_bps == 0 || policy.withholding_wallet.is_some(),
+        FeeDistributorError::MissingWithholdingWallet
+    );
+    // Same for the minimum payout, its unit and the daily cap; rejects thresholds that
+    // overflow u64 and amounts implausible for the quote decimals
+    policy.check_quote_amounts()?;
+    // Only snapshots remember what an investor had locked the day before
+    require!(
+        !policy.backpay_enabled || policy.weight_source == WeightSource::DailySnapshot,
+        FeeDistributorError::BackpayRequiresSnapshot
+    );
+    // Live weights give each page its own pool, which a 100% share would hand to page one
+    require!(
+        policy.remainder_mode != RemainderMode::ToInvestors
+            || policy.weight_source.has_day_level_pool(),
+        FeeDistributorError::ToInvestorsRequiresSnapshot
+    );
+    // Pages can only rank globally against a ledger of the whole set
+    require!(
+        policy.top_n_investors == 0 || policy.weight_source.has_day_level_pool(),
+        FeeDistributorError::TopNRequiresDayLevelWeights
+    );
+    // The day-start page ranks the whole set in one transaction
+    require!(
+        policy.top_n_investors == 0
+            || (policy.top_n_investors <= MAX_TOP_N_INVESTOR_SET
+                && progress.total_investors <= MAX_TOP_N_INVESTOR_SET),
+        FeeDistributorError::TopNInvestorSetTooLarge
+    );
+    // The close distribution pays by the last day's ledger, which live pages don't keep
+    require!(
+        !policy.distribute_on_close || policy.weight_source.has_day_level_pool(),
+        FeeDistributorError::DistributeOnCloseRequiresDayLevelWeights
+    );
+    // Only day-level pools pay an investor the same whatever page holds them, and only
+    // account order pays by index; the last-investor dust depends on the final page
+    require!(
+        !policy.deterministic_index_order
+            || (policy.payout_priority == PayoutPriority::AccountOrder
+                && policy.weight_source.has_day_level_pool()
+                && !policy.dust_to_last_investor),
+        FeeDistributorError::DeterministicOrderUnsupported
+    );
+    // USD weights are priced once per snapshot or table, which live pages don't have
+    if policy.weight_in_usd {
+        require!(
+            policy.weight_source.has_day_level_pool(),
+            FeeDistributorError::UsdWeightRequiresDayLevelWeights
+        );
+        require_keys_neq!(policy.price_oracle, Pubkey::default(), FeeDistributorError::InvalidPriceOracle);
+    }
+    // Governance deposits are one per day; page slices would bypass the DAO treasury
+    if policy.creator_deposit_mode == CreatorDepositMode::Governance {
+        require!(
+            policy.creator_payout_mode == CreatorPayoutMode::EndOfDay,
+            FeeDistributorError::GovernanceDepositRequiresEndOfDay
+        );
+        require!(
+            policy.governance_realm != Pubkey::default()
+                && policy.governance_treasury != Pubkey::default(),
+            FeeDistributorError::InvalidGovernanceAccounts
+        );
+    }
+    // Only snapshots remember what an investor had locked at the previous period's start
+    require!(
+        policy.weighting_mode == WeightingMode::Instantaneous
+            || policy.weight_source == WeightSource::DailySnapshot,
+        FeeDistributorError::TimeWeightingRequiresSnapshot
+    );
+    require!(
+        policy.withdrawal_handling == WithdrawalHandling::Current
+            || policy.weight_source == WeightSource::DailySnapshot,
+        FeeDistributorError::WithdrawalGraceRequiresSnapshot
+    );
+    // A fee needs somewhere to go
+    require!(
+        policy.protocol_fee_bps == 0 || policy.protocol_fee_wallet != Pubkey::default(),
+        FeeDistributorError::InvalidProtocolFee
+    );
+    // A conversion needs a pool to swap through into a mint other than quote
+    if let Some(payout_mint) = policy.payout_mint {
+        require!(
+            policy.swap_pool.is_some() && payout_mint != policy.quote_mint,
+            FeeDistributorError::InvalidPayoutConversion
+        );
+        // Pull claims pay quote straight from the treasury
+        require!(
+            policy.distribution_mode == DistributionMode::Push,
+            FeeDistributorError::PayoutConversionRequiresPush
+        );
+    }
+    // A draw is valued at the rate alone, so it needs one
+    require!(
+        policy.payout_settlement == PayoutSettlement::Swap || policy.settlement_rate > 0,
+        FeeDistributorError::InvalidSettlementRate
+    );
+    // Creating ATAs with no rent to fund them would fail every page that needs one
+    require!(
+        policy.max_ata_creations_per_page == 0 || policy.max_ata_creation_funding > 0,
+        FeeDistributorError::InvalidAtaCreationLimits
+    );
+
+    Ok(())
+}
+
+#[event]
+pub struct PolicyUpdated {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub investor_fee_share_bps: u16,
+    pub daily_cap_lamports: u64,
+    pub min_payout_lamports: u64,
+    pub creator_payout_mode: CreatorPayoutMode,
+    pub investor_fallback_wallet: Option<Pubkey>,
+    pub weight_source: WeightSource,
+    pub distribution_mode: DistributionMode,
+    pub distribution_interval: i64,
+    pub distribution_scheme: DistributionScheme,
+    pub fixed_payout_lamports: u64,
+    pub fixed_shortfall: FixedShortfall,
+    pub defer_creator_close: bool,
+    pub max_claim_age_secs: i64,
+    pub remainder_mode: RemainderMode,
+    pub min_payout_unit: MinPayoutUnit,
+    pub attach_memo: bool,
+    pub memo_reference: String,
+    pub fee_source: Option<Pubkey>,
+    pub max_single_investor_bps: u16,
+    pub claim_paused: bool,
+    pub allocation_decimals: u8,
+    pub defer_below_minimum: bool,
+    pub payout_granularity_lamports: u64,
+    pub backpay_enabled: bool,
+    pub backpay_lamports: u64,
+    pub payout_priority: PayoutPriority,
+    pub yield_reserve: Option<Pubkey>,
+    pub pull_claim_cooldown: bool,
+    pub tolerate_claim_failure: bool,
+    pub damm_pool: Option<Pubkey>,
+    pub payout_mint: Option<Pubkey>,
+    pub swap_pool: Option<Pubkey>,
+    pub max_swap_slippage_bps: u16,
+    pub reject_delegated_atas: bool,
+    pub min_claim_to_distribute: u64,
+    pub claim_ema_alpha_bps: u16,
+    pub circuit_breaker_multiple_bps: u32,
+    pub top_n_investors: u32,
+    pub require_opt_in: bool,
+    pub min_creator_share_bps: u16,
+    pub skip_weekends: bool,
+    pub lifetime_cap_lamports: u64,
+    pub weight_in_usd: bool,
+    pub price_oracle: Pubkey,
+    pub creator_deposit_mode: CreatorDepositMode,
+    pub governance_realm: Pubkey,
+    pub governance_treasury: Pubkey,
+    pub confirm_deliveries: bool,
+    pub creator_granularity_lamports: u64,
+    pub max_claim_growth_bps: u32,
+    pub weighting_mode: WeightingMode,
+    pub verbose_events: bool,
+    pub residual_mode: ResidualMode,
+    pub protocol_fee_bps: u16,
+    pub protocol_fee_wallet: Pubkey,
+    pub exclude_creator_from_investors: bool,
+    pub reconciliation_tolerance_lamports: u64,
+    pub max_transfer_size: u64,
+    pub page_quorum_bps: u16,
+    pub max_oracle_staleness_seconds: u32,
+    pub max_oracle_confidence_bps: u16,
+    pub prefunded_balance_mode: PrefundedBalanceMode,
+    pub weight_blend_bps: u16,
+    pub max_investors_per_day: u32,
+    pub distributor_program: Option<Pubkey>,
+    pub dust_to_last_investor: bool,
+    pub withholding_bps: u16,
+    pub withholding_wallet: Option<Pubkey>,
+    pub distribute_on_close: bool,
+    pub persist_dust_across_days: bool,
+    pub require_creator_signature: bool,
+    pub withdrawal_handling: WithdrawalHandling,
+    pub creator_daily_cap_lamports: u64,
+    pub deterministic_index_order: bool,
+    pub payout_settlement: PayoutSettlement,
+    pub settlement_rate: u64,
+    pub daily_cap_allocation: DailyCapAllocation,
+    pub max_ata_creations_per_page: u8,
+    pub max_ata_creation_funding: u64,
+    pub record_day_history: bool,
+}
+
+impl PolicyUpdated {
+    /// The full resulting policy; `initialize` emits it too, so consecutive events
+    /// give every update's before and after values
+    pub fn from_policy(policy: &DistributionPolicy) -> Self {
+        Self {
+            vault: policy.vault,
+            authority: policy.authority,
+            investor_fee_share_bps: policy.investor_fee_share_bps,
+            daily_cap_lamports: policy.daily_cap_lamports,
+            min_payout_lamports: policy.min_payout_lamports,
+            creator_payout_mode: policy.creator_payout_mode,
+            investor_fallback_wallet: policy.investor_fallback_wallet,
+            weight_source: policy.weight_source,
+            distribution_mode: policy.distribution_mode,
+            distribution_interval: policy.distribution_interval,
+            distribution_scheme: policy.distribution_scheme,
+            fixed_payout_lamports: policy.fixed_payout_lamports,
+            fixed_shortfall: policy.fixed_shortfall,
+            defer_creator_close: policy.defer_creator_close,
+            max_claim_age_secs: policy.max_claim_age_secs,
+            remainder_mode: policy.remainder_mode,
+            min_payout_unit: policy.min_payout_unit,
+            attach_memo: policy.attach_memo,
+            memo_reference: policy.memo_reference.clone(),
+            fee_source: policy.fee_source,
+            max_single_investor_bps: policy.max_single_investor_bps,
+            claim_paused: policy.claim_paused,
+            allocation_decimals: policy.allocation_decimals,
+            defer_below_minimum: policy.defer_below_minimum,
+            payout_granularity_lamports: policy.payout_granularity_lamports,
+            backpay_enabled: policy.backpay_enabled,
+            backpay_lamports: policy.backpay_lamports,
+            payout_priority: policy.payout_priority,
+            yield_reserve: policy.yield_reserve,
+            pull_claim_cooldown: policy.pull_claim_cooldown,
+            tolerate_claim_failure: policy.tolerate_claim_failure,
+            damm_pool: policy.damm_pool,
+            payout_mint: policy.payout_mint,
+            swap_pool: policy.swap_pool,
+            max_swap_slippage_bps: policy.max_swap_slippage_bps,
+            reject_delegated_atas: policy.reject_delegated_atas,
+            min_claim_to_distribute: policy.min_claim_to_distribute,
+            claim_ema_alpha_bps: policy.claim_ema_alpha_bps,
+            circuit_breaker_multiple_bps: policy.circuit_breaker_multiple_bps,
+            top_n_investors: policy.top_n_investors,
+            require_opt_in: policy.require_opt_in,
+            min_creator_share_bps: policy.min_creator_share_bps,
+            skip_weekends: policy.skip_weekends,
+            lifetime_cap_lamports: policy.lifetime_cap_lamports,
+            weight_in_usd: policy.weight_in_usd,
+            price_oracle: policy.price_oracle,
+            creator_deposit_mode: policy.creator_deposit_mode,
+            governance_realm: policy.governance_realm,
+            governance_treasury: policy.governance_treasury,
+            confirm_deliveries: policy.confirm_deliveries,
+            creator_granularity_lamports: policy.creator_granularity_lamports,
+            max_claim_growth_bps: policy.max_claim_growth_bps,
+            weighting_mode: policy.weighting_mode,
+            verbose_events: policy.verbose_events,
+            residual_mode: policy.residual_mode,
+            protocol_fee_bps: policy.protocol_fee_bps,
+            protocol_fee_wallet: policy.protocol_fee_wallet,
+            exclude_creator_from_investors: policy.exclude_creator_from_investors,
+            reconciliation_tolerance_lamports: policy.reconciliation_tolerance_lamports,
+            max_transfer_size: policy.max_transfer_size,
+            page_quorum_bps: policy.page_quorum_bps,
+            max_oracle_staleness_seconds: policy.max_oracle_staleness_seconds,
+            max_oracle_confidence_bps: policy.max_oracle_confidence_bps,
+            prefunded_balance_mode: policy.prefunded_balance_mode,
+            weight_blend_bps: policy.weight_blend_bps,
+            max_investors_per_day: policy.max_investors_per_day,
+            distributor_program: policy.distributor_program,
+            dust_to_last_investor: policy.dust_to_last_investor,
+            withholding_bps: policy.withholding_bps,
+            withholding_wallet: policy.withholding_wallet,
+            distribute_on_close: policy.distribute_on_close,
+            persist_dust_across_days: policy.persist_dust_across_days,
+            require_creator_signature: policy.require_creator_signature,
+            withdrawal_handling: policy.withdrawal_handling,
+            creator_daily_cap_lamports: policy.creator_daily_cap_lamports,
+            deterministic_index_order: policy.deterministic_index_order,
+            payout_settlement: policy.payout_settlement,
+            settlement_rate: policy.settlement_rate,
+            daily_cap_allocation: policy.daily_cap_allocation,
+            max_ata_creations_per_page: policy.max_ata_creations_per_page,
+            max_ata_creation_funding: policy.max_ata_creation_funding,
+            record_day_history: policy.record_day_history,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::*,
+    errors::FeeDistributorError,
+    instructions::distribute::{
+        day_weight, is_excluded_creator_stream, pin_usd_weight_price, read_streamflow_locked_amount,
+        share_overrides_for, share_weight,
+    },
+    math::{page_bounds, to_investor_index},
+    state::*,
+};
+
+/// Commit a page of investor weights for the upcoming day, read from their streams -
+/// authority only, between days
+#[derive(Accounts)]
+#[instruction(page_size: u8)]
+pub struct WriteWeightTable<'info> {
+    /// Policy authority (funds weight table growth)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// The vault identifier
+    /// CHECK: Used only for PDA derivation
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Distribution policy
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), POLICY_SEED],
+        bump = policy.bump,
+        has_one = vault,
+        has_one = authority @ FeeDistributorError::Unauthorized,
+        constraint = !policy.finalized @ FeeDistributorError::VaultArchived,
+    )]
+    pub policy: Account<'info, DistributionPolicy>,
+    
+    /// Distribution progress tracker (records the price a USD-weighted table is pinned at)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), PROGRESS_SEED],
+        bump = progress.bump,
+        has_one = vault,
+    )]
+    pub progress: Account<'info, DistributionProgress>,
+    
+    /// Weight table, grown to fit this page's entries
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.key().as_ref(), WEIGHT_TABLE_SEED],
+        bump = weight_table.bump,
+        has_one = vault,
+        realloc = WeightTable::space(page_bounds(
+            weight_table.next_write_index(progress.latest_epoch.saturating_add(1)),
+            std::cmp::min(page_size, MAX_PAGE_SIZE),
+            progress.total_investors,
+        )?.1),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub weight_table: Account<'info, WeightTable>,
+    
+    /// Share multipliers (required while the policy has overrides)
+    #[account(
+        seeds = [VAULT_SEED, vault.key().as_ref(), SHARE_OVERRIDES_SEED],
+        bump = share_overrides.bump,
+        has_one = vault,
+    )]
+    pub share_overrides: Option<Account<'info, ShareOverrides>>,
+    
+    /// Price oracle (required when a table is started under `weight_in_usd`)
+    /// CHECK: Checked against the policy's price_oracle
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Remaining accounts (passed dynamically):
+    // For each investor in this page, in index order:
+    //   1. stream_account (Streamflow stream)
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WriteWeightTable<'info>>,
+    page_size: u8,
+) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    
+    // Oversized pages are clamped like in distribute_fees
+    require!(page_size > 0, FeeDistributorError::InvalidPageSize);
+    let page_size = std::cmp::min(page_size, MAX_PAGE_SIZE);
+    
+    // The running day reads the table; it is only ever written for the next one
+    require!(!ctx.accounts.progress.is_mid_day(), FeeDistributorError::DayInProgress);
+    
+    // A table committed for an earlier day is stale and is started over, with every
+    // page priced alike
+    let day_epoch = ctx.accounts.progress.latest_epoch.saturating_add(1);
+    let weight_table = &mut ctx.accounts.weight_table;
+    if weight_table.day_epoch != day_epoch {
+        weight_table.reset(day_epoch);
+        ctx.accounts.progress.usd_weight_price = pin_usd_weight_price(
+            &ctx.accounts.policy,
+            ctx.accounts.price_oracle.as_deref(),
+            current_ts,
+        )?;
+    }
+    let progress = &ctx.accounts.progress;
+    
+    let (start_idx, end_idx) =
+        page_bounds(weight_table.write_cursor, page_size, progress.total_investors)?;
+    require!(start_idx < end_idx, FeeDistributorError::InvalidPaginationCursor);
+    let (page_start, page_end) = (to_investor_index(start_idx)?, to_investor_index(end_idx)?);
+    let stream_accounts = ctx
+        .remaining_accounts
+        .get(..end_idx - start_idx)
+        .ok_or(FeeDistributorError::MissingInvestorAccounts)?;
+    
+    let share_overrides = share_overrides_for(
+        &ctx.accounts.policy,
+        ctx.accounts.share_overrides.as_deref(),
+    )?;
+    
+    // Each weight is validated against its stream this once; pages only read it back
+    let table = weight_table.to_account_info();
+    let mut page_locked: u64 = 0;
+    let mut page_weight: u64 = 0;
+    let mut page_active: u32 = 0;
+    for (i, stream_account) in stream_accounts.iter().enumerate() {
+        let locked = read_streamflow_locked_amount(stream_account, current_ts)?;
+        let excluded = is_excluded_creator_stream(&ctx.accounts.policy, stream_account)?;
+        let weight = if excluded {
+            0
+        } else {
+            day_weight(
+                share_weight(share_overrides, &stream_account.key(), locked)?,
+                progress.usd_weight_price,
+            )?
+        };
+        WeightTable::write_entry(
+            &table,
+            start_idx + i,
+            &WeightEntry {
+                stream: stream_account.key(),
+                locked,
+                weight,
+            },
+        )?;
+        
+        page_locked = page_locked
+            .checked_add(locked)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        page_weight = page_weight
+            .checked_add(weight)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        if locked > 0 && !excluded {
+            page_active += 1;
+        }
+    }
+    
+    weight_table.total_locked = weight_table
+        .total_locked
+        .checked_add(page_locked)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    weight_table.total_weight = weight_table
+        .total_weight
+        .checked_add(page_weight)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    weight_table.active_investors += page_active;
+    weight_table.write_cursor = page_end;
+    
+    emit!(WeightTablePage {
+        vault: ctx.accounts.vault.key(),
+        day_epoch,
+        page_start,
+        page_end,
+        total_locked: weight_table.total_locked,
+        total_weight: weight_table.total_weight,
+        complete: page_end == progress.total_investors,
+    });
+    
+    Ok(())
+}
+
+#[event]
+pub struct WeightTablePage {
+    pub vault: Pubkey,
+    /// Day the table is being committed for
+    pub day_epoch: u64,
+    pub page_start: u32,
+    pub page_end: u32,
+    pub total_locked: u64,
+    pub total_weight: u64,
+    pub complete: bool,
+}
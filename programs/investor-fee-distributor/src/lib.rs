@@ -1,11 +1,19 @@
 use anchor_lang::prelude::*;
 
 pub mod constants;
+pub mod distributor_adapter;
 pub mod errors;
 pub mod instructions;
+pub mod math;
 pub mod state;
+pub mod swap_adapter;
+pub mod yield_adapter;
+
+#[cfg(test)]
+mod test_fixtures;
 
 use instructions::*;
+use state::PayoutSplit;
 
 declare_id!("4uZxW8UrmijWukHwDShTqwc8EyhQxJ5rxaijnVXKbZaF");
 
@@ -39,4 +47,296 @@ pub mod investor_fee_distributor {
     ) -> Result<()> {
         instructions::distribute::handler(ctx, page_size)
     }
-}
\ No newline at end of file
+
+    /// Pay the creator remainder and complete a deferred-close day - permissionless
+    pub fn close_day(ctx: Context<CloseDay>) -> Result<()> {
+        instructions::close_day::handler(ctx)
+    }
+
+    /// Settle a day abandoned mid-pagination to the creator - authority only
+    pub fn force_close_day(ctx: Context<ForceCloseDay>) -> Result<()> {
+        instructions::force_close_day::handler(ctx)
+    }
+
+    /// Drop a day's claim before any investor has been paid - authority only
+    pub fn cancel_day(ctx: Context<CancelDay>) -> Result<()> {
+        instructions::cancel_day::handler(ctx)
+    }
+
+    /// Claim the day's fees into the treasury for the next distribution - permissionless
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees::handler(ctx)
+    }
+
+    /// Put principal into the policy's yield reserve - authority only
+    pub fn fund_yield_reserve(ctx: Context<FundYieldReserve>, amount: u64) -> Result<()> {
+        instructions::fund_yield_reserve::handler(ctx, amount)
+    }
+
+    /// Read a page of investor streams into the day's locked snapshot - permissionless
+    pub fn snapshot_page<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SnapshotPage<'info>>,
+        page_size: u8,
+    ) -> Result<()> {
+        instructions::snapshot::handler(ctx, page_size)
+    }
+
+    /// Return the current day's eligible_bps and f_locked, optionally with stream locked
+    /// amounts projected to `as_of_ts` - read-only
+    pub fn get_effective_share<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetEffectiveShare<'info>>,
+        as_of_ts: Option<i64>,
+    ) -> Result<EffectiveShare> {
+        instructions::get_effective_share::handler(ctx, as_of_ts)
+    }
+
+    /// Return the summary of the locked snapshot currently stored - read-only
+    pub fn get_current_snapshot(ctx: Context<GetCurrentSnapshot>) -> Result<SnapshotSummary> {
+        instructions::get_current_snapshot::handler(ctx)
+    }
+
+    /// Return what the current day can still distribute to investors, optionally with
+    /// stream locked amounts projected to `as_of_ts` - read-only
+    pub fn remaining_distributable<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemainingDistributable<'info>>,
+        page_size: u8,
+        as_of_ts: Option<i64>,
+    ) -> Result<RemainingPool> {
+        instructions::remaining_distributable::handler(ctx, page_size, as_of_ts)
+    }
+
+    /// Return every active cap's limit and remaining headroom, and which cap binds -
+    /// read-only
+    pub fn get_cap_headroom(ctx: Context<GetCapHeadroom>) -> Result<CapHeadroom> {
+        instructions::get_cap_headroom::handler(ctx)
+    }
+
+    /// Return the key status fields of up to `MAX_BATCH_STATUS_VAULTS` vaults whose
+    /// progress accounts are passed as remaining accounts - read-only
+    pub fn batch_status<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchStatus<'info>>,
+    ) -> Result<Vec<VaultStatus>> {
+        instructions::batch_status::handler(ctx)
+    }
+
+    /// Return the treasury balance reconciled against every progress counter, at any
+    /// point of the day - authority only, read-only
+    pub fn audit_balance(ctx: Context<AuditBalance>) -> Result<BalanceAudit> {
+        instructions::audit_balance::handler(ctx)
+    }
+
+    /// Return each investor's locked amount, weight and payout for the next page of
+    /// the current day - read-only
+    pub fn preview_page_breakdown<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PreviewPageBreakdown<'info>>,
+        page_size: u8,
+    ) -> Result<Vec<PagePayoutPreview>> {
+        instructions::preview_page_breakdown::handler(ctx, page_size)
+    }
+
+    /// Return what the next page of the current day would pay under a proposed policy
+    /// change next to the live policy, without applying it - read-only
+    pub fn preview_policy_change<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PreviewPolicyChange<'info>>,
+        page_size: u8,
+        params: UpdatePolicyParams,
+    ) -> Result<PolicyChangePreview> {
+        instructions::preview_policy_change::handler(ctx, page_size, params)
+    }
+
+    /// Open a scratch account to simulate the current day into - permissionless
+    pub fn open_day_simulation(ctx: Context<OpenDaySimulation>) -> Result<()> {
+        instructions::open_day_simulation::handler(ctx)
+    }
+
+    /// Simulate the next page of the current day into the caller's scratch account,
+    /// closing the simulated day after its last page - owner only, moves no funds
+    pub fn simulate_full_day<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SimulateFullDay<'info>>,
+        page_size: u8,
+    ) -> Result<()> {
+        instructions::simulate_full_day::handler(ctx, page_size)
+    }
+
+    /// Close a simulation scratch account, returning its rent - owner only
+    pub fn close_day_simulation(ctx: Context<CloseDaySimulation>) -> Result<()> {
+        instructions::close_day_simulation::handler(ctx)
+    }
+
+    /// Estimate the transactions, compute and fees of cranking a full day - read-only
+    pub fn estimate_crank_cost(
+        ctx: Context<EstimateCrankCost>,
+        page_size: u8,
+        compute_unit_price_micro_lamports: u64,
+    ) -> Result<CrankCostEstimate> {
+        instructions::estimate_crank_cost::handler(ctx, page_size, compute_unit_price_micro_lamports)
+    }
+
+    /// Return the compute unit limit to request for a distribute_fees page of
+    /// `page_size` investors - read-only
+    pub fn recommended_compute_units(
+        ctx: Context<RecommendedComputeUnits>,
+        page_size: u8,
+    ) -> Result<ComputeUnitRecommendation> {
+        instructions::recommended_compute_units::handler(ctx, page_size)
+    }
+
+    /// Deposit externally collected fees as the day's claim - policy fee source only
+    pub fn deposit_and_recognize(ctx: Context<DepositAndRecognize>, amount: u64) -> Result<()> {
+        instructions::deposit_and_recognize::handler(ctx, amount)
+    }
+
+    /// Open an investor's pull-mode accrual account - permissionless
+    pub fn open_accrual(ctx: Context<OpenAccrual>) -> Result<()> {
+        instructions::open_accrual::handler(ctx)
+    }
+
+    /// Consent to receive distributions from the vault - stream recipient only
+    pub fn opt_in(ctx: Context<OptIn>) -> Result<()> {
+        instructions::opt_in::handler(ctx)
+    }
+
+    /// Withdraw the caller's accrued pull-mode payouts
+    pub fn claim_my_payout(ctx: Context<ClaimMyPayout>) -> Result<()> {
+        instructions::claim_my_payout::handler(ctx)
+    }
+
+    /// Return an investor's all-time total credited to their accrual - read-only
+    pub fn get_investor_lifetime(ctx: Context<GetInvestorLifetime>) -> Result<u64> {
+        instructions::get_investor_lifetime::handler(ctx)
+    }
+
+    /// Update tunable policy parameters - authority only, between days
+    pub fn update_policy(ctx: Context<UpdatePolicy>, params: UpdatePolicyParams) -> Result<()> {
+        instructions::update_policy::handler(ctx, params)
+    }
+
+    /// Update only the minimum payout threshold - authority only, between days
+    pub fn set_min_payout(ctx: Context<UpdatePolicy>, min_payout_lamports: u64) -> Result<()> {
+        instructions::set_min_payout::handler(ctx, min_payout_lamports)
+    }
+
+    /// Pause or resume fee claims while distribution continues - authority only
+    pub fn set_claim_paused(ctx: Context<UpdatePolicy>, claim_paused: bool) -> Result<()> {
+        instructions::set_claim_paused::handler(ctx, claim_paused)
+    }
+
+    /// Set an investor's negotiated share multiplier and minimum payout - authority
+    /// only, between days
+    pub fn set_share_override(
+        ctx: Context<SetShareOverride>,
+        stream: Pubkey,
+        multiplier_bps: u16,
+        min_payout_override: Option<u64>,
+    ) -> Result<()> {
+        instructions::set_share_override::handler(ctx, stream, multiplier_bps, min_payout_override)
+    }
+
+    /// Add investors to the end of the distribution set or prune the last ones -
+    /// authority only, between days
+    pub fn set_investor_count(ctx: Context<SetInvestorCount>, total_investors: u32) -> Result<()> {
+        instructions::set_investor_count::handler(ctx, total_investors)
+    }
+
+    /// Split an investor's pushed payouts with a second ATA, or clear the split -
+    /// authority only
+    pub fn set_payout_split(
+        ctx: Context<SetPayoutSplit>,
+        stream: Pubkey,
+        payout_split: Option<PayoutSplit>,
+    ) -> Result<()> {
+        instructions::set_payout_split::handler(ctx, stream, payout_split)
+    }
+
+    /// Create the vault's weight table for `OnChainTable` weights - authority only
+    pub fn initialize_weight_table(ctx: Context<InitializeWeightTable>) -> Result<()> {
+        instructions::initialize_weight_table::handler(ctx)
+    }
+
+    /// Commit a page of investor weights for the upcoming day - authority only, between days
+    pub fn write_weight_table<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WriteWeightTable<'info>>,
+        page_size: u8,
+    ) -> Result<()> {
+        instructions::write_weight_table::handler(ctx, page_size)
+    }
+
+    /// Create a treasury coordinator that splits one fee pool between vaults - authority only
+    pub fn initialize_coordinator(ctx: Context<InitializeCoordinator>) -> Result<()> {
+        instructions::initialize_coordinator::handler(ctx)
+    }
+
+    /// Add, reweight or remove a coordinator member vault - authority only
+    pub fn set_coordinator_member(ctx: Context<SetCoordinatorMember>, share_bps: u16) -> Result<()> {
+        instructions::set_coordinator_member::handler(ctx, share_bps)
+    }
+
+    /// Split the coordinator treasury into the member treasuries by share - permissionless
+    pub fn split_coordinator_claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SplitCoordinatorClaim<'info>>,
+    ) -> Result<()> {
+        instructions::split_coordinator_claim::handler(ctx)
+    }
+
+    /// Start the next day even if its claim is below the policy minimum - authority only
+    pub fn force_distribution(ctx: Context<ForceDistribution>) -> Result<()> {
+        instructions::force_distribution::handler(ctx)
+    }
+
+    /// Let days start again after the circuit breaker tripped, accepting the pending
+    /// claim - authority only
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+        instructions::reset_circuit_breaker::handler(ctx)
+    }
+
+    /// Reset a corrupted progress account to its post-initialize state, given
+    /// `FORCE_RESET_PROGRESS_CONFIRMATION` - authority only
+    pub fn force_reset_progress(ctx: Context<ForceResetProgress>, confirmation: [u8; 16]) -> Result<()> {
+        instructions::force_reset_progress::handler(ctx, confirmation)
+    }
+
+    /// Recreate a missing progress account for an existing policy and treasury -
+    /// authority only
+    pub fn init_progress(ctx: Context<InitProgress>, params: InitProgressParams) -> Result<()> {
+        instructions::init_progress::handler(ctx, params)
+    }
+
+    /// Pay a finalized vault's leftover treasury to the creator - permissionless
+    pub fn sweep_finalized_treasury(ctx: Context<SweepFinalizedTreasury>) -> Result<()> {
+        instructions::sweep_finalized_treasury::handler(ctx)
+    }
+
+    /// Archive a vault, sweeping its residual balance to the creator (to investors
+    /// first under `distribute_on_close`) and optionally closing its progress and
+    /// treasury - authority only
+    pub fn finalize_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeVault<'info>>,
+        close_accounts: bool,
+    ) -> Result<()> {
+        instructions::finalize_vault::handler(ctx, close_accounts)
+    }
+
+    /// Move an empty, idle vault to a new quote mint - authority only
+    pub fn migrate_quote_mint(ctx: Context<MigrateQuoteMint>) -> Result<()> {
+        instructions::migrate_quote_mint::handler(ctx)
+    }
+
+    /// Open the record a day's close writes its totals into - permissionless
+    pub fn open_day_record(ctx: Context<OpenDayRecord>, day_epoch: u64) -> Result<()> {
+        instructions::open_day_record::handler(ctx, day_epoch)
+    }
+
+    /// Reconcile up to `MAX_AUDIT_HISTORY_DAYS` consecutive closed days from their
+    /// day records, starting at `from_epoch` - read-only
+    pub fn audit_history<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AuditHistory<'info>>,
+        from_epoch: u64,
+    ) -> Result<HistoryAudit> {
+        instructions::audit_history::handler(ctx, from_epoch)
+    }
+
+    /// Move the treasury to a user-controlled wallet - test builds only, authority only
+    pub fn set_test_treasury_authority(ctx: Context<SetTestTreasuryAuthority>) -> Result<()> {
+        instructions::set_test_treasury_authority::handler(ctx)
+    }
+}
@@ -0,0 +1,943 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, errors::FeeDistributorError};
+
+/// Locked fraction of Y0 in basis points, clamped to 10000
+pub fn calculate_locked_fraction(locked_total: u64, y0: u64) -> Result<u64> {
+    if y0 == 0 {
+        return Ok(0);
+    }
+
+    // f_locked = (locked_total / y0) * 10000, clamped before narrowing: far more
+    // locked than Y0 would not fit a u64
+    let fraction = (locked_total as u128)
+        .checked_mul(BASIS_POINTS_DIVISOR as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        .checked_div(y0 as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+
+    Ok(std::cmp::min(fraction, BASIS_POINTS_DIVISOR as u128) as u64)
+}
+
+/// Investor share for the day: the configured percentage cap, bounded by f_locked
+pub fn eligible_bps(investor_fee_share_bps: u16, f_locked: u64) -> u64 {
+    std::cmp::min(investor_fee_share_bps as u64, f_locked)
+}
+
+/// `amount * bps / 10000`, computed in u128 so large claims can't overflow
+pub fn apply_bps(amount: u64, bps: u64) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+
+    u64::try_from(value).map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// `amount * part / whole`, floored; 0 when `whole` is 0
+pub fn pro_rata(amount: u64, part: u64, whole: u64) -> Result<u64> {
+    if whole == 0 {
+        return Ok(0);
+    }
+
+    let value = (amount as u128)
+        .checked_mul(part as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        .checked_div(whole as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+
+    u64::try_from(value).map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// Equal per-investor payout: the fixed amount, or an even split of `pool` if it can't cover everyone
+pub fn equal_split(fixed_amount: u64, pool: u64, active_count: u64) -> u64 {
+    match pool.checked_div(active_count) {
+        Some(even_share) => std::cmp::min(fixed_amount, even_share),
+        None => 0,
+    }
+}
+
+/// Split a payout into `(first, secondary)` with `secondary_bps` of it, floored, to
+/// the secondary destination; the two always add up to `amount`
+pub fn split_payout(amount: u64, secondary_bps: u16) -> Result<(u64, u64)> {
+    let secondary = apply_bps(amount, secondary_bps as u64)?;
+    Ok((amount - secondary, secondary))
+}
+
+/// Split a payout into `(net, withheld)`: the investor keeps `10000 - withholding_bps`
+/// of it, floored, and the rest is withheld; the two always add up to `amount`
+pub fn withholding_split(amount: u64, withholding_bps: u16) -> Result<(u64, u64)> {
+    let net = apply_bps(amount, BASIS_POINTS_DIVISOR - withholding_bps as u64)?;
+    Ok((net, amount - net))
+}
+
+/// Fee a Token-2022 transfer fee of `fee_bps`, capped at `maximum_fee`, takes from a
+/// transfer of `amount`: rounded up, as the token program computes it
+pub fn transfer_fee(amount: u64, fee_bps: u16, maximum_fee: u64) -> u64 {
+    if amount == 0 || fee_bps == 0 {
+        return 0;
+    }
+    
+    let fee = (amount as u128 * fee_bps as u128).div_ceil(BASIS_POINTS_DIVISOR as u128);
+    std::cmp::min(fee, maximum_fee as u128) as u64
+}
+
+/// Whether `actual` is within `tolerance` below `expected`; never above it
+pub fn within_tolerance(actual: u64, expected: u64, tolerance: u64) -> bool {
+    actual <= expected && actual >= expected.saturating_sub(tolerance)
+}
+
+/// Transfers of at most `max_transfer_size` (0 = no limit) needed to send `amount`
+pub fn installment_count(amount: u64, max_transfer_size: u64) -> u64 {
+    if max_transfer_size == 0 || amount <= max_transfer_size {
+        return 1;
+    }
+    amount.div_ceil(max_transfer_size)
+}
+
+/// `amount` cut into `installment_count` transfers: full-size installments, then
+/// whatever is left. They always add up to `amount`.
+pub fn installments(amount: u64, max_transfer_size: u64) -> Vec<u64> {
+    if installment_count(amount, max_transfer_size) == 1 {
+        return vec![amount];
+    }
+    
+    let mut sizes = vec![max_transfer_size; (amount / max_transfer_size) as usize];
+    let rest = amount % max_transfer_size;
+    if rest > 0 {
+        sizes.push(rest);
+    }
+    sizes
+}
+
+/// `amount` rounded down to a multiple of `granularity`; 0 or 1 leaves it unchanged
+pub fn round_down_to_granularity(amount: u64, granularity: u64) -> u64 {
+    if granularity <= 1 {
+        return amount;
+    }
+
+    amount - amount % granularity
+}
+
+/// `amount * 10^decimals`, for amounts given in whole token units
+pub fn whole_tokens_to_lamports(amount: u64, decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|unit| amount.checked_mul(unit))
+        .ok_or(FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// Deploy-time sanity check of the raw quote amounts against the quote mint's decimals.
+/// A non-zero daily cap must be at least one whole token and at least the minimum
+/// payout (otherwise nothing could ever be paid), and the minimum payout may not exceed
+/// `MAX_MIN_PAYOUT_WHOLE_TOKENS` whole tokens.
+pub fn check_quote_amounts(daily_cap_lamports: u64, min_payout_threshold: u64, quote_decimals: u8) -> Result<()> {
+    // None when a single whole token doesn't fit in a u64
+    let one_token = 10u64.checked_pow(quote_decimals as u32);
+    let max_min_payout = one_token.map_or(u64::MAX, |unit| unit.saturating_mul(MAX_MIN_PAYOUT_WHOLE_TOKENS));
+    require!(
+        min_payout_threshold <= max_min_payout,
+        FeeDistributorError::ImplausibleQuoteAmounts
+    );
+
+    if daily_cap_lamports > 0 {
+        require!(
+            one_token.is_none_or(|unit| daily_cap_lamports >= unit)
+                && daily_cap_lamports >= min_payout_threshold,
+            FeeDistributorError::ImplausibleQuoteAmounts
+        );
+    }
+
+    Ok(())
+}
+
+/// Clamp every payout to `cap` and hand the excess to the uncapped investors pro-rata
+/// by weight, repeating until nobody is over the cap. Each round caps at least one
+/// more investor, so it ends after at most `payouts.len()` rounds. Excess that can't
+/// be placed (everyone capped, or floor rounding) is left unpaid.
+pub fn redistribute_over_cap(payouts: &mut [u64], weights: &[u64], cap: u64) -> Result<()> {
+    let mut capped = vec![false; payouts.len()];
+    
+    loop {
+        let mut excess = 0u64;
+        for (payout, is_capped) in payouts.iter_mut().zip(capped.iter_mut()) {
+            if *payout > cap {
+                excess = excess
+                    .checked_add(*payout - cap)
+                    .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+                *payout = cap;
+                *is_capped = true;
+            }
+        }
+        
+        let uncapped_weight = weights
+            .iter()
+            .zip(&capped)
+            .filter(|(_, is_capped)| !**is_capped)
+            .try_fold(0u64, |total, (weight, _)| total.checked_add(*weight))
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        
+        if excess == 0 || uncapped_weight == 0 {
+            return Ok(());
+        }
+        
+        for ((payout, weight), is_capped) in payouts.iter_mut().zip(weights).zip(&capped) {
+            if !*is_capped {
+                *payout = payout
+                    .checked_add(pro_rata(excess, *weight, uncapped_weight)?)
+                    .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+            }
+        }
+    }
+}
+
+/// Investor index range `[start, end)` of the page starting at `cursor`, clamped to
+/// `total_investors`. Conversions and the addition are checked so no index can wrap.
+/// `start` may still be past the set; callers reject that with their own error.
+pub fn page_bounds(cursor: u32, page_size: u8, total_investors: u32) -> Result<(usize, usize)> {
+    let start = usize::try_from(cursor).map_err(|_| FeeDistributorError::InvalidPaginationCursor)?;
+    let total = usize::try_from(total_investors)
+        .map_err(|_| FeeDistributorError::InvalidPaginationCursor)?;
+    let end = start
+        .checked_add(usize::from(page_size))
+        .ok_or(FeeDistributorError::InvalidPaginationCursor)?;
+
+    Ok((start, std::cmp::min(end, total)))
+}
+
+/// An investor index as stored in progress and events
+pub fn to_investor_index(index: usize) -> Result<u32> {
+    u32::try_from(index).map_err(|_| FeeDistributorError::InvalidPaginationCursor.into())
+}
+
+/// Number of pages of `page_size` investors needed to cover `total_investors`
+pub fn pages_needed(total_investors: u64, page_size: u64) -> u64 {
+    if page_size == 0 {
+        return 0;
+    }
+    
+    total_investors.div_ceil(page_size)
+}
+
+/// Priority fee for `compute_units` at `micro_lamports_per_cu`, rounded up like the runtime
+pub fn priority_fee_lamports(compute_units: u64, micro_lamports_per_cu: u64) -> Result<u64> {
+    let micro_lamports = (compute_units as u128)
+        .checked_mul(micro_lamports_per_cu as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    
+    u64::try_from(micro_lamports.div_ceil(1_000_000))
+        .map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// Timestamp a read-only preview evaluates locked amounts at: `now`, or an `as_of_ts`
+/// between `now` and `MAX_AS_OF_HORIZON` ahead of it
+pub fn resolve_as_of_ts(as_of_ts: Option<i64>, now: i64) -> Result<i64> {
+    let Some(as_of_ts) = as_of_ts else {
+        return Ok(now);
+    };
+    
+    require!(
+        as_of_ts >= now && as_of_ts <= now.saturating_add(MAX_AS_OF_HORIZON),
+        FeeDistributorError::InvalidAsOfTimestamp
+    );
+    Ok(as_of_ts)
+}
+
+/// Headroom left under an absolute cap, where a cap of 0 means unlimited
+pub fn cap_headroom(cap: u64, used: u64) -> u64 {
+    if cap > 0 {
+        cap.saturating_sub(used)
+    } else {
+        u64::MAX
+    }
+}
+
+/// Every cap that bounds what investors can receive on a page
+pub struct DistributionCaps {
+    /// Maximum investor share of the claim in bps (the percentage cap)
+    pub investor_fee_share_bps: u16,
+    /// Locked fraction of Y0 in bps
+    pub f_locked: u64,
+    /// Absolute daily cap on investor payouts (0 = unlimited)
+    pub daily_cap_lamports: u64,
+    /// Investor pool carried over from an earlier day; already investor money, so
+    /// only the locked fraction applies, not the percentage cap
+    pub deferred_pool: u64,
+    /// Already paid to investors against the daily cap
+    pub daily_distributed: u64,
+    /// Lifetime cap left when the day started (u64::MAX = unlimited), shared by the
+    /// day's payouts like the daily cap
+    pub lifetime_headroom: u64,
+    /// Scale the amount by the daily cap's share of the day's full investor pool
+    /// (`DailyCapAllocation::Proportional`), before the cap's headroom applies
+    pub proportional_daily_cap: bool,
+}
+
+impl DistributionCaps {
+    /// Amount investors may receive out of `claimed`: the minimum of every active cap
+    pub fn distributable(&self, claimed: u64) -> Result<u64> {
+        let bps = eligible_bps(self.investor_fee_share_bps, self.f_locked);
+        let mut investor_fee_quote = apply_bps(claimed, bps)?
+            .checked_add(apply_bps(self.deferred_pool, self.f_locked)?)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+
+        // The full pool is what a fully locked day could pay, known from the claim
+        // alone, so every page of the day is scaled by the same ratio
+        if self.proportional_daily_cap && self.daily_cap_lamports > 0 {
+            let full_pool = apply_bps(claimed, self.investor_fee_share_bps as u64)?
+                .checked_add(self.deferred_pool)
+                .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+            if full_pool > self.daily_cap_lamports {
+                investor_fee_quote = pro_rata(investor_fee_quote, self.daily_cap_lamports, full_pool)?;
+            }
+        }
+
+        let absolute_headroom = [
+            cap_headroom(self.daily_cap_lamports, self.daily_distributed),
+            self.lifetime_headroom.saturating_sub(self.daily_distributed),
+        ];
+
+        Ok(absolute_headroom
+            .iter()
+            .fold(investor_fee_quote, |amount, headroom| std::cmp::min(amount, *headroom)))
+    }
+}
+
+/// Split a coordinator treasury `balance` by member `shares_bps` (summing to 10000),
+/// returning each member's amount and the rounding dust left over
+pub fn split_by_shares(balance: u64, shares_bps: &[u16]) -> Result<(Vec<u64>, u64)> {
+    let amounts = shares_bps
+        .iter()
+        .map(|share| apply_bps(balance, *share as u64))
+        .collect::<Result<Vec<u64>>>()?;
+    let allocated = amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    let dust = balance
+        .checked_sub(allocated)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    
+    Ok((amounts, dust))
+}
+
+/// Investor pool the remaining `LiveStreams` pages of a day can pay, one locked
+/// fraction per page in crank order. Each page is assumed paid in full, so it uses up
+/// its part of the daily cap before the next page is sized.
+pub fn remaining_pages_pool(mut caps: DistributionCaps, claimed: u64, page_f_locked: &[u64]) -> Result<u64> {
+    let mut pool = 0u64;
+    for f_locked in page_f_locked {
+        caps.f_locked = *f_locked;
+        let page_pool = caps.distributable(claimed)?;
+        caps.daily_distributed = caps
+            .daily_distributed
+            .checked_add(page_pool)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+        pool = pool
+            .checked_add(page_pool)
+            .ok_or(FeeDistributorError::ArithmeticOverflow)?;
+    }
+
+    Ok(pool)
+}
+
+/// Moving average of daily claims after folding in `claim`, weighted `alpha_bps`
+/// against the average so far; the first sample (`samples == 0`) seeds it
+pub fn update_claim_ema(ema: u64, samples: u32, claim: u64, alpha_bps: u16) -> u64 {
+    if samples == 0 {
+        return claim;
+    }
+    
+    let alpha = std::cmp::min(alpha_bps as u64, BASIS_POINTS_DIVISOR) as u128;
+    let weighted = ema as u128 * (BASIS_POINTS_DIVISOR as u128 - alpha) + claim as u128 * alpha;
+    // A weighted mean never exceeds the larger of its inputs
+    (weighted / BASIS_POINTS_DIVISOR as u128) as u64
+}
+
+/// Whether `claim` exceeds `multiple_bps` of the claim average (30000 = 3x). Never
+/// while the breaker is disabled (0) or before `CIRCUIT_BREAKER_WARMUP_DAYS` samples
+pub fn is_anomalous_claim(claim: u64, ema: u64, samples: u32, multiple_bps: u32) -> bool {
+    multiple_bps > 0
+        && samples >= CIRCUIT_BREAKER_WARMUP_DAYS
+        && claim as u128 * BASIS_POINTS_DIVISOR as u128 > ema as u128 * multiple_bps as u128
+}
+
+/// A `price * 10^expo` oracle quote in `USD_PRICE_SCALE` fixed point, rounded down
+pub fn normalize_usd_price(price: u64, expo: i32) -> Result<u64> {
+    let exponent = expo
+        .checked_add(USD_PRICE_SCALE.ilog10() as i32)
+        .ok_or(FeeDistributorError::InvalidPriceOracle)?;
+    let factor = 10u128
+        .checked_pow(exponent.unsigned_abs())
+        .ok_or(FeeDistributorError::InvalidPriceOracle)?;
+    let scaled = if exponent >= 0 {
+        (price as u128).checked_mul(factor).ok_or(FeeDistributorError::ArithmeticOverflow)?
+    } else {
+        price as u128 / factor
+    };
+    
+    u64::try_from(scaled).map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// USD weight of a token weight at a `USD_PRICE_SCALE` price, in the token's base
+/// units; computed in u128 and rejected rather than truncated past u64
+pub fn usd_weight(weight: u64, usd_price: u64) -> Result<u64> {
+    let value = weight as u128 * usd_price as u128 / USD_PRICE_SCALE as u128;
+    u64::try_from(value).map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// Time-weighted locked amount over a period, approximated as the average of the
+/// amounts locked at its start and end
+pub fn time_weighted_locked(start: u64, end: u64) -> u64 {
+    ((start as u128 + end as u128) / 2) as u64
+}
+
+/// Blend of a locked amount and the original allocation, `blend_bps` of it taken from
+/// the allocation. Both are investor-token amounts, so the blend is one too and
+/// normalizes against the day's total like any other weight. Nothing locked means
+/// nothing weighted, so an investor who fully unlocked stays inactive
+pub fn blended_weight(locked: u64, allocation: u64, blend_bps: u16) -> u64 {
+    if locked == 0 {
+        return 0;
+    }
+    let blend = blend_bps as u128;
+    ((locked as u128 * (BASIS_POINTS_DIVISOR as u128 - blend) + allocation as u128 * blend)
+        / BASIS_POINTS_DIVISOR as u128) as u64
+}
+
+/// Day of the week of a unix timestamp in UTC, 0 = Monday .. 6 = Sunday
+pub fn day_of_week(ts: i64) -> u8 {
+    // 1970-01-01 was a Thursday
+    (ts.div_euclid(SECONDS_PER_DAY) + 3).rem_euclid(7) as u8
+}
+
+/// Whether a unix timestamp falls on a Saturday or Sunday in UTC
+pub fn is_weekend(ts: i64) -> bool {
+    day_of_week(ts) >= 5
+}
+
+/// The lowest-ranked `(locked, index)` among the top `n` investors, ranked by locked
+/// amount (largest first) with ties going to the lower index. Investors with nothing
+/// locked are never ranked; when `n` or fewer have anything locked, everyone makes the
+/// cut and `(0, u32::MAX)` is returned.
+pub fn top_n_cutoff(locked: &[u64], n: u32) -> Result<(u64, u32)> {
+    let mut ranked = Vec::new();
+    for (index, amount) in locked.iter().enumerate() {
+        if *amount > 0 {
+            ranked.push((*amount, to_investor_index(index)?));
+        }
+    }
+    
+    let n = n as usize;
+    if n == 0 || ranked.len() <= n {
+        return Ok((0, u32::MAX));
+    }
+    
+    let (_, cutoff, _) = ranked.select_nth_unstable_by(n - 1, |a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    Ok(*cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caps(investor_fee_share_bps: u16, f_locked: u64, daily_cap: u64, daily_used: u64) -> DistributionCaps {
+        DistributionCaps {
+            investor_fee_share_bps,
+            f_locked,
+            daily_cap_lamports: daily_cap,
+            deferred_pool: 0,
+            daily_distributed: daily_used,
+            lifetime_headroom: u64::MAX,
+            proportional_daily_cap: false,
+        }
+    }
+
+    #[test]
+    fn all_caps_unlimited_returns_full_share() {
+        let distributable = caps(10_000, 10_000, 0, 0).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 1_000_000);
+    }
+
+    #[test]
+    fn percentage_cap_binds_when_daily_cap_unlimited() {
+        let distributable = caps(2_500, 10_000, 0, 0).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 250_000);
+    }
+
+    #[test]
+    fn locked_fraction_binds_below_percentage_cap() {
+        let distributable = caps(8_000, 3_000, 0, 0).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 300_000);
+    }
+
+    #[test]
+    fn locked_fraction_at_its_boundaries() {
+        assert_eq!(calculate_locked_fraction(0, 1_000_000).unwrap(), 0);
+        assert_eq!(calculate_locked_fraction(500_000, 0).unwrap(), 0);
+        assert_eq!(calculate_locked_fraction(1, 3).unwrap(), 3_333);
+        assert_eq!(calculate_locked_fraction(1_000_000, 1_000_000).unwrap(), BASIS_POINTS_DIVISOR);
+        // More locked than Y0 clamps to fully locked, even past what a u64 can hold
+        assert_eq!(calculate_locked_fraction(1_000_001, 1_000_000).unwrap(), BASIS_POINTS_DIVISOR);
+        assert_eq!(calculate_locked_fraction(u64::MAX, 1).unwrap(), BASIS_POINTS_DIVISOR);
+    }
+
+    #[test]
+    fn eligible_share_at_the_locked_fraction_boundaries() {
+        // Nothing locked: investors get nothing, whatever the cap
+        assert_eq!(eligible_bps(3_000, 0), 0);
+        // Exactly equal: both bounds give the configured share
+        assert_eq!(eligible_bps(3_000, 3_000), 3_000);
+        assert_eq!(eligible_bps(3_000, 2_999), 2_999);
+        // Fully locked: exactly the configured maximum
+        assert_eq!(eligible_bps(3_000, BASIS_POINTS_DIVISOR), 3_000);
+        assert_eq!(eligible_bps(10_000, BASIS_POINTS_DIVISOR), 10_000);
+
+        let f_locked = calculate_locked_fraction(300_000, 1_000_000).unwrap();
+        assert_eq!(f_locked, 3_000);
+        assert_eq!(caps(3_000, f_locked, 0, 0).distributable(1_000_000).unwrap(), 300_000);
+        assert_eq!(caps(3_000, BASIS_POINTS_DIVISOR, 0, 0).distributable(1_000_000).unwrap(), 300_000);
+        assert_eq!(caps(3_000, 0, 0, 0).distributable(1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn daily_cap_binds_below_percentage_cap() {
+        let distributable = caps(5_000, 10_000, 100_000, 0).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 100_000);
+    }
+
+    #[test]
+    fn percentage_cap_binds_below_daily_cap() {
+        let distributable = caps(1_000, 10_000, 500_000, 0).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 100_000);
+    }
+
+    #[test]
+    fn daily_cap_accounts_for_already_distributed() {
+        let distributable = caps(5_000, 10_000, 100_000, 70_000).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 30_000);
+    }
+
+    #[test]
+    fn lifetime_headroom_is_shared_by_the_days_pages() {
+        let mut caps = caps(5_000, 10_000, 0, 0);
+        caps.lifetime_headroom = 120_000;
+        assert_eq!(caps.distributable(1_000_000).unwrap(), 120_000);
+        // A later live-stream page only gets what the earlier ones left
+        caps.daily_distributed = 100_000;
+        assert_eq!(caps.distributable(1_000_000).unwrap(), 20_000);
+        caps.daily_distributed = 130_000;
+        assert_eq!(caps.distributable(1_000_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn exhausted_daily_cap_distributes_nothing() {
+        let distributable = caps(5_000, 10_000, 100_000, 150_000).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 0);
+    }
+
+    #[test]
+    fn zero_percentage_cap_is_not_unlimited() {
+        // Unlike absolute caps, 0 bps means investors receive nothing
+        let distributable = caps(0, 10_000, 0, 0).distributable(1_000_000).unwrap();
+        assert_eq!(distributable, 0);
+    }
+
+    #[test]
+    fn max_claim_does_not_overflow() {
+        let distributable = caps(10_000, 10_000, 0, 0).distributable(u64::MAX).unwrap();
+        assert_eq!(distributable, u64::MAX);
+
+        let distributable = caps(5_000, 10_000, u64::MAX - 1, 0).distributable(u64::MAX).unwrap();
+        assert_eq!(distributable, u64::MAX / 2);
+    }
+
+    #[test]
+    fn deferred_pool_skips_percentage_cap_but_not_locked_fraction() {
+        let mut with_deferred = caps(2_000, 5_000, 0, 0);
+        with_deferred.deferred_pool = 10_000;
+        // 20% of the claim plus 50% (f_locked) of the deferred pool
+        assert_eq!(with_deferred.distributable(1_000_000).unwrap(), 200_000 + 5_000);
+
+        with_deferred.daily_cap_lamports = 201_000;
+        assert_eq!(with_deferred.distributable(1_000_000).unwrap(), 201_000);
+    }
+
+    #[test]
+    fn page_bounds_clamp_to_the_investor_set() {
+        assert_eq!(page_bounds(0, 40, 120).unwrap(), (0, 40));
+        assert_eq!(page_bounds(80, 40, 100).unwrap(), (80, 100));
+        // A cursor at the end yields an empty page for the caller to reject
+        assert_eq!(page_bounds(100, 40, 100).unwrap(), (100, 100));
+        assert_eq!(page_bounds(u32::MAX, u8::MAX, u32::MAX).unwrap(), (u32::MAX as usize, u32::MAX as usize));
+    }
+
+    #[test]
+    fn investor_index_conversion_rejects_values_past_u32() {
+        assert_eq!(to_investor_index(u32::MAX as usize).unwrap(), u32::MAX);
+        assert!(to_investor_index(u32::MAX as usize + 1).is_err());
+    }
+
+    #[test]
+    fn split_payout_leaves_dust_with_the_first_destination() {
+        assert_eq!(split_payout(1_000, 3_000).unwrap(), (700, 300));
+        assert_eq!(split_payout(1_001, 3_000).unwrap(), (701, 300));
+        assert_eq!(split_payout(3, 5_000).unwrap(), (2, 1));
+        assert_eq!(split_payout(u64::MAX, 9_999).unwrap().0 + split_payout(u64::MAX, 9_999).unwrap().1, u64::MAX);
+    }
+
+    #[test]
+    fn withholding_split_floors_the_net_payout() {
+        assert_eq!(withholding_split(1_000, 3_000).unwrap(), (700, 300));
+        assert_eq!(withholding_split(1_001, 3_000).unwrap(), (700, 301));
+        assert_eq!(withholding_split(1_000, 0).unwrap(), (1_000, 0));
+        assert_eq!(withholding_split(1_000, 10_000).unwrap(), (0, 1_000));
+    }
+
+    #[test]
+    fn round_down_to_granularity_keeps_multiples_only() {
+        assert_eq!(round_down_to_granularity(123_456, 1_000), 123_000);
+        assert_eq!(round_down_to_granularity(999, 1_000), 0);
+        assert_eq!(round_down_to_granularity(123_456, 1), 123_456);
+        assert_eq!(round_down_to_granularity(123_456, 0), 123_456);
+    }
+
+    #[test]
+    fn equal_split_pays_fixed_amount_when_pool_covers_everyone() {
+        assert_eq!(equal_split(1_000, 10_000, 5), 1_000);
+    }
+
+    #[test]
+    fn equal_split_splits_shortfall_evenly() {
+        assert_eq!(equal_split(1_000, 3_000, 5), 600);
+        // Flooring leaves dust for the creator
+        assert_eq!(equal_split(1_000, 1_001, 3), 333);
+    }
+
+    #[test]
+    fn equal_split_without_active_investors_is_zero() {
+        assert_eq!(equal_split(1_000, 10_000, 0), 0);
+    }
+
+    #[test]
+    fn whole_tokens_scale_by_decimals() {
+        assert_eq!(whole_tokens_to_lamports(1, 6).unwrap(), 1_000_000);
+        assert_eq!(whole_tokens_to_lamports(3, 9).unwrap(), 3_000_000_000);
+        assert_eq!(whole_tokens_to_lamports(5, 0).unwrap(), 5);
+    }
+
+    #[test]
+    fn whole_tokens_overflow_is_an_error() {
+        assert_eq!(whole_tokens_to_lamports(18, 18).unwrap(), 18 * 10u64.pow(18));
+        assert!(whole_tokens_to_lamports(19, 18).is_err());
+        assert!(whole_tokens_to_lamports(1, 20).is_err());
+    }
+
+    #[test]
+    fn as_of_ts_defaults_to_now_and_stays_within_the_horizon() {
+        let now = 1_700_000_000;
+        assert_eq!(resolve_as_of_ts(None, now).unwrap(), now);
+        assert_eq!(resolve_as_of_ts(Some(now + SECONDS_PER_DAY), now).unwrap(), now + SECONDS_PER_DAY);
+        assert_eq!(resolve_as_of_ts(Some(now + MAX_AS_OF_HORIZON), now).unwrap(), now + MAX_AS_OF_HORIZON);
+
+        assert!(resolve_as_of_ts(Some(now - 1), now).is_err());
+        assert!(resolve_as_of_ts(Some(now + MAX_AS_OF_HORIZON + 1), now).is_err());
+    }
+
+    #[test]
+    fn coordinator_split_never_allocates_more_than_the_balance() {
+        let (amounts, dust) = split_by_shares(1_000_003, &[6_000, 4_000]).unwrap();
+        assert_eq!(amounts, vec![600_001, 400_001]);
+        assert_eq!(dust, 1);
+
+        let (amounts, dust) = split_by_shares(10, &[3_333, 3_333, 3_334]).unwrap();
+        assert_eq!(amounts.iter().sum::<u64>() + dust, 10);
+
+        let (amounts, dust) = split_by_shares(u64::MAX, &[5_000, 5_000]).unwrap();
+        assert_eq!(amounts.iter().sum::<u64>() + dust, u64::MAX);
+    }
+
+    #[test]
+    fn remaining_pages_share_the_daily_cap_in_order() {
+        // 30% share, pages 10% and 40% locked, 1M claim: 100k + 300k uncapped
+        assert_eq!(remaining_pages_pool(caps(3_000, 0, 0, 0), 1_000_000, &[1_000, 4_000]).unwrap(), 400_000);
+
+        // A 250k cap with 50k already paid leaves 200k: page one takes 100k, page two the rest
+        assert_eq!(
+            remaining_pages_pool(caps(3_000, 0, 250_000, 50_000), 1_000_000, &[1_000, 4_000]).unwrap(),
+            200_000
+        );
+
+        // Fully unlocked pages and no pages left both mean nothing remains
+        assert_eq!(remaining_pages_pool(caps(3_000, 0, 0, 0), 1_000_000, &[0, 0]).unwrap(), 0);
+        assert_eq!(remaining_pages_pool(caps(3_000, 0, 0, 0), 1_000_000, &[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn proportional_daily_cap_scales_every_page_alike() {
+        // 30% share of a 1M claim is a 300k full pool; a 150k cap halves every page
+        let proportional = || DistributionCaps { proportional_daily_cap: true, ..caps(3_000, 1_000, 150_000, 0) };
+        assert_eq!(proportional().distributable(1_000_000).unwrap(), 50_000);
+
+        // In page order the first page takes its whole 100k and the second what's left
+        assert_eq!(remaining_pages_pool(caps(3_000, 0, 150_000, 0), 1_000_000, &[1_000, 2_000]).unwrap(), 150_000);
+        assert_eq!(caps(3_000, 2_000, 150_000, 100_000).distributable(1_000_000).unwrap(), 50_000);
+
+        // Proportionally each page gets half of its pool, in either order and as one page
+        assert_eq!(
+            DistributionCaps { f_locked: 2_000, daily_distributed: 50_000, ..proportional() }
+                .distributable(1_000_000)
+                .unwrap(),
+            100_000
+        );
+        for pages in [&[1_000, 2_000][..], &[2_000, 1_000], &[3_000]] {
+            assert_eq!(remaining_pages_pool(proportional(), 1_000_000, pages).unwrap(), 150_000);
+        }
+
+        // A cap above the full pool never binds
+        let unbound = DistributionCaps { daily_cap_lamports: 400_000, ..proportional() };
+        assert_eq!(unbound.distributable(1_000_000).unwrap(), 100_000);
+    }
+
+    #[test]
+    fn quote_amounts_are_checked_against_decimals() {
+        // 6 decimals (USDC): no cap and a 0.01 USDC minimum are fine
+        assert!(check_quote_amounts(0, 10_000, 6).is_ok());
+        assert!(check_quote_amounts(5_000_000, 10_000, 6).is_ok());
+
+        // A cap below one whole token reads as lamports meant to be tokens
+        assert!(check_quote_amounts(999_999, 0, 6).is_err());
+        // A cap below the minimum could never pay anyone
+        assert!(check_quote_amounts(2_000_000, 3_000_000, 6).is_err());
+        // A minimum above a million whole tokens is scaled twice
+        assert!(check_quote_amounts(0, 1_000_001_000_000, 6).is_err());
+
+        // 0 decimals: one lamport is one token
+        assert!(check_quote_amounts(1, 1, 0).is_ok());
+        assert!(check_quote_amounts(0, MAX_MIN_PAYOUT_WHOLE_TOKENS + 1, 0).is_err());
+
+        // 18 decimals: one token fits, a million doesn't, so only the cap floor applies
+        assert!(check_quote_amounts(10u64.pow(18), u64::MAX, 18).is_err());
+        assert!(check_quote_amounts(0, u64::MAX, 18).is_ok());
+        // 20 decimals: a whole token exceeds u64, so no floor on the cap
+        assert!(check_quote_amounts(1, 0, 20).is_ok());
+    }
+
+    #[test]
+    fn cap_redistributes_excess_to_other_investors() {
+        // 10_000 pool, one whale at 70%, cap of 40%
+        let weights = [7_000, 2_000, 1_000];
+        let mut payouts = [7_000, 2_000, 1_000];
+
+        redistribute_over_cap(&mut payouts, &weights, 4_000).unwrap();
+
+        assert_eq!(payouts, [4_000, 4_000, 2_000]);
+    }
+
+    #[test]
+    fn cap_converges_when_several_investors_exceed_it() {
+        // Two investors start over the cap, and redistribution pushes a third over
+        let weights = [4_000, 3_500, 1_600, 600, 300];
+        let mut payouts = weights;
+        let cap = 2_500;
+
+        redistribute_over_cap(&mut payouts, &weights, cap).unwrap();
+
+        assert!(payouts.iter().all(|payout| *payout <= cap));
+        assert_eq!(payouts[..3], [cap, cap, cap]);
+        // The last two share the 2_500 left pro-rata 600:300, floored
+        assert_eq!(payouts[3..], [1_666, 833]);
+        let total: u64 = payouts.iter().sum();
+        assert!(total <= weights.iter().sum::<u64>());
+        assert_eq!(total, 10_000 - 1);
+    }
+
+    #[test]
+    fn cap_leaves_excess_unpaid_when_everyone_is_capped() {
+        let weights = [5_000, 5_000];
+        let mut payouts = [5_000, 5_000];
+
+        redistribute_over_cap(&mut payouts, &weights, 3_000).unwrap();
+
+        assert_eq!(payouts, [3_000, 3_000]);
+    }
+
+    #[test]
+    fn pages_needed_rounds_up_partial_pages() {
+        assert_eq!(pages_needed(100, 50), 2);
+        assert_eq!(pages_needed(101, 50), 3);
+        assert_eq!(pages_needed(7, 50), 1);
+        assert_eq!(pages_needed(0, 50), 0);
+        assert_eq!(pages_needed(10, 0), 0);
+    }
+
+    #[test]
+    fn priority_fee_rounds_up_to_whole_lamports() {
+        assert_eq!(priority_fee_lamports(200_000, 0).unwrap(), 0);
+        assert_eq!(priority_fee_lamports(200_000, 10_000).unwrap(), 2_000);
+        assert_eq!(priority_fee_lamports(1, 1).unwrap(), 1);
+    }
+
+    #[test]
+    fn result_is_minimum_of_every_active_cap() {
+        let claimed = 987_654_321;
+        for share in [0u16, 1, 2_500, 5_000, 9_999, 10_000] {
+            for f_locked in [0u64, 1, 4_000, 10_000] {
+                for daily_cap in [0u64, 1, 50_000_000, u64::MAX] {
+                    let distributable = caps(share, f_locked, daily_cap, 0)
+                        .distributable(claimed)
+                        .unwrap();
+
+                    let by_share = claimed as u128 * share as u128 / 10_000;
+                    let by_locked = claimed as u128 * f_locked as u128 / 10_000;
+                    let by_daily = if daily_cap == 0 { u128::MAX } else { daily_cap as u128 };
+                    let expected = by_share.min(by_locked).min(by_daily);
+
+                    assert_eq!(distributable as u128, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn claim_ema_seeds_on_the_first_day_and_then_smooths() {
+        assert_eq!(update_claim_ema(0, 0, 1_000_000, 2_000), 1_000_000);
+        // 20% of the way to today's claim
+        assert_eq!(update_claim_ema(1_000_000, 1, 2_000_000, 2_000), 1_200_000);
+        assert_eq!(update_claim_ema(1_000_000, 1, 0, 2_000), 800_000);
+        // Extremes don't overflow
+        assert_eq!(update_claim_ema(u64::MAX, 5, u64::MAX, 2_000), u64::MAX);
+        assert_eq!(update_claim_ema(7, 5, 9, 10_000), 9);
+    }
+
+    #[test]
+    fn circuit_breaker_waits_for_history_and_trips_only_above_the_multiple() {
+        // Plenty of deviation, but not enough days to judge it by
+        for samples in 0..CIRCUIT_BREAKER_WARMUP_DAYS {
+            assert!(!is_anomalous_claim(1_000_000, 1, samples, 30_000));
+        }
+
+        let samples = CIRCUIT_BREAKER_WARMUP_DAYS;
+        assert!(!is_anomalous_claim(3_000_000, 1_000_000, samples, 30_000));
+        assert!(is_anomalous_claim(3_000_001, 1_000_000, samples, 30_000));
+        // Disabled, and small claims never trip it
+        assert!(!is_anomalous_claim(u64::MAX, 1, samples, 0));
+        assert!(!is_anomalous_claim(0, 1_000_000, samples, 30_000));
+    }
+
+    #[test]
+    fn top_n_cutoff_ranks_by_locked_and_breaks_ties_by_index() {
+        let locked = [500, 0, 900, 500, 100, 900];
+        assert_eq!(top_n_cutoff(&locked, 1).unwrap(), (900, 2));
+        assert_eq!(top_n_cutoff(&locked, 2).unwrap(), (900, 5));
+        // The tie at 500 goes to the lower index
+        assert_eq!(top_n_cutoff(&locked, 3).unwrap(), (500, 0));
+        assert_eq!(top_n_cutoff(&locked, 4).unwrap(), (500, 3));
+        // Five investors have something locked; the empty stream never ranks
+        assert_eq!(top_n_cutoff(&locked, 5).unwrap(), (0, u32::MAX));
+        assert_eq!(top_n_cutoff(&locked, 50).unwrap(), (0, u32::MAX));
+    }
+
+    #[test]
+    fn usd_prices_normalize_to_micro_usd_and_weights_never_truncate() {
+        // $1.23456789 quoted at expo -8, $2 at expo 0
+        assert_eq!(normalize_usd_price(123_456_789, -8).unwrap(), 1_234_567);
+        assert_eq!(normalize_usd_price(2, 0).unwrap(), 2_000_000);
+        assert!(normalize_usd_price(u64::MAX, 0).is_err());
+        assert!(normalize_usd_price(1, -60).is_err());
+
+        assert_eq!(usd_weight(1_000, 1_500_000).unwrap(), 1_500);
+        assert_eq!(usd_weight(u64::MAX, USD_PRICE_SCALE).unwrap(), u64::MAX);
+        assert!(usd_weight(u64::MAX, 2 * USD_PRICE_SCALE).is_err());
+    }
+
+    #[test]
+    fn time_weighting_favours_investors_locked_through_the_period() {
+        // A kept 1_000 locked all period, B locked 1_000 only just before the snapshot
+        let (a_start, a_end, b_start, b_end) = (1_000, 1_000, 0, 1_000);
+        let pool = 9_000;
+
+        // Instantaneous: both show 1_000 at the snapshot and split evenly
+        assert_eq!(pro_rata(pool, a_end, a_end + b_end).unwrap(), 4_500);
+        assert_eq!(pro_rata(pool, b_end, a_end + b_end).unwrap(), 4_500);
+
+        // Time-weighted: B averages 500 over the period, so A gets two thirds
+        let (a, b) = (time_weighted_locked(a_start, a_end), time_weighted_locked(b_start, b_end));
+        assert_eq!((a, b), (1_000, 500));
+        assert_eq!(pro_rata(pool, a, a + b).unwrap(), 6_000);
+        assert_eq!(pro_rata(pool, b, a + b).unwrap(), 3_000);
+
+        // Unlocking during the period counts too, and the average never overflows
+        assert_eq!(time_weighted_locked(1_000, 0), 500);
+        assert_eq!(time_weighted_locked(3, 0), 1);
+        assert_eq!(time_weighted_locked(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn blended_weights_mix_locked_and_allocation_and_conserve_the_pool() {
+        // (locked, allocation): A still locks all of 1_000, B 250 of 1_000, C 500 of 3_000
+        let investors = [(1_000, 1_000), (250, 1_000), (500, 3_000)];
+        let pool = 10_000;
+        let weights = |blend_bps| investors.map(|(locked, allocation)| blended_weight(locked, allocation, blend_bps));
+
+        assert_eq!(weights(0), [1_000, 250, 500]);
+        assert_eq!(weights(10_000), [1_000, 1_000, 3_000]);
+        assert_eq!(weights(5_000), [1_000, 625, 1_750]);
+
+        for blend_bps in [0, 5_000, 10_000] {
+            let weights = weights(blend_bps);
+            let total: u64 = weights.iter().sum();
+            let paid: u64 = weights.iter().map(|weight| pro_rata(pool, *weight, total).unwrap()).sum();
+            // Flooring loses less than a lamport per investor
+            assert!(paid <= pool && pool - paid < weights.len() as u64);
+        }
+
+        // A fully unlocked investor isn't weighted by their allocation alone
+        assert_eq!(blended_weight(0, 1_000, 10_000), 0);
+        assert_eq!(blended_weight(u64::MAX, u64::MAX, 3_333), u64::MAX);
+    }
+
+    #[test]
+    fn transfer_fee_rounds_up_to_its_maximum() {
+        assert_eq!(transfer_fee(0, 50, 1_000), 0);
+        assert_eq!(transfer_fee(1_000, 0, 1_000), 0);
+        // 0.5% of 1_001 is 5.005, rounded up
+        assert_eq!(transfer_fee(1_001, 50, 1_000), 6);
+        assert_eq!(transfer_fee(1_000_000, 50, 1_000), 1_000);
+        assert_eq!(transfer_fee(u64::MAX, 10_000, u64::MAX), u64::MAX);
+
+        assert!(within_tolerance(998, 1_000, 2));
+        assert!(!within_tolerance(997, 1_000, 2));
+        assert!(!within_tolerance(1_001, 1_000, 2));
+    }
+
+    #[test]
+    fn installments_add_up_to_the_payout() {
+        assert_eq!(installments(1_000, 0), vec![1_000]);
+        assert_eq!(installments(1_000, 1_000), vec![1_000]);
+        assert_eq!(installments(1_000, 300), vec![300, 300, 300, 100]);
+        assert_eq!(installments(900, 300), vec![300, 300, 300]);
+        assert_eq!(installments(0, 300), vec![0]);
+        assert_eq!(installment_count(1_000, 300), 4);
+        assert_eq!(installment_count(u64::MAX, 1), u64::MAX);
+        for (amount, max) in [(7_777u64, 1_000u64), (1, 1), (123_456, 7)] {
+            let sizes = installments(amount, max);
+            assert_eq!(sizes.iter().sum::<u64>(), amount);
+            assert!(sizes.iter().all(|&size| size <= max));
+        }
+    }
+
+    #[test]
+    fn day_of_week_counts_from_a_thursday_epoch_in_utc() {
+        assert_eq!(day_of_week(0), 3);
+        assert_eq!(day_of_week(-1), 2);
+        // 2024-01-01 was a Monday
+        let monday = 1_704_067_200;
+        assert_eq!(day_of_week(monday), 0);
+        assert_eq!(day_of_week(monday + 5 * SECONDS_PER_DAY - 1), 4);
+        assert!(!is_weekend(monday + 5 * SECONDS_PER_DAY - 1));
+        assert!(is_weekend(monday + 5 * SECONDS_PER_DAY));
+        assert!(is_weekend(monday + 7 * SECONDS_PER_DAY - 1));
+        assert_eq!(day_of_week(monday + 7 * SECONDS_PER_DAY), 0);
+    }
+}
@@ -1,11 +1,211 @@
 use anchor_lang::prelude::*;
 
+/// When the creator's share of a day's claim is paid out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CreatorPayoutMode {
+    /// Creator receives the whole remainder on the final page of the day
+    EndOfDay,
+    /// Creator receives a pro-rata slice of their guaranteed share on every page,
+    /// topped up with the reconciled remainder on the final page
+    PerPage,
+}
+
+/// How the creator's remainder reaches the creator side
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CreatorDepositMode {
+    /// Plain token transfer to the creator's quote ATA
+    Transfer,
+    /// Transferred into a DAO's SPL Governance treasury; fails when the governance
+    /// accounts are missing or don't check out
+    Governance,
+}
+
+/// Where per-investor locked amounts come from during distribution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeightSource {
+    /// Read each page's streams while distributing that page
+    LiveStreams,
+    /// Read from the day's `LockedSnapshot`, built by `snapshot_page` beforehand
+    DailySnapshot,
+    /// Read from the `WeightTable` the authority committed for the day with
+    /// `write_weight_table`
+    OnChainTable,
+}
+
+impl WeightSource {
+    /// Whether weights are fixed for the whole day, so every page shares one day-level
+    /// investor pool
+    pub fn has_day_level_pool(self) -> bool {
+        matches!(self, WeightSource::DailySnapshot | WeightSource::OnChainTable)
+    }
+}
+
+/// Which locked amount a snapshot weighs each investor by
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeightingMode {
+    /// The amount locked when the snapshot reads the stream
+    Instantaneous,
+    /// The average of the amounts locked at the previous snapshot and this one, so
+    /// tokens kept locked through the period count for more
+    TimeWeighted,
+    /// The amount locked when the snapshot reads the stream, blended with the stream's
+    /// deposited amount (the original allocation) by `weight_blend_bps`
+    Blended,
+    /// The stream's deposited amount, whatever has vested or unlocked since, so
+    /// weights and the locked fraction stay constant for the program's duration
+    DepositedAmount,
+}
+
+/// How a snapshot weighs an investor whose locked amount fell since the previous
+/// snapshot, e.g. after a partial withdrawal mid-period
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WithdrawalHandling {
+    /// The lower amount read now, at once
+    Current,
+    /// The higher of the previous snapshot's reading and the current one, for the
+    /// period the snapshot covers; the next snapshot starts from the lower reading
+    Grace,
+}
+
+impl WithdrawalHandling {
+    /// Locked amount a snapshot reads for a stream holding `stream_locked` now and
+    /// `period_start_locked` at the previous snapshot. The stream can't tell a
+    /// withdrawal from tokens unlocking, so `Grace` covers any decrease.
+    pub fn snapshot_locked(self, period_start_locked: u64, stream_locked: u64) -> u64 {
+        match self {
+            WithdrawalHandling::Current => stream_locked,
+            WithdrawalHandling::Grace => std::cmp::max(period_start_locked, stream_locked),
+        }
+    }
+}
+
+/// Where converted payouts are funded from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PayoutSettlement {
+    /// Each payout's quote is swapped through the swap pool
+    Swap,
+    /// Each payout is drawn from the pre-funded payout treasury at `settlement_rate`,
+    /// and its quote is paid to the swap pool account, reimbursing the funder
+    TreasuryDraw,
+}
+
+/// How investor payouts reach investors
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DistributionMode {
+    /// The crank transfers each payout to the investor's ATA
+    Push,
+    /// The crank records each payout in the investor's accrual PDA and the
+    /// investor withdraws it with `claim_my_payout`
+    Pull,
+}
+
+/// How the investor pool is split between active (locked > 0) investors
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DistributionScheme {
+    /// Weighted by each investor's locked amount
+    ProRata,
+    /// Every active investor receives `fixed_payout_lamports`, funds permitting
+    EqualFixed,
+}
+
+/// What `EqualFixed` does when the pool can't pay the fixed amount to everyone
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FixedShortfall {
+    /// Lower everyone's payout to `pool / active_count`
+    SplitEvenly,
+    /// Pay the full fixed amount in index order until the pool runs out
+    ByIndex,
+}
+
+/// What happens to treasury funds nothing accounts for once a day completes (dust,
+/// transfer-fee savings, direct deposits)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResidualMode {
+    /// Left unrecognized in the treasury until the next claim picks them up
+    RollToNextDay,
+    /// Paid to the creator's quote ATA as soon as the day completes
+    SweepToCreator,
+    /// Recognized as pending, so they are carried into the next day with its claim
+    SweepToDust,
+}
+
+/// What the vault's first claim does with quote already in the treasury before it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PrefundedBalanceMode {
+    /// Held in the treasury as `prefunded_reserve`; only the claim's own delta is claimed
+    Reserve,
+    /// Counted as claimed along with the claim, like any other unrecognized balance
+    Distribute,
+}
+
+/// Where the day's undistributable investor residual goes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RemainderMode {
+    /// Included in the creator remainder
+    Creator,
+    /// Burned from the treasury; the creator still receives the rest of the remainder
+    Burn,
+    /// No creator leg: the whole claim goes to investors pro-rata to their weights, as if
+    /// `investor_fee_share_bps` and `f_locked` were both 100%. Whatever a day leaves
+    /// unpaid (rounding dust, skipped payouts, the daily cap) is carried into the next
+    /// day's investor pool instead of being paid to the creator. Requires `DailySnapshot`
+    /// weights, so every page shares one day-level pool.
+    ToInvestors,
+}
+
+/// How a `LiveStreams` day shares `daily_cap_lamports` between its pages; day-level
+/// weights already apply the cap to one day-level pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DailyCapAllocation {
+    /// Pages use up the cap in crank order, so once it binds later pages get less
+    PageOrder,
+    /// Every page's pool is scaled by the cap's share of the day's full investor
+    /// pool, so page position doesn't matter
+    Proportional,
+}
+
+/// Order a page's investors are paid in, which decides who misses out when the pool
+/// runs short. Ties keep the order the accounts were supplied in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PayoutPriority {
+    /// The order the accounts were supplied in
+    AccountOrder,
+    /// Largest locked amount first
+    LockedDescending,
+    /// Smallest locked amount first
+    LockedAscending,
+}
+
+/// Unit `min_payout_lamports` is expressed in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MinPayoutUnit {
+    /// Raw quote token lamports
+    Lamports,
+    /// Whole quote tokens, scaled by `quote_decimals`
+    WholeTokens,
+}
+
+/// Whether the claim circuit breaker lets the next day start
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CircuitBreakerState {
+    /// Days start unless their claim is anomalous
+    Armed,
+    /// An anomalous claim stopped the day start; waits for `reset_circuit_breaker`
+    Tripped,
+    /// The authority accepted the pending claim; the next day start lets it through
+    /// and re-arms the breaker
+    Cleared,
+}
+
 /// Configuration for fee distribution policy
 #[account]
 pub struct DistributionPolicy {
     /// Vault this policy belongs to
     pub vault: Pubkey,
     
+    /// Authority allowed to update the policy
+    pub authority: Pubkey,
+    
     /// Quote token mint
     pub quote_mint: Pubkey,
     
@@ -23,9 +223,314 @@ pub struct DistributionPolicy {
     /// 0 means no cap
     pub daily_cap_lamports: u64,
     
-    /// Minimum payout per investor to avoid dust
+    /// Minimum payout per investor to avoid dust, in `min_payout_unit`
     pub min_payout_lamports: u64,
     
+    /// When the creator remainder is paid out
+    pub creator_payout_mode: CreatorPayoutMode,
+    
+    /// Receives payouts of investors whose ATA fails validation
+    /// None skips those investors instead
+    pub investor_fallback_wallet: Option<Pubkey>,
+    
+    /// Where locked amounts are read from during distribution
+    pub weight_source: WeightSource,
+    
+    /// Whether payouts are pushed to investors or pulled by them
+    pub distribution_mode: DistributionMode,
+    
+    /// Seconds between distributions; fees are still claimed daily
+    pub distribution_interval: i64,
+    
+    /// How the investor pool is split between investors
+    pub distribution_scheme: DistributionScheme,
+    
+    /// Per-investor amount under `EqualFixed`
+    pub fixed_payout_lamports: u64,
+    
+    /// `EqualFixed` behaviour when funds can't cover every investor
+    pub fixed_shortfall: FixedShortfall,
+    
+    /// Leave the creator payout and day completion to a separate `close_day`
+    /// call instead of the final investor page
+    pub defer_creator_close: bool,
+    
+    /// Seconds after the last claim beyond which the creator leg re-validates the
+    /// treasury against the claimed amounts (0 = never)
+    pub max_claim_age_secs: i64,
+    
+    /// Number of entries in the vault's `ShareOverrides`; while non-zero the
+    /// registry must be passed wherever weights are computed
+    pub share_override_count: u16,
+    
+    /// What happens to investor-pool amounts that couldn't be paid out
+    pub remainder_mode: RemainderMode,
+    
+    /// Decimals of the quote mint, recorded at initialization
+    pub quote_decimals: u8,
+    
+    /// Whether `min_payout_lamports` is raw lamports or whole tokens
+    pub min_payout_unit: MinPayoutUnit,
+    
+    /// Attach an SPL Memo to every pushed investor payout
+    pub attach_memo: bool,
+    
+    /// Reference prefixed to each payout memo (up to `MAX_MEMO_REFERENCE_LEN` bytes)
+    pub memo_reference: String,
+    
+    /// Signer allowed to deposit externally collected fees with `deposit_and_recognize`
+    /// None disables deposits
+    pub fee_source: Option<Pubkey>,
+    
+    /// Most a single investor may receive of a page's investor pool under `ProRata`,
+    /// in bps (0 = no cap); the excess goes to the page's other investors
+    pub max_single_investor_bps: u16,
+    
+    /// Skip DAMM fee claims (e.g. during a pool migration) while distribution continues
+    pub claim_paused: bool,
+    
+    /// Decimals of the token the investor streams vest, recorded at initialization
+    pub investor_token_decimals: u8,
+    
+    /// Decimals `total_investor_allocation` is expressed in; equal to
+    /// `investor_token_decimals` when Y0 is in raw units like the streams
+    pub allocation_decimals: u8,
+    
+    /// Carry a day's investor pool to the next day instead of paying it to the
+    /// creator when every investor fell below the minimum payout
+    pub defer_below_minimum: bool,
+    
+    /// Investor payouts are rounded down to a multiple of this (1 = no rounding);
+    /// the rounded-off part stays in the treasury for the creator remainder
+    pub payout_granularity_lamports: u64,
+    
+    /// Pay a catch-up to investors whose stream went from nothing to something locked
+    /// since the previous snapshot (`DailySnapshot` only)
+    pub backpay_enabled: bool,
+    
+    /// Catch-up paid to each newly active investor on their first active day, out of
+    /// the day's unlocked share (never the creator's guaranteed share)
+    pub backpay_lamports: u64,
+    
+    /// Order investors within a page are paid in
+    pub payout_priority: PayoutPriority,
+    
+    /// Yield-bearing position held by the treasury authority whose yield `claim_fees`
+    /// realizes into the treasury; None keeps the treasury a plain token account
+    pub yield_reserve: Option<Pubkey>,
+    
+    /// Limit `claim_my_payout` to once per distribution period per investor
+    pub pull_claim_cooldown: bool,
+    
+    /// Start the day from the treasury balance instead of failing when the DAMM
+    /// pool can't be claimed from
+    pub tolerate_claim_failure: bool,
+    
+    /// DAMM v2 pool whose status is checked before each claim; None skips the check
+    pub damm_pool: Option<Pubkey>,
+    
+    /// Mint investors are paid in, converted from quote per payout; None pays quote
+    pub payout_mint: Option<Pubkey>,
+    
+    /// DEX pool payout conversions swap through
+    pub swap_pool: Option<Pubkey>,
+    
+    /// Slippage bound on each conversion swap, in basis points of the pool's quote
+    pub max_swap_slippage_bps: u16,
+    
+    /// Skip push payouts into investor ATAs with a delegate set (pull claims fail)
+    pub reject_delegated_atas: bool,
+    
+    /// Smallest pending claim a day starts for; smaller claims are held and batched
+    /// into a later day (0 disables)
+    pub min_claim_to_distribute: u64,
+    
+    /// Weight of each new day's claim in the claim moving average, in basis points
+    pub claim_ema_alpha_bps: u16,
+    
+    /// Stop day starts whose claim exceeds this multiple of the claim moving average,
+    /// in basis points (30000 = 3x; 0 disables the circuit breaker)
+    pub circuit_breaker_multiple_bps: u32,
+    
+    /// Pay only the investors ranking in the top this many by locked amount across
+    /// the whole set (0 = everyone); needs day-level weights
+    pub top_n_investors: u32,
+    
+    /// Only pay investors who opted in with `opt_in`; pushed payouts of the others go
+    /// to the fallback wallet or stay in the treasury, pull accruals wait for the opt-in
+    pub require_opt_in: bool,
+    
+    /// Share of every claim, in basis points, the creator keeps whatever the locked
+    /// fraction or remainder mode (0 = no floor); caps the investor share at the rest
+    pub min_creator_share_bps: u16,
+    
+    /// Hold day starts falling on a Saturday or Sunday (UTC) so weekend claims are
+    /// distributed as one batch on Monday
+    pub skip_weekends: bool,
+    
+    /// Lifetime cap on investor distributions in quote lamports (0 = unlimited); the
+    /// day reaching it finalizes the vault
+    pub lifetime_cap_lamports: u64,
+    
+    /// Weight investors by the USD value of their locked tokens at `price_oracle`'s
+    /// price, pinned when a snapshot or weight table is started; needs day-level weights
+    pub weight_in_usd: bool,
+    
+    /// Price feed of the vested token, read for `weight_in_usd`
+    pub price_oracle: Pubkey,
+    
+    /// How the creator remainder is paid out
+    pub creator_deposit_mode: CreatorDepositMode,
+    
+    /// Realm of the DAO whose treasury receives `Governance` deposits
+    pub governance_realm: Pubkey,
+    
+    /// The DAO treasury's quote token account `Governance` deposits go to
+    pub governance_treasury: Pubkey,
+    
+    /// Re-read every pushed payout's destination after the transfer and fail the
+    /// page unless its balance rose by exactly the amount sent
+    pub confirm_deliveries: bool,
+    
+    /// The creator remainder is rounded down to a multiple of this many lamports; the
+    /// rest is held for the next day (1 = no rounding)
+    pub creator_granularity_lamports: u64,
+    
+    /// Largest growth of a day's recognized claim over the prior day's, in bps;
+    /// the excess stays pending for later days (`UNLIMITED_CLAIM_GROWTH_BPS` = off)
+    pub max_claim_growth_bps: u32,
+    
+    /// Set by `finalize_vault`; an archived vault accepts nothing but claims of what
+    /// it still owes and the recovery of its rent
+    pub finalized: bool,
+    
+    /// Locked amount snapshots weigh investors by; all but `Instantaneous` need
+    /// `DailySnapshot`
+    pub weighting_mode: WeightingMode,
+    
+    /// Emit the page events of every page; off, intermediate pages are silent and the
+    /// day close emits a `DayDistributionSummary` instead
+    pub verbose_events: bool,
+    
+    /// What happens to unrecognized treasury funds when a day completes
+    pub residual_mode: ResidualMode,
+    
+    /// Share of each DAMM claim sent to `protocol_fee_wallet` before the investor and
+    /// creator split; at most `MAX_PROTOCOL_FEE_BPS`, 0 takes no fee
+    pub protocol_fee_bps: u16,
+    
+    /// Owner of the quote account receiving the protocol fee
+    pub protocol_fee_wallet: Pubkey,
+    
+    /// Leave streams whose recipient is the creator wallet out of the investor
+    /// distribution, so the creator is paid only through the remainder
+    pub exclude_creator_from_investors: bool,
+    
+    /// Lamports a payout delivery or the treasury may fall short of what the books
+    /// expect before it counts as drift, on top of any expected transfer fee
+    pub reconciliation_tolerance_lamports: u64,
+    
+    /// Largest single transfer of a pushed investor payout (0 = no limit); larger
+    /// payouts are sent in installments
+    pub max_transfer_size: u64,
+    
+    /// Share of a live-stream page's index range that must hold the stream the weight
+    /// table lists at that index (0 = off)
+    pub page_quorum_bps: u16,
+    
+    /// Oldest oracle price, by its publish time, that USD weights may be pinned at
+    pub max_oracle_staleness_seconds: u32,
+    
+    /// Widest oracle confidence interval accepted for USD weights, relative to the price
+    pub max_oracle_confidence_bps: u16,
+    
+    /// Whether a treasury balance found before the vault's first claim is held back or
+    /// distributed with it
+    pub prefunded_balance_mode: PrefundedBalanceMode,
+    
+    /// Share of a `Blended` weight taken from the original allocation rather than the
+    /// locked amount (0 = pure locked, 10000 = pure allocation)
+    pub weight_blend_bps: u16,
+    
+    /// Most investors paid in a day across all its pages (0 = unlimited); the rest
+    /// of the day's investors are skipped and their shares go to the creator
+    pub max_investors_per_day: u32,
+    
+    /// External program pushed investor payouts are handed to instead of being
+    /// transferred to the investor's ATA; None uses the built-in transfer
+    pub distributor_program: Option<Pubkey>,
+    
+    /// Whether the final page adds the day's rounding dust to the last investor it
+    /// pays, instead of leaving it to the creator
+    pub dust_to_last_investor: bool,
+    
+    /// Share of every investor payout withheld and sent to the withholding wallet
+    /// (0 = none); distributions are still accounted gross
+    pub withholding_bps: u16,
+    
+    /// Receives the withheld share of investor payouts; required while
+    /// `withholding_bps` is non-zero
+    pub withholding_wallet: Option<Pubkey>,
+    
+    /// Whether `finalize_vault` pays the residual treasury balance to investors, by
+    /// the last day's weights, before anything goes to the creator
+    pub distribute_on_close: bool,
+    
+    /// Whether the dust a day leaves unpaid (`carry_over_dust` at its close) is held
+    /// back from the creator and added to the next day's available investor amount
+    pub persist_dust_across_days: bool,
+    
+    /// Whether `distribute_fees` needs the creator wallet's signature, making every
+    /// page creator-gated instead of permissionless
+    pub require_creator_signature: bool,
+    
+    /// Whether snapshots weigh an investor whose locked amount fell by the lower
+    /// amount at once or by the previous one for the period; `Grace` needs
+    /// `DailySnapshot`
+    pub withdrawal_handling: WithdrawalHandling,
+    
+    /// Most the creator receives of a day (in quote token lamports); the overflow is
+    /// carried into the next day's investor pool. 0 means no cap
+    pub creator_daily_cap_lamports: u64,
+    
+    /// Whether investors are paid strictly by global index, so any page chunking of
+    /// a day pays the same: a shortfall passes over only the investor it can't cover.
+    /// Requires `AccountOrder`, day-level weights and no `dust_to_last_investor`
+    pub deterministic_index_order: bool,
+    
+    /// Whether converted payouts are swapped or drawn from the payout treasury
+    pub payout_settlement: PayoutSettlement,
+    
+    /// Payout-mint units a `TreasuryDraw` settles per `SETTLEMENT_RATE_SCALE`
+    /// quote units of payout
+    pub settlement_rate: u64,
+    
+    /// How a `LiveStreams` day's pages share `daily_cap_lamports`
+    pub daily_cap_allocation: DailyCapAllocation,
+    
+    /// Most missing investor quote ATAs one pushed page creates, funded by the caller
+    /// (0 = never create; the payout is redirected or skipped as for any invalid ATA)
+    pub max_ata_creations_per_page: u8,
+    
+    /// Most rent, in lamports, the caller funds for the ATAs one page creates
+    pub max_ata_creation_funding: u64,
+    
+    /// Write each closing day's totals into its `DayRecord`, which the close then
+    /// requires, so `audit_history` can reconcile across days
+    pub record_day_history: bool,
+    
+    /// Number of `ShareOverrides` entries carrying a `min_payout_override`; while
+    /// non-zero the registry must be passed to every page, whatever the weight source
+    pub min_payout_override_count: u16,
+    
+    /// Number of `ShareOverrides` entries carrying a `payout_split`; while non-zero
+    /// the registry must be passed to every pushed page
+    pub payout_split_count: u16,
+    
+    /// Canonical bump of the treasury authority PDA, recorded at initialization; every
+    /// treasury signature uses it instead of re-deriving it
+    pub treasury_authority_bump: u8,
+    
     /// Bump for PDA derivation
     pub bump: u8,
 }
@@ -33,13 +538,215 @@ pub struct DistributionPolicy {
 impl DistributionPolicy {
     pub const LEN: usize = 8 + // discriminator
         32 + // vault
+        32 + // authority
         32 + // quote_mint
         32 + // creator_wallet
         8 +  // total_investor_allocation
         2 +  // investor_fee_share_bps
         8 +  // daily_cap_lamports
         8 +  // min_payout_lamports
+        1 +  // creator_payout_mode
+        33 + // investor_fallback_wallet
+        1 +  // weight_source
+        1 +  // distribution_mode
+        8 +  // distribution_interval
+        1 +  // distribution_scheme
+        8 +  // fixed_payout_lamports
+        1 +  // fixed_shortfall
+        1 +  // defer_creator_close
+        8 +  // max_claim_age_secs
+        2 +  // share_override_count
+        1 +  // remainder_mode
+        1 +  // quote_decimals
+        1 +  // min_payout_unit
+        1 +  // attach_memo
+        4 + crate::constants::MAX_MEMO_REFERENCE_LEN + // memo_reference
+        33 + // fee_source
+        2 +  // max_single_investor_bps
+        1 +  // claim_paused
+        1 +  // investor_token_decimals
+        1 +  // allocation_decimals
+        1 +  // defer_below_minimum
+        8 +  // payout_granularity_lamports
+        1 +  // backpay_enabled
+        8 +  // backpay_lamports
+        1 +  // payout_priority
+        33 + // yield_reserve
+        1 +  // pull_claim_cooldown
+        1 +  // tolerate_claim_failure
+        33 + // damm_pool
+        33 + // payout_mint
+        33 + // swap_pool
+        2 +  // max_swap_slippage_bps
+        1 +  // reject_delegated_atas
+        8 +  // min_claim_to_distribute
+        2 +  // claim_ema_alpha_bps
+        4 +  // circuit_breaker_multiple_bps
+        4 +  // top_n_investors
+        1 +  // require_opt_in
+        2 +  // min_creator_share_bps
+        1 +  // skip_weekends
+        8 +  // lifetime_cap_lamports
+        1 +  // weight_in_usd
+        32 + // price_oracle
+        1 +  // creator_deposit_mode
+        32 + // governance_realm
+        32 + // governance_treasury
+        1 +  // confirm_deliveries
+        8 +  // creator_granularity_lamports
+        4 +  // max_claim_growth_bps
+        1 +  // finalized
+        1 +  // weighting_mode
+        1 +  // verbose_events
+        1 +  // residual_mode
+        2 +  // protocol_fee_bps
+        32 + // protocol_fee_wallet
+        1 +  // exclude_creator_from_investors
+        8 +  // reconciliation_tolerance_lamports
+        8 +  // max_transfer_size
+        2 +  // page_quorum_bps
+        4 +  // max_oracle_staleness_seconds
+        2 +  // max_oracle_confidence_bps
+        1 +  // prefunded_balance_mode
+        2 +  // weight_blend_bps
+        4 +  // max_investors_per_day
+        33 + // distributor_program
+        1 + // dust_to_last_investor
+        2 + // withholding_bps
+        33 + // withholding_wallet
+        1 + // distribute_on_close
+        1 + // persist_dust_across_days
+        1 + // require_creator_signature
+        1 + // withdrawal_handling
+        8 + // creator_daily_cap_lamports
+        1 + // deterministic_index_order
+        1 + // payout_settlement
+        8 + // settlement_rate
+        1 + // daily_cap_allocation
+        1 +  // max_ata_creations_per_page
+        8 +  // max_ata_creation_funding
+        1 +  // record_day_history
+        2 +  // min_payout_override_count
+        2 +  // payout_split_count
+        1 +  // treasury_authority_bump
         1;   // bump
+    
+    /// Whether pages carry each investor's opt-in PDA: pushed payouts under
+    /// `require_opt_in` (pull claims check the opt-in themselves)
+    pub fn requires_opt_in_accounts(&self) -> bool {
+        self.require_opt_in && self.distribution_mode == DistributionMode::Push
+    }
+    
+    /// Y0 in the investor token's raw units, the unit stream locked amounts use
+    pub fn allocation_base_units(&self) -> Result<u64> {
+        let scale = self
+            .investor_token_decimals
+            .checked_sub(self.allocation_decimals)
+            .ok_or(crate::errors::FeeDistributorError::AllocationUnitMismatch)?;
+        crate::math::whole_tokens_to_lamports(self.total_investor_allocation, scale)
+    }
+    
+    /// Locked fraction of Y0 in bps, after normalizing Y0 to raw units. A locked total
+    /// above Y0 means the two were recorded in different units and is rejected rather
+    /// than clamped to 100%.
+    pub fn locked_fraction(&self, total_locked: u64) -> Result<u64> {
+        let y0 = self.allocation_base_units()?;
+        require!(
+            y0 == 0 || total_locked <= y0,
+            crate::errors::FeeDistributorError::AllocationUnitMismatch
+        );
+        crate::math::calculate_locked_fraction(total_locked, y0)
+    }
+    
+    /// Whether a day start at `current_ts` waits for the next business day
+    pub fn holds_for_weekend(&self, current_ts: i64) -> bool {
+        self.skip_weekends && crate::math::is_weekend(current_ts)
+    }
+    
+    /// Whether the accounts a cranker passed are the DAO treasury `Governance` deposits
+    /// go to: the policy's realm, owned by the governance program being called, and
+    /// its quote treasury
+    pub fn is_governance_deposit_target(
+        &self,
+        governance_program: &Pubkey,
+        realm: &Pubkey,
+        realm_owner: &Pubkey,
+        treasury: &Pubkey,
+        treasury_mint: &Pubkey,
+    ) -> bool {
+        *realm == self.governance_realm
+            && realm_owner == governance_program
+            && *treasury == self.governance_treasury
+            && *treasury_mint == self.quote_mint
+    }
+    
+    /// Whether `escrow` is the policy's distributor escrow: a quote account owned by the
+    /// distributor's escrow authority PDA, passed as `escrow_authority`
+    pub fn is_distributor_escrow(
+        &self,
+        distributor_program: &Pubkey,
+        escrow_owner: &Pubkey,
+        escrow_mint: &Pubkey,
+        escrow_authority: &Pubkey,
+    ) -> bool {
+        self.distributor_program == Some(*distributor_program)
+            && *escrow_mint == self.quote_mint
+            && escrow_owner == escrow_authority
+            && *escrow_authority
+                == crate::distributor_adapter::escrow_authority(distributor_program, &self.vault)
+    }
+    
+    /// Investor share of the claim in bps; 100% when the remainder goes to investors,
+    /// and never more than `min_creator_share_bps` leaves the creator
+    pub fn effective_investor_share_bps(&self) -> u16 {
+        let share = match self.remainder_mode {
+            RemainderMode::ToInvestors => crate::constants::BASIS_POINTS_DIVISOR as u16,
+            RemainderMode::Creator | RemainderMode::Burn => self.investor_fee_share_bps,
+        };
+        let creator_floor = std::cmp::min(self.min_creator_share_bps, crate::constants::BASIS_POINTS_DIVISOR as u16);
+        
+        std::cmp::min(share, crate::constants::BASIS_POINTS_DIVISOR as u16 - creator_floor)
+    }
+    
+    /// Locked fraction that scales the investor pool. Under `ToInvestors` it is 100%;
+    /// locked amounts then only weight investors against each other.
+    pub fn pool_locked_fraction(&self, total_locked: u64) -> Result<u64> {
+        let f_locked = self.locked_fraction(total_locked)?;
+        
+        Ok(match self.remainder_mode {
+            RemainderMode::ToInvestors => crate::constants::BASIS_POINTS_DIVISOR,
+            RemainderMode::Creator | RemainderMode::Burn => f_locked,
+        })
+    }
+    
+    /// Memo attached to an investor's payout: `<memo_reference>:<index>`, or just the
+    /// index when no reference is set
+    pub fn payout_memo(&self, investor_index: u32) -> String {
+        if self.memo_reference.is_empty() {
+            investor_index.to_string()
+        } else {
+            format!("{}:{}", self.memo_reference, investor_index)
+        }
+    }
+    
+    /// The minimum payout in quote lamports, converting from whole tokens if configured
+    pub fn min_payout_threshold(&self) -> Result<u64> {
+        match self.min_payout_unit {
+            MinPayoutUnit::Lamports => Ok(self.min_payout_lamports),
+            MinPayoutUnit::WholeTokens => {
+                crate::math::whole_tokens_to_lamports(self.min_payout_lamports, self.quote_decimals)
+            }
+        }
+    }
+    
+    /// Reject a daily cap or minimum payout that doesn't make sense for `quote_decimals`
+    pub fn check_quote_amounts(&self) -> Result<()> {
+        crate::math::check_quote_amounts(
+            self.daily_cap_lamports,
+            self.min_payout_threshold()?,
+            self.quote_decimals,
+        )
+    }
 }
 
 /// Tracks the state of ongoing distribution across days and pages
@@ -72,6 +779,116 @@ pub struct DistributionProgress {
     /// Total investors in the distribution set
     pub total_investors: u32,
     
+    /// Next investor index to be read into the locked snapshot
+    pub snapshot_cursor: u32,
+    
+    /// Sum of locked amounts across the snapshot
+    pub total_locked_today: u64,
+    
+    /// Investors with a non-zero locked amount in the snapshot
+    pub active_investors_today: u32,
+    
+    /// Sum of share-multiplied weights across the snapshot
+    pub total_weight_today: u64,
+    
+    /// Timestamp the current snapshot was started at
+    pub snapshot_ts: i64,
+    
+    /// Pull-mode payouts allocated to investors but not yet claimed
+    pub pull_outstanding: u64,
+    
+    /// Timestamp of the last fee claim
+    pub last_claim_ts: i64,
+    
+    /// Fees claimed since the current day's distribution started, awaiting the next one
+    pub current_period_claimed: u64,
+    
+    /// Investor pool computed for the current day (sum of page pools in live-stream mode)
+    pub current_day_investor_pool: u64,
+    
+    /// Undistributable investor residual burned in current day
+    pub current_day_burned: u64,
+    
+    /// Investor pool held back at the close of an all-below-minimum day, awaiting the next day
+    pub deferred_investor_pool: u64,
+    
+    /// Deferred investor pool folded into the current day
+    pub current_day_deferred: u64,
+    
+    /// Catch-up paid to newly active investors in current day
+    pub current_day_backpay: u64,
+    
+    /// Quote value put into the yield reserve; only value above it is realized
+    pub yield_principal: u64,
+    
+    /// Yield realized from the reserve over the vault's lifetime
+    pub total_yield_realized: u64,
+    
+    /// Number of the most recently started day (0 before the first distribution)
+    pub latest_epoch: u64,
+    
+    /// The authority asked for the next day to start below `min_claim_to_distribute`
+    pub distribution_forced: bool,
+    
+    /// Investors paid or allocated a payout so far in the current day
+    pub current_day_investors_paid: u32,
+    
+    /// Moving average of the claims days started with (see `claim_ema_alpha_bps`)
+    pub claim_ema: u64,
+    
+    /// Claims folded into `claim_ema` so far
+    pub claim_ema_days: u32,
+    
+    /// Whether the claim circuit breaker lets the next day start
+    pub circuit_breaker: CircuitBreakerState,
+    
+    /// Locked amount of the lowest-ranked investor still paid under `top_n_investors`,
+    /// ranked at the current day's start
+    pub top_n_cutoff_locked: u64,
+    
+    /// Index of that investor; equal locked amounts rank by the lower index
+    pub top_n_cutoff_index: u32,
+    
+    /// Investor distributions of every completed day, counted against the lifetime cap
+    pub lifetime_distributed: u64,
+    
+    /// The lifetime cap was reached; no further day ever starts
+    pub vault_finalized: bool,
+    
+    /// USD price (`USD_PRICE_SCALE` fixed point) the upcoming day's snapshot or weight
+    /// table is weighted at; 0 when it weighs locked tokens
+    pub usd_weight_price: u64,
+    
+    /// Claim recognized by the last day that started with one (0 before the first)
+    pub prior_day_claimed: u64,
+    
+    /// Bumped by `set_investor_count` whenever investors are added or pruned
+    pub set_version: u64,
+    
+    /// `set_version` the current snapshot was started under
+    pub snapshot_set_version: u64,
+    
+    /// Treasury balance held back as pre-funding under `PrefundedBalanceMode::Reserve`
+    pub prefunded_reserve: u64,
+    
+    /// Whether the vault has recognized treasury funds, by a claim or without one;
+    /// only balance found before then counts as pre-funding
+    pub prefunding_settled: bool,
+    
+    /// Residual balance `finalize_vault` is distributing to investors under
+    /// `distribute_on_close`
+    pub close_pool: u64,
+    
+    /// Next investor index of the close distribution
+    pub close_cursor: u32,
+    
+    /// Paid out of `close_pool` so far
+    pub close_distributed: u64,
+    
+    /// Dust the last closed day held back for the next one under
+    /// `persist_dust_across_days`; part of the open day's funds once it starts
+    pub persisted_dust: u64,
+    
     /// Bump for PDA derivation
     pub bump: u8,
 }
@@ -87,23 +904,1061 @@ impl DistributionProgress {
         4 +  // pagination_cursor
         1 +  // day_completed
         4 +  // total_investors
+        4 +  // snapshot_cursor
+        8 +  // total_locked_today
+        4 +  // active_investors_today
+        8 +  // total_weight_today
+        8 +  // snapshot_ts
+        8 +  // pull_outstanding
+        8 +  // last_claim_ts
+        8 +  // current_period_claimed
+        8 +  // current_day_investor_pool
+        8 +  // current_day_burned
+        8 +  // deferred_investor_pool
+        8 +  // current_day_deferred
+        8 +  // current_day_backpay
+        8 +  // yield_principal
+        8 +  // total_yield_realized
+        8 +  // latest_epoch
+        1 +  // distribution_forced
+        4 +  // current_day_investors_paid
+        8 +  // claim_ema
+        4 +  // claim_ema_days
+        1 +  // circuit_breaker
+        8 +  // top_n_cutoff_locked
+        4 +  // top_n_cutoff_index
+        8 +  // lifetime_distributed
+        1 +  // vault_finalized
+        8 +  // usd_weight_price
+        8 +  // prior_day_claimed
+        8 +  // set_version
+        8 +  // snapshot_set_version
+        8 +  // prefunded_reserve
+        1 +  // prefunding_settled
+        8 +  // close_pool
+        4 +  // close_cursor
+        8 +  // close_distributed
+        8 +  // persisted_dust
         1;   // bump
     
-    /// Check if a new day has started
-    pub fn is_new_day(&self, current_ts: i64) -> bool {
-        current_ts >= self.last_distribution_ts + crate::constants::SECONDS_PER_DAY
+    /// Check if a new distribution day has started (`distribution_interval` since the last one)
+    pub fn is_new_day(&self, current_ts: i64, distribution_interval: i64) -> bool {
+        current_ts >= self.last_distribution_ts.saturating_add(distribution_interval)
+    }
+    
+    /// Check if the daily fee claim is due
+    pub fn is_claim_due(&self, current_ts: i64) -> bool {
+        current_ts >= self.last_claim_ts.saturating_add(crate::constants::SECONDS_PER_DAY)
+    }
+    
+    /// Add a fee claim to the period awaiting distribution
+    pub fn record_claim(&mut self, amount: u64, current_ts: i64) -> Result<()> {
+        self.last_claim_ts = current_ts;
+        self.recognize_funds(amount)
+    }
+    
+    /// Add funds already in the treasury to the period awaiting distribution,
+    /// without counting as a claim
+    pub fn recognize_funds(&mut self, amount: u64) -> Result<()> {
+        self.current_period_claimed = self
+            .current_period_claimed
+            .checked_add(amount)
+            .ok_or(crate::errors::FeeDistributorError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Treasury funds already accounted for: pull-mode liabilities, claims awaiting
+    /// distribution, the undistributed part of an open day and the pre-funded reserve
+    pub fn recognized_treasury_balance(&self) -> u64 {
+        let open_day_remaining = if self.day_completed {
+            self.persisted_dust
+        } else {
+            self.creator_remainder()
+        };
+        
+        self.pull_outstanding
+            .saturating_add(self.current_period_claimed)
+            .saturating_add(self.deferred_investor_pool)
+            .saturating_add(open_day_remaining)
+            .saturating_add(self.prefunded_reserve)
     }
     
     /// Reset for a new day
     pub fn start_new_day(&mut self, current_ts: i64) {
         self.last_distribution_ts = current_ts;
+        self.latest_epoch = self.latest_epoch.saturating_add(1);
         self.current_day_claimed = 0;
         self.current_day_distributed_investors = 0;
         self.current_day_distributed_creator = 0;
-        self.carry_over_dust = 0;
+        self.current_day_investor_pool = 0;
+        self.current_day_burned = 0;
+        self.current_day_backpay = 0;
+        self.current_day_investors_paid = 0;
+        self.current_day_deferred = self.deferred_investor_pool;
+        self.deferred_investor_pool = 0;
+        // Zero unless the closed day held its dust back under `persist_dust_across_days`
+        self.carry_over_dust = self.persisted_dust;
         self.pagination_cursor = 0;
         self.day_completed = false;
     }
+    
+    /// Drop the claim awaiting distribution, and the claim of a day that started without
+    /// paying anyone, returning the amount dropped. The funds stay in the treasury
+    /// unrecognized until the next claim picks them up.
+    pub fn cancel_pending_claim(&mut self) -> u64 {
+        let open_day_claimed = if self.day_completed {
+            0
+        } else {
+            std::mem::take(&mut self.current_day_claimed)
+        };
+        
+        open_day_claimed.saturating_add(std::mem::take(&mut self.current_period_claimed))
+    }
+    
+    /// Put every field back to its state right after `initialize`, for
+    /// `force_reset_progress`. Anything held for a day, claimed or deferred is released
+    /// to the treasury's unrecognized balance. Only these are kept:
+    /// - `total_investors` and `set_version`, which weight tables and snapshots are
+    ///   checked against;
+    /// - `pull_outstanding`, `yield_principal`, `prefunded_reserve` and
+    ///   `prefunding_settled`, which still hold claims on the treasury;
+    /// - `latest_epoch`, so a weight table committed for an old day can't pass for the
+    ///   next one;
+    /// - `lifetime_distributed` and `vault_finalized`, so a finalized vault stays
+    ///   finalized;
+    /// - `close_pool`, `close_cursor` and `close_distributed`, so a close distribution
+    ///   in progress resumes where it stopped.
+    pub fn force_reset(&mut self) {
+        *self = DistributionProgress {
+            vault: self.vault,
+            last_distribution_ts: 0,
+            current_day_claimed: 0,
+            current_day_distributed_investors: 0,
+            current_day_distributed_creator: 0,
+            carry_over_dust: 0,
+            pagination_cursor: 0,
+            day_completed: false,
+            total_investors: self.total_investors,
+            snapshot_cursor: 0,
+            total_locked_today: 0,
+            active_investors_today: 0,
+            total_weight_today: 0,
+            snapshot_ts: 0,
+            pull_outstanding: self.pull_outstanding,
+            last_claim_ts: 0,
+            current_period_claimed: 0,
+            current_day_investor_pool: 0,
+            current_day_burned: 0,
+            deferred_investor_pool: 0,
+            current_day_deferred: 0,
+            current_day_backpay: 0,
+            yield_principal: self.yield_principal,
+            total_yield_realized: 0,
+            latest_epoch: self.latest_epoch,
+            distribution_forced: false,
+            current_day_investors_paid: 0,
+            claim_ema: 0,
+            claim_ema_days: 0,
+            circuit_breaker: CircuitBreakerState::Armed,
+            top_n_cutoff_locked: 0,
+            top_n_cutoff_index: u32::MAX,
+            lifetime_distributed: self.lifetime_distributed,
+            vault_finalized: self.vault_finalized,
+            usd_weight_price: 0,
+            prior_day_claimed: 0,
+            set_version: self.set_version,
+            snapshot_set_version: 0,
+            prefunded_reserve: self.prefunded_reserve,
+            prefunding_settled: self.prefunding_settled,
+            close_pool: self.close_pool,
+            close_cursor: self.close_cursor,
+            close_distributed: self.close_distributed,
+            persisted_dust: 0,
+            bump: self.bump,
+        };
+    }
+    
+    /// Whether some pages of the current day have run but the day is not closed
+    pub fn is_mid_day(&self) -> bool {
+        self.pagination_cursor > 0 && !self.day_completed
+    }
+    
+    /// Whether every investor page has run but the day still awaits `close_day`
+    pub fn is_awaiting_close(&self) -> bool {
+        self.pagination_cursor >= self.total_investors && !self.day_completed
+    }
+    
+    /// Investors the current day may still pay under `max_investors_per_day`; `None`
+    /// when unlimited
+    pub fn investor_slots(&self, max_investors_per_day: u32) -> Option<u32> {
+        (max_investors_per_day > 0)
+            .then(|| max_investors_per_day.saturating_sub(self.current_day_investors_paid))
+    }
+    
+    /// Whether the last fee claim is older than `max_claim_age_secs` (0 = never stale)
+    pub fn is_claim_stale(&self, current_ts: i64, max_claim_age_secs: i64) -> bool {
+        max_claim_age_secs > 0 && current_ts > self.last_claim_ts.saturating_add(max_claim_age_secs)
+    }
+    
+    /// Outstanding amount for the day: claimed (plus any pool deferred into it) - paid to
+    /// investors (including catch-up) - paid to creator - burned - pool deferred to the next day
+    pub fn creator_remainder(&self) -> u64 {
+        self.current_day_claimed
+            .saturating_add(self.current_day_deferred)
+            .saturating_add(self.persisted_dust)
+            .saturating_sub(self.deferred_investor_pool)
+            .saturating_sub(self.current_day_distributed_investors)
+            .saturating_sub(self.current_day_backpay)
+            .saturating_sub(self.current_day_distributed_creator)
+            .saturating_sub(self.current_day_burned)
+    }
+    
+    /// What the running day brought in from the day before: the investor pool it
+    /// deferred and the dust it held. Only meaningful until the day's close holds new
+    /// dust.
+    pub fn day_carried_in(&self) -> u64 {
+        self.current_day_deferred.saturating_add(self.persisted_dust)
+    }
+    
+    /// What the day can still pay as catch-up: the creator remainder less the unpaid
+    /// part of the day-level investor pool and the creator's unpaid guaranteed share
+    /// (`claimed * (10000 - investor_fee_share_bps)`)
+    pub fn backpay_headroom(&self, investor_fee_share_bps: u16, day_investor_pool: u64) -> Result<u64> {
+        let investor_max =
+            crate::math::apply_bps(self.current_day_claimed, investor_fee_share_bps as u64)?;
+        let creator_guaranteed = self.current_day_claimed.saturating_sub(investor_max);
+        
+        Ok(self
+            .creator_remainder()
+            .saturating_sub(day_investor_pool.saturating_sub(self.current_day_distributed_investors))
+            .saturating_sub(creator_guaranteed.saturating_sub(self.current_day_distributed_creator)))
+    }
+    
+    /// Part of the day's investor pool that wasn't paid out (dust, skipped payouts)
+    pub fn undistributable(&self) -> u64 {
+        self.current_day_investor_pool
+            .saturating_sub(self.current_day_distributed_investors)
+            .saturating_sub(self.deferred_investor_pool)
+            .saturating_sub(self.persisted_dust)
+    }
+    
+    /// Day the stored snapshot belongs to: the upcoming day for one taken since the
+    /// last distribution started, otherwise the day that consumed it. `None` when no
+    /// snapshot is stored.
+    pub fn snapshot_epoch(&self) -> Option<u64> {
+        if self.snapshot_ts == 0 {
+            None
+        } else if self.snapshot_ts > self.last_distribution_ts {
+            Some(self.latest_epoch.saturating_add(1))
+        } else {
+            Some(self.latest_epoch)
+        }
+    }
+    
+    /// Whether the pending claim is too small to start a day for, and should be held
+    pub fn holds_claim(&self, min_claim_to_distribute: u64) -> bool {
+        self.current_period_claimed < min_claim_to_distribute && !self.distribution_forced
+    }
+    
+    /// Lifetime cap left for the current day (u64::MAX when uncapped)
+    pub fn lifetime_headroom(&self, lifetime_cap_lamports: u64) -> u64 {
+        crate::math::cap_headroom(lifetime_cap_lamports, self.lifetime_distributed)
+    }
+    
+    /// Count the closing day's investor distributions against the lifetime cap,
+    /// finalizing the vault once they reach it. Returns whether this day finalized it.
+    pub fn record_lifetime_distribution(&mut self, lifetime_cap_lamports: u64) -> bool {
+        self.lifetime_distributed = self
+            .lifetime_distributed
+            .saturating_add(self.current_day_distributed_investors);
+        
+        let reached = lifetime_cap_lamports > 0 && self.lifetime_distributed >= lifetime_cap_lamports;
+        let finalized_now = reached && !self.vault_finalized;
+        self.vault_finalized |= reached;
+        finalized_now
+    }
+    
+    /// Whether investor `index` with `locked` ranks within the day's top-N cutoff
+    pub fn ranks_in_top_n(&self, locked: u64, index: usize) -> bool {
+        locked > self.top_n_cutoff_locked
+            || (locked == self.top_n_cutoff_locked && index <= self.top_n_cutoff_index as usize)
+    }
+    
+    /// The part of `period_claimed` a new day recognizes: at most `prior_day_claimed`
+    /// grown by `max_claim_growth_bps`. With no prior claim (before the first day that
+    /// had one) the whole claim is recognized.
+    pub fn growth_capped_claim(&self, period_claimed: u64, max_claim_growth_bps: u32) -> u64 {
+        if max_claim_growth_bps == crate::constants::UNLIMITED_CLAIM_GROWTH_BPS || self.prior_day_claimed == 0 {
+            return period_claimed;
+        }
+        
+        let cap = self.prior_day_claimed as u128
+            * (crate::constants::BASIS_POINTS_DIVISOR as u128 + max_claim_growth_bps as u128)
+            / crate::constants::BASIS_POINTS_DIVISOR as u128;
+        std::cmp::min(period_claimed as u128, cap) as u64
+    }
+    
+    /// Run the claim a day is about to start with through the circuit breaker. An
+    /// anomalous claim trips it and returns `false`; any other claim (or one the
+    /// authority cleared) re-arms it, is folded into `claim_ema` and returns `true`.
+    pub fn admit_day_claim(&mut self, claim: u64, claim_ema_alpha_bps: u16, multiple_bps: u32) -> bool {
+        let cleared = self.circuit_breaker == CircuitBreakerState::Cleared;
+        if !cleared && crate::math::is_anomalous_claim(claim, self.claim_ema, self.claim_ema_days, multiple_bps) {
+            self.circuit_breaker = CircuitBreakerState::Tripped;
+            return false;
+        }
+        self.circuit_breaker = CircuitBreakerState::Armed;
+        
+        // A day without a claim (claims paused, nothing new) says nothing about the pool
+        if claim > 0 {
+            self.claim_ema =
+                crate::math::update_claim_ema(self.claim_ema, self.claim_ema_days, claim, claim_ema_alpha_bps);
+            self.claim_ema_days = self.claim_ema_days.saturating_add(1);
+        }
+        true
+    }
+    
+    /// Whether a snapshot, complete or not, has been started since the last
+    /// distribution, i.e. for the upcoming day
+    pub fn has_upcoming_snapshot(&self) -> bool {
+        self.snapshot_ts > self.last_distribution_ts
+    }
+    
+    /// Whether the upcoming day's snapshot was started before investors were last
+    /// added or pruned, so its totals and entries describe another set
+    pub fn is_snapshot_stale(&self) -> bool {
+        self.has_upcoming_snapshot() && self.snapshot_set_version != self.set_version
+    }
+    
+    /// Whether a complete snapshot has been taken since the last distribution started
+    pub fn is_snapshot_ready(&self) -> bool {
+        self.has_upcoming_snapshot() && self.snapshot_cursor == self.total_investors
+    }
+    
+    /// Whether `finalize_vault` still has investors to pay the close pool to
+    pub fn close_distribution_pending(&self) -> bool {
+        self.close_pool > 0 && self.close_cursor < self.total_investors
+    }
+}
+
+/// Per-investor locked amounts frozen for one day's distribution
+///
+/// Only the header is (de)serialized by Anchor. Entries are stored raw after it,
+/// one `SnapshotEntry` per investor index, so a page only touches its own entries
+/// regardless of how large the investor set is.
+#[account]
+pub struct LockedSnapshot {
+    /// Vault this snapshot belongs to
+    pub vault: Pubkey,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl LockedSnapshot {
+    pub const HEADER_LEN: usize = 8 + // discriminator
+        32 + // vault
+        1;   // bump
+    
+    /// Account size holding `entries` snapshot entries
+    pub fn space(entries: usize) -> usize {
+        Self::HEADER_LEN + entries * SnapshotEntry::LEN
+    }
+    
+    /// Read the entry for investor `index`
+    pub fn read_entry(info: &AccountInfo, index: usize) -> Result<SnapshotEntry> {
+        let offset = Self::space(index);
+        let data = info.try_borrow_data()?;
+        let bytes = data
+            .get(offset..offset + SnapshotEntry::LEN)
+            .ok_or(crate::errors::FeeDistributorError::SnapshotIncomplete)?;
+        
+        Ok(SnapshotEntry::try_from_slice(bytes)?)
+    }
+    
+    /// Whether one of the first `entries` entries was read from `stream`. Only the
+    /// stream key of each entry is compared, straight from the raw bytes.
+    pub fn holds_stream(info: &AccountInfo, entries: usize, stream: &Pubkey) -> Result<bool> {
+        let data = info.try_borrow_data()?;
+        let bytes = data
+            .get(Self::HEADER_LEN..Self::space(entries))
+            .ok_or(crate::errors::FeeDistributorError::SnapshotIncomplete)?;
+        
+        Ok(bytes
+            .chunks_exact(SnapshotEntry::LEN)
+            .any(|entry| entry[..32] == stream.as_ref()[..]))
+    }
+    
+    /// Write the entry for investor `index`
+    pub fn write_entry(info: &AccountInfo, index: usize, entry: &SnapshotEntry) -> Result<()> {
+        let offset = Self::space(index);
+        let mut data = info.try_borrow_mut_data()?;
+        let mut bytes = data
+            .get_mut(offset..offset + SnapshotEntry::LEN)
+            .ok_or(crate::errors::FeeDistributorError::InvalidPaginationCursor)?;
+        
+        entry.serialize(&mut bytes)?;
+        Ok(())
+    }
+}
+
+/// A single investor's snapshotted stream and locked amount
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SnapshotEntry {
+    /// Streamflow stream the amount was read from
+    pub stream: Pubkey,
+    /// Locked amount the day weighs the investor by: `stream_locked`, or under
+    /// `TimeWeighted` its average with `period_start_locked`
+    pub locked: u64,
+    /// Locked amount, under `Blended` its blend with the allocation, scaled by the
+    /// investor's share multiplier
+    pub weight: u64,
+    /// Nothing was locked for this stream at the previous snapshot, something is now
+    pub newly_active: bool,
+    /// Locked amount read from the stream at snapshot time; the start of the next
+    /// snapshot's period
+    pub stream_locked: u64,
+    /// Locked amount at the previous snapshot, or `stream_locked` without one
+    pub period_start_locked: u64,
+    /// Timestamp of the snapshot that wrote the entry
+    pub snapshot_ts: i64,
+}
+
+impl SnapshotEntry {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 8 + 8 + 8;
+    
+    /// Whether `stream` went from zero to `locked > 0` since this (previous) entry.
+    /// An entry for another stream, or never written, says nothing about the history.
+    pub fn became_active(&self, stream: &Pubkey, locked: u64) -> bool {
+        self.stream == *stream && self.stream_locked == 0 && locked > 0
+    }
+    
+    /// Locked amount `stream` had at the start of the period a snapshot for the
+    /// upcoming day covers, given this (previous) entry and the amount locked now.
+    /// An entry already written for the upcoming day, by a snapshot that was discarded
+    /// and retaken, keeps the start it found.
+    pub fn period_start_for(&self, stream: &Pubkey, locked: u64, last_distribution_ts: i64) -> u64 {
+        if self.stream != *stream {
+            locked
+        } else if self.snapshot_ts > last_distribution_ts {
+            self.period_start_locked
+        } else {
+            self.stream_locked
+        }
+    }
+}
+
+/// Per-investor weights the authority committed for one day's distribution
+///
+/// Laid out like `LockedSnapshot`: only the header is (de)serialized by Anchor and one
+/// `WeightEntry` per investor index is stored raw after it. Entries are read from the
+/// streams as they are written, so `distribute_fees` never parses a stream.
+#[account]
+pub struct WeightTable {
+    /// Vault this table belongs to
+    pub vault: Pubkey,
+    
+    /// Day the weights are committed for (`progress.latest_epoch` once it has started)
+    pub day_epoch: u64,
+    
+    /// Investors written for `day_epoch`; later entries are left over from an older day
+    pub write_cursor: u32,
+    
+    /// Sum of the locked amounts written
+    pub total_locked: u64,
+    
+    /// Sum of the share-multiplied weights written
+    pub total_weight: u64,
+    
+    /// Written investors with a non-zero locked amount
+    pub active_investors: u32,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl WeightTable {
+    pub const HEADER_LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // day_epoch
+        4 +  // write_cursor
+        8 +  // total_locked
+        8 +  // total_weight
+        4 +  // active_investors
+        1;   // bump
+    
+    /// Account size holding `entries` weight entries
+    pub fn space(entries: usize) -> usize {
+        Self::HEADER_LEN + entries * WeightEntry::LEN
+    }
+    
+    /// Whether every one of `total_investors` has been written for `day_epoch`
+    pub fn is_committed_for(&self, day_epoch: u64, total_investors: u32) -> bool {
+        self.day_epoch == day_epoch && self.write_cursor == total_investors
+    }
+    
+    /// Index the next write for `day_epoch` starts at: 0 once the table is stale
+    pub fn next_write_index(&self, day_epoch: u64) -> u32 {
+        if self.day_epoch == day_epoch {
+            self.write_cursor
+        } else {
+            0
+        }
+    }
+    
+    /// Start the table over for `day_epoch`
+    pub fn reset(&mut self, day_epoch: u64) {
+        self.day_epoch = day_epoch;
+        self.write_cursor = 0;
+        self.total_locked = 0;
+        self.total_weight = 0;
+        self.active_investors = 0;
+    }
+    
+    /// Read the entry for investor `index`
+    pub fn read_entry(info: &AccountInfo, index: usize) -> Result<WeightEntry> {
+        let offset = Self::space(index);
+        let data = info.try_borrow_data()?;
+        let bytes = data
+            .get(offset..offset + WeightEntry::LEN)
+            .ok_or(crate::errors::FeeDistributorError::WeightTableStale)?;
+        
+        Ok(WeightEntry::try_from_slice(bytes)?)
+    }
+    
+    /// Write the entry for investor `index`
+    pub fn write_entry(info: &AccountInfo, index: usize, entry: &WeightEntry) -> Result<()> {
+        let offset = Self::space(index);
+        let mut data = info.try_borrow_mut_data()?;
+        let mut bytes = data
+            .get_mut(offset..offset + WeightEntry::LEN)
+            .ok_or(crate::errors::FeeDistributorError::InvalidPaginationCursor)?;
+        
+        entry.serialize(&mut bytes)?;
+        Ok(())
+    }
+}
+
+/// A single investor's committed stream, locked amount and weight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct WeightEntry {
+    /// Streamflow stream the amount was read from
+    pub stream: Pubkey,
+    /// Locked amount when the entry was written
+    pub locked: u64,
+    /// Locked amount scaled by the investor's share multiplier
+    pub weight: u64,
+}
+
+impl WeightEntry {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+/// Authority-managed share multipliers and payout minimums for investors with
+/// negotiated terms
+#[account]
+pub struct ShareOverrides {
+    /// Vault these overrides belong to
+    pub vault: Pubkey,
+    
+    /// Streams with a multiplier other than 1x or their own minimum payout
+    pub entries: Vec<ShareOverride>,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl ShareOverrides {
+    /// Account size holding `entries` overrides
+    pub fn space(entries: usize) -> usize {
+        8 +  // discriminator
+        32 + // vault
+        4 + entries * ShareOverride::LEN + // entries
+        1    // bump
+    }
+    
+    /// The entry for `stream`, if it has negotiated terms
+    pub fn entry(&self, stream: &Pubkey) -> Option<&ShareOverride> {
+        self.entries.iter().find(|entry| entry.stream == *stream)
+    }
+    
+    /// Multiplier applied to `stream`'s weight (1x when it has no override)
+    pub fn multiplier_bps(&self, stream: &Pubkey) -> u16 {
+        self.entry(stream)
+            .map_or(crate::constants::BASIS_POINTS_DIVISOR as u16, |entry| entry.multiplier_bps)
+    }
+    
+    /// Minimum payout `stream` is held to instead of the policy's, if it has one
+    pub fn min_payout_override(&self, stream: &Pubkey) -> Option<u64> {
+        self.entry(stream).and_then(|entry| entry.min_payout_override)
+    }
+    
+    /// Number of entries carrying a `min_payout_override`
+    pub fn min_payout_override_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.min_payout_override.is_some())
+            .count()
+    }
+    
+    /// Second destination `stream`'s pushed payouts are split with, if any
+    pub fn payout_split(&self, stream: &Pubkey) -> Option<PayoutSplit> {
+        self.entry(stream).and_then(|entry| entry.payout_split)
+    }
+    
+    /// Number of entries carrying a `payout_split`
+    pub fn payout_split_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.payout_split.is_some())
+            .count()
+    }
+    
+    /// Number of entries once `stream` is set to `multiplier_bps` and
+    /// `min_payout_override`, keeping its payout split (1x without a minimum or a
+    /// split removes it)
+    pub fn len_after(&self, stream: &Pubkey, multiplier_bps: u16, min_payout_override: Option<u64>) -> usize {
+        let keep = ShareOverride::is_kept(multiplier_bps, min_payout_override, self.payout_split(stream));
+        self.len_kept(stream, keep)
+    }
+    
+    /// Number of entries once `stream`'s payout split is set to `payout_split`, its
+    /// other terms unchanged
+    pub fn len_after_split(&self, stream: &Pubkey, payout_split: Option<PayoutSplit>) -> usize {
+        let keep = ShareOverride::is_kept(
+            self.multiplier_bps(stream),
+            self.min_payout_override(stream),
+            payout_split,
+        );
+        self.len_kept(stream, keep)
+    }
+    
+    fn len_kept(&self, stream: &Pubkey, keep: bool) -> usize {
+        let exists = self.entry(stream).is_some();
+        
+        match (exists, keep) {
+            (false, true) => self.entries.len() + 1,
+            (true, false) => self.entries.len() - 1,
+            _ => self.entries.len(),
+        }
+    }
+}
+
+/// One investor's negotiated terms, keyed by their stream
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ShareOverride {
+    /// Streamflow stream identifying the investor
+    pub stream: Pubkey,
+    /// Weight multiplier in basis points (10000 = strict pro-rata)
+    pub multiplier_bps: u16,
+    /// Minimum payout in quote lamports, replacing the policy's for this investor
+    pub min_payout_override: Option<u64>,
+    /// Second destination the investor's pushed payouts are split with
+    pub payout_split: Option<PayoutSplit>,
+}
+
+impl ShareOverride {
+    pub const LEN: usize = 32 + 2 + 9 + 1 + PayoutSplit::LEN;
+    
+    /// Whether terms differ from the defaults, so the entry is worth storing
+    pub fn is_kept(multiplier_bps: u16, min_payout_override: Option<u64>, payout_split: Option<PayoutSplit>) -> bool {
+        multiplier_bps != crate::constants::BASIS_POINTS_DIVISOR as u16
+            || min_payout_override.is_some()
+            || payout_split.is_some()
+    }
+}
+
+/// A fixed-ratio split of an investor's payout between the ATA in their page pair and
+/// a second ATA of theirs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PayoutSplit {
+    /// Second token account, owned by the stream's recipient like the first
+    pub secondary_ata: Pubkey,
+    /// Share of each payout sent to `secondary_ata` (1-9999); rounding dust stays
+    /// with the first destination
+    pub secondary_bps: u16,
+}
+
+impl PayoutSplit {
+    pub const LEN: usize = 32 + 2;
+}
+
+/// Splits one claimed fee pool between several vaults (e.g. investor cohorts with
+/// different fee shares). Fees land in the coordinator's own treasury and are moved
+/// into each member vault's treasury by share, where that vault's policy distributes
+/// them as its claim; no vault ever draws from another vault's funds.
+#[account]
+pub struct TreasuryCoordinator {
+    /// Arbitrary key scoping the coordinator's PDAs
+    pub coordinator_id: Pubkey,
+    
+    /// Can add, change and remove members
+    pub authority: Pubkey,
+    
+    /// Quote mint of the coordinator treasury and every member treasury
+    pub quote_mint: Pubkey,
+    
+    /// Fees recognized by splits over the coordinator's lifetime
+    pub total_claimed: u64,
+    
+    /// Rounding dust held in the coordinator treasury for the next split
+    pub carried_dust: u64,
+    
+    /// Member vaults and their shares, in split order
+    pub members: Vec<CoordinatorMember>,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl TreasuryCoordinator {
+    /// Account size holding `members` members
+    pub fn space(members: usize) -> usize {
+        8 +  // discriminator
+        32 + // coordinator_id
+        32 + // authority
+        32 + // quote_mint
+        8 +  // total_claimed
+        8 +  // carried_dust
+        4 + members * CoordinatorMember::LEN + // members
+        1    // bump
+    }
+    
+    /// Share of `vault` in bps (0 when it isn't a member)
+    pub fn share_bps(&self, vault: &Pubkey) -> u16 {
+        self.members
+            .iter()
+            .find(|member| member.vault == *vault)
+            .map_or(0, |member| member.share_bps)
+    }
+    
+    /// Sum of every member's share in bps
+    pub fn total_share_bps(&self) -> u64 {
+        self.members.iter().map(|member| member.share_bps as u64).sum()
+    }
+    
+    /// Number of members once `vault` is set to `share_bps` (0 removes it)
+    pub fn len_after(&self, vault: &Pubkey, share_bps: u16) -> usize {
+        let exists = self.members.iter().any(|member| member.vault == *vault);
+        
+        match (exists, share_bps > 0) {
+            (false, true) => self.members.len() + 1,
+            (true, false) => self.members.len() - 1,
+            _ => self.members.len(),
+        }
+    }
+}
+
+/// One vault drawing from a coordinator's fee pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CoordinatorMember {
+    /// Member vault
+    pub vault: Pubkey,
+    /// The member vault's treasury, which receives its share
+    pub treasury: Pubkey,
+    /// Share of each split in basis points
+    pub share_bps: u16,
+    /// Moved into the member treasury over the coordinator's lifetime
+    pub total_allocated: u64,
+}
+
+impl CoordinatorMember {
+    pub const LEN: usize = 32 + 32 + 2 + 8;
+}
+
+/// Pull-mode payouts owed to one investor, keyed by their stream
+#[account]
+pub struct InvestorAccrual {
+    /// Vault this accrual belongs to
+    pub vault: Pubkey,
+    
+    /// Streamflow stream identifying the investor
+    pub stream: Pubkey,
+    
+    /// Wallet allowed to claim the accrued payouts
+    pub owner: Pubkey,
+    
+    /// Allocated and not yet claimed
+    pub claimable: u64,
+    
+    /// Total claimed over the accrual's lifetime
+    pub total_claimed: u64,
+    
+    /// Timestamp of the last claim (0 = never claimed)
+    pub last_claim_ts: i64,
+    
+    /// Start of the distribution period the last claim was made in
+    pub last_claim_period: i64,
+    
+    /// Total credited over the accrual's lifetime, catch-up included and withheld
+    /// shares excluded; claimed or not
+    pub lifetime_received: u64,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl InvestorAccrual {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // stream
+        32 + // owner
+        8 +  // claimable
+        8 +  // total_claimed
+        8 +  // last_claim_ts
+        8 +  // last_claim_period
+        8 +  // lifetime_received
+        1;   // bump
+    
+    /// Whether the investor already claimed in the period starting at `period_start`.
+    /// The cooldown also lapses a full `distribution_interval` after the claim, so a
+    /// period that never starts (skipped or stalled days) can't lock the investor out.
+    pub fn is_cooling_down(&self, period_start: i64, current_ts: i64, distribution_interval: i64) -> bool {
+        self.last_claim_ts > 0
+            && self.last_claim_period == period_start
+            && current_ts < self.last_claim_ts.saturating_add(distribution_interval)
+    }
+}
+
+/// An investor's explicit consent to receive distributions, keyed by their stream.
+/// Only `opt_in` creates it, signed by the stream's recipient.
+#[account]
+pub struct OptInRegistry {
+    /// Vault the consent was given for
+    pub vault: Pubkey,
+    
+    /// Streamflow stream identifying the investor
+    pub stream: Pubkey,
+    
+    /// Stream recipient who opted in
+    pub investor: Pubkey,
+    
+    /// When the investor opted in
+    pub opted_in_ts: i64,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl OptInRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // stream
+        32 + // investor
+        8 +  // opted_in_ts
+        1;   // bump
+}
+
+/// One day's totals, opened ahead by `open_day_record` and written when the day
+/// closes under `record_day_history`; `audit_history` reconciles a run of them
+#[account]
+pub struct DayRecord {
+    /// Vault the day belongs to
+    pub vault: Pubkey,
+    
+    /// Day the record is for (`progress.latest_epoch` while it runs)
+    pub day_epoch: u64,
+    
+    /// Written by the day's close; an opened record stays empty until then
+    pub closed: bool,
+    
+    /// Claim the day started with, creator rounding held by the day before included
+    pub claimed: u64,
+    
+    /// Investor pool deferred and dust held by the day before
+    pub carried_in: u64,
+    
+    /// Paid or allocated to investors, catch-up included
+    pub distributed_investors: u64,
+    
+    /// Paid to the creator (or the DAO treasury), `PerPage` slices included
+    pub distributed_creator: u64,
+    
+    /// Burned under `RemainderMode::Burn`
+    pub burned: u64,
+    
+    /// Held for the next day: the deferred investor pool, persisted dust and
+    /// creator rounding
+    pub carried_out: u64,
+    
+    /// When the day closed
+    pub closed_ts: i64,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl DayRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // day_epoch
+        1 +  // closed
+        8 +  // claimed
+        8 +  // carried_in
+        8 +  // distributed_investors
+        8 +  // distributed_creator
+        8 +  // burned
+        8 +  // carried_out
+        8 +  // closed_ts
+        1;   // bump
+    
+    /// Copy the totals of a day `progress` just closed. `carried_in` is its
+    /// `day_carried_in` from before the close, and `rounding_held` the creator rounding
+    /// the close added to the pending claim.
+    pub fn record(
+        &mut self,
+        progress: &DistributionProgress,
+        carried_in: u64,
+        rounding_held: u64,
+        closed_ts: i64,
+    ) -> Result<()> {
+        self.closed = true;
+        self.claimed = progress.current_day_claimed;
+        self.carried_in = carried_in;
+        self.distributed_investors = progress
+            .current_day_distributed_investors
+            .checked_add(progress.current_day_backpay)
+            .ok_or(crate::errors::FeeDistributorError::ArithmeticOverflow)?;
+        self.distributed_creator = progress.current_day_distributed_creator;
+        self.burned = progress.current_day_burned;
+        self.carried_out = progress
+            .deferred_investor_pool
+            .checked_add(progress.persisted_dust)
+            .and_then(|held| held.checked_add(rounding_held))
+            .ok_or(crate::errors::FeeDistributorError::ArithmeticOverflow)?;
+        self.closed_ts = closed_ts;
+        Ok(())
+    }
+    
+    /// What came into the day less what it paid, burned or carried out; non-zero only
+    /// when the treasury couldn't cover the day's remainder or the counters disagree
+    pub fn discrepancy(&self) -> i128 {
+        self.claimed as i128 + self.carried_in as i128
+            - self.distributed_investors as i128
+            - self.distributed_creator as i128
+            - self.burned as i128
+            - self.carried_out as i128
+    }
+}
+
+/// Scratch account `simulate_full_day` accumulates a day's simulated pages into, one
+/// per vault and owner. It simulates from the real progress and treasury it was
+/// started from and restarts itself once either moves on.
+#[account]
+pub struct DaySimulation {
+    /// Vault being simulated
+    pub vault: Pubkey,
+    
+    /// Wallet that opened the simulation and may drive or close it
+    pub owner: Pubkey,
+    
+    /// Day being simulated (`latest_epoch` of the real progress)
+    pub day_epoch: u64,
+    
+    /// Real `pagination_cursor` the simulation started from
+    pub base_cursor: u32,
+    
+    /// Real `current_day_distributed_investors` the simulation started from
+    pub base_distributed: u64,
+    
+    /// Real treasury balance the simulation started from
+    pub base_treasury_balance: u64,
+    
+    /// Treasury balance after the simulated pages
+    pub treasury_balance: u64,
+    
+    /// The progress as the simulated pages leave it
+    pub progress: DistributionProgress,
+    
+    /// How the simulated day closes, once its last page is simulated
+    pub day_close: Option<SimulatedDayClose>,
+    
+    /// One line per simulated investor, in investor order from `base_cursor`
+    pub payouts: Vec<SimulatedPayout>,
+    
+    /// Bump for PDA derivation
+    pub bump: u8,
+}
+
+impl DaySimulation {
+    /// Account size holding `payouts` lines
+    pub fn space(payouts: usize) -> usize {
+        8 +  // discriminator
+        32 + // vault
+        32 + // owner
+        8 +  // day_epoch
+        4 +  // base_cursor
+        8 +  // base_distributed
+        8 +  // base_treasury_balance
+        8 +  // treasury_balance
+        (DistributionProgress::LEN - 8) + // progress
+        1 + SimulatedDayClose::LEN + // day_close
+        4 + payouts * SimulatedPayout::LEN + // payouts
+        1    // bump
+    }
+    
+    /// Start over from the real progress and treasury balance
+    pub fn restart(&mut self, progress: &DistributionProgress, treasury_balance: u64) {
+        self.day_epoch = progress.latest_epoch;
+        self.base_cursor = progress.pagination_cursor;
+        self.base_distributed = progress.current_day_distributed_investors;
+        self.base_treasury_balance = treasury_balance;
+        self.treasury_balance = treasury_balance;
+        self.progress = progress.clone();
+        self.day_close = None;
+        self.payouts.clear();
+    }
+    
+    /// Whether the simulation still starts where the real day stands
+    pub fn is_current_for(&self, progress: &DistributionProgress, treasury_balance: u64) -> bool {
+        self.day_epoch == progress.latest_epoch
+            && self.base_cursor == progress.pagination_cursor
+            && self.base_distributed == progress.current_day_distributed_investors
+            && self.base_treasury_balance == treasury_balance
+    }
+    
+    /// Lines held once the next page of up to `page_size` investors is simulated
+    /// against the real `progress` and `treasury_balance`
+    pub fn len_after_page(&self, progress: &DistributionProgress, treasury_balance: u64, page_size: u8) -> usize {
+        let (lines, cursor) = if self.is_current_for(progress, treasury_balance) {
+            (self.payouts.len(), self.progress.pagination_cursor)
+        } else {
+            (0, progress.pagination_cursor)
+        };
+        let page = progress.total_investors.saturating_sub(cursor).min(u32::from(page_size));
+        lines + page as usize
+    }
+}
+
+/// One investor's line of a simulated day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimulatedPayout {
+    pub investor_index: u32,
+    /// Locked amount the page weighs the investor by
+    pub locked: u64,
+    /// The investor's share of the page's (or day's) total weight
+    pub weight_bps: u16,
+    /// What the investor would receive, catch-up included; 0 when skipped
+    pub payout: u64,
+}
+
+impl SimulatedPayout {
+    pub const LEN: usize = 4 + 8 + 2 + 8;
+}
+
+/// The creator's settlement of a simulated day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimulatedDayClose {
+    /// Paid to the creator (or the DAO treasury) at the close
+    pub remainder: u64,
+    /// Burned under `RemainderMode::Burn`
+    pub burned: u64,
+    /// Held back below `creator_granularity_lamports`
+    pub rounding_held: u64,
+}
+
+impl SimulatedDayClose {
+    pub const LEN: usize = 8 + 8 + 8;
 }
 
 /// Represents a single investor in the distribution
@@ -114,4 +1969,37 @@ pub struct InvestorDistributionEntry {
     pub investor_quote_ata: Pubkey,
     /// Streamflow stream account
     pub stream_account: Pubkey,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinator_tracks_member_count_and_shares() {
+        let vault = Pubkey::new_unique();
+        let mut coordinator = TreasuryCoordinator {
+            coordinator_id: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            total_claimed: 0,
+            carried_dust: 0,
+            members: Vec::new(),
+            bump: 0,
+        };
+        assert_eq!(coordinator.len_after(&vault, 0), 0);
+        assert_eq!(coordinator.len_after(&vault, 6_000), 1);
+
+        coordinator.members.push(CoordinatorMember {
+            vault,
+            treasury: Pubkey::new_unique(),
+            share_bps: 6_000,
+            total_allocated: 0,
+        });
+        assert_eq!(coordinator.share_bps(&vault), 6_000);
+        assert_eq!(coordinator.share_bps(&Pubkey::new_unique()), 0);
+        assert_eq!(coordinator.total_share_bps(), 6_000);
+        assert_eq!(coordinator.len_after(&vault, 4_000), 1);
+        assert_eq!(coordinator.len_after(&vault, 0), 0);
+    }
+}
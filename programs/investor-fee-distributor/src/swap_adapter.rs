@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, accessor, Transfer};
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, SETTLEMENT_RATE_SCALE},
+    errors::FeeDistributorError,
+};
+
+/// A DEX route that converts quote into the policy's payout mint.
+///
+/// Swaps are made per payout, so every converted amount leaves the treasury exactly
+/// as an unconverted payout would; the quote-side accounting doesn't change.
+pub trait SwapAdapter {
+    /// Payout-mint amount the route would return for `amount_in` quote right now
+    fn quote_out(&self, amount_in: u64) -> Result<u64>;
+
+    /// Swap `amount_in` quote out of the treasury into the payout treasury, returning
+    /// the payout-mint amount received; must fail below `min_amount_out`
+    fn swap(&self, amount_in: u64, min_amount_out: u64) -> Result<u64>;
+}
+
+/// Fewest payout tokens accepted for an `expected` quote within `max_slippage_bps`
+pub fn min_amount_out(expected: u64, max_slippage_bps: u16) -> Result<u64> {
+    let min = (expected as u128)
+        .checked_mul(BASIS_POINTS_DIVISOR.saturating_sub(max_slippage_bps as u64) as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        / BASIS_POINTS_DIVISOR as u128;
+
+    u64::try_from(min).map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// Payout-mint amount a `quote_amount` payout is worth at `settlement_rate`, floored
+pub fn settlement_amount(quote_amount: u64, settlement_rate: u64) -> Result<u64> {
+    let amount = (quote_amount as u128)
+        .checked_mul(settlement_rate as u128)
+        .ok_or(FeeDistributorError::ArithmeticOverflow)?
+        / SETTLEMENT_RATE_SCALE as u128;
+
+    u64::try_from(amount).map_err(|_| FeeDistributorError::ArithmeticOverflow.into())
+}
+
+/// Swap `amount_in`, failing with `SwapSlippageExceeded` when the route returns less
+/// than its own quote allows; the error aborts the whole page
+pub fn swap_within_slippage(
+    adapter: &impl SwapAdapter,
+    amount_in: u64,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let min_out = min_amount_out(adapter.quote_out(amount_in)?, max_slippage_bps)?;
+    let received = adapter.swap(amount_in, min_out)?;
+    require!(received >= min_out, FeeDistributorError::SwapSlippageExceeded);
+
+    Ok(received)
+}
+
+/// Stand-in for a DEX pool: quote is sent to the pool's quote account and the same
+/// amount of the payout mint is released 1:1 from liquidity pre-deposited in the
+/// payout treasury
+///
+/// PLACEHOLDER: a real adapter reads the pool's price for `quote_out` and CPIs into
+/// the DEX's swap instruction with `min_amount_out` as its slippage bound.
+pub struct SimulatedSwap<'a, 'info> {
+    pub treasury: AccountInfo<'info>,
+    pub swap_pool: AccountInfo<'info>,
+    pub payout_treasury: AccountInfo<'info>,
+    pub treasury_authority: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl SwapAdapter for SimulatedSwap<'_, '_> {
+    fn quote_out(&self, amount_in: u64) -> Result<u64> {
+        Ok(amount_in)
+    }
+
+    fn swap(&self, amount_in: u64, min_amount_out: u64) -> Result<u64> {
+        // The pool can only pay out the liquidity it holds
+        let amount_out = std::cmp::min(amount_in, accessor::amount(&self.payout_treasury)?);
+        require!(amount_out >= min_amount_out, FeeDistributorError::SwapSlippageExceeded);
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                Transfer {
+                    from: self.treasury.clone(),
+                    to: self.swap_pool.clone(),
+                    authority: self.treasury_authority.clone(),
+                },
+                self.signer_seeds,
+            ),
+            amount_in,
+        )?;
+
+        Ok(amount_out)
+    }
+}
+
+/// A `TreasuryDraw` settlement: each payout is drawn from the pre-funded payout
+/// treasury at the policy's `settlement_rate` instead of being swapped. The payout's
+/// quote still leaves the treasury, to the swap pool account, where whoever funds the
+/// payout treasury is reimbursed in quote; the quote-side accounting doesn't change.
+pub struct TreasuryDraw<'a, 'info> {
+    /// The accounts a swap would use
+    pub pool: SimulatedSwap<'a, 'info>,
+    pub settlement_rate: u64,
+}
+
+impl SwapAdapter for TreasuryDraw<'_, '_> {
+    fn quote_out(&self, amount_in: u64) -> Result<u64> {
+        settlement_amount(amount_in, self.settlement_rate)
+    }
+
+    fn swap(&self, amount_in: u64, _min_amount_out: u64) -> Result<u64> {
+        // Unlike a pool's price, the rate can't move: a short treasury fails outright
+        let amount_out = self.quote_out(amount_in)?;
+        require!(
+            accessor::amount(&self.pool.payout_treasury)? >= amount_out,
+            FeeDistributorError::InsufficientSettlementLiquidity
+        );
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.pool.token_program.clone(),
+                Transfer {
+                    from: self.pool.treasury.clone(),
+                    to: self.pool.swap_pool.clone(),
+                    authority: self.pool.treasury_authority.clone(),
+                },
+                self.pool.signer_seeds,
+            ),
+            amount_in,
+        )?;
+
+        Ok(amount_out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockPool {
+        /// Payout tokens per 10000 quote the pool quotes
+        rate_bps: u64,
+        /// Payout tokens per 10000 quote it actually fills at
+        fill_bps: u64,
+    }
+
+    impl SwapAdapter for MockPool {
+        fn quote_out(&self, amount_in: u64) -> Result<u64> {
+            Ok(amount_in * self.rate_bps / 10_000)
+        }
+
+        fn swap(&self, amount_in: u64, _min_amount_out: u64) -> Result<u64> {
+            Ok(amount_in * self.fill_bps / 10_000)
+        }
+    }
+
+    #[test]
+    fn swap_accepts_fills_within_slippage() {
+        let pool = MockPool { rate_bps: 9_990, fill_bps: 9_950 };
+        assert_eq!(min_amount_out(999_000, 50).unwrap(), 994_005);
+        assert_eq!(swap_within_slippage(&pool, 1_000_000, 50).unwrap(), 995_000);
+    }
+
+    #[test]
+    fn swap_rejects_fills_beyond_slippage() {
+        // The price moved 1% between quote and fill against a 0.5% bound
+        let pool = MockPool { rate_bps: 10_000, fill_bps: 9_900 };
+        assert!(swap_within_slippage(&pool, 1_000_000, 50).is_err());
+        assert_eq!(min_amount_out(u64::MAX, 0).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn settlement_amount_values_quote_at_the_rate() {
+        // 1 quote unit settles 2.5 payout units; the fraction is floored
+        let rate = 2_500_000_000;
+        assert_eq!(settlement_amount(1_000, rate).unwrap(), 2_500);
+        assert_eq!(settlement_amount(3, rate).unwrap(), 7);
+        assert_eq!(settlement_amount(1_000_000, SETTLEMENT_RATE_SCALE).unwrap(), 1_000_000);
+        assert!(settlement_amount(u64::MAX, 2 * SETTLEMENT_RATE_SCALE).is_err());
+    }
+}
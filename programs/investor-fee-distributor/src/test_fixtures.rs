@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, state::*};
+
+/// A plain `ProRata`, `Push`, live-stream policy with the given investor share and
+/// minimum payout
+pub(crate) fn policy_with_min_payout(investor_fee_share_bps: u16, min_payout_lamports: u64) -> DistributionPolicy {
+    DistributionPolicy {
+        vault: Pubkey::default(),
+        authority: Pubkey::default(),
+        quote_mint: Pubkey::default(),
+        creator_wallet: Pubkey::default(),
+        total_investor_allocation: 0,
+        investor_fee_share_bps,
+        daily_cap_lamports: 0,
+        min_payout_lamports,
+        creator_payout_mode: CreatorPayoutMode::EndOfDay,
+        investor_fallback_wallet: None,
+        weight_source: WeightSource::LiveStreams,
+        distribution_mode: DistributionMode::Push,
+        distribution_interval: SECONDS_PER_DAY,
+        distribution_scheme: DistributionScheme::ProRata,
+        fixed_payout_lamports: 0,
+        fixed_shortfall: FixedShortfall::SplitEvenly,
+        defer_creator_close: false,
+        max_claim_age_secs: 0,
+        share_override_count: 0,
+        remainder_mode: RemainderMode::Creator,
+        quote_decimals: 6,
+        min_payout_unit: MinPayoutUnit::Lamports,
+        attach_memo: false,
+        memo_reference: String::new(),
+        fee_source: None,
+        max_single_investor_bps: 0,
+        claim_paused: false,
+        investor_token_decimals: 6,
+        allocation_decimals: 6,
+        defer_below_minimum: false,
+        payout_granularity_lamports: 1,
+        backpay_enabled: false,
+        backpay_lamports: 0,
+        payout_priority: PayoutPriority::AccountOrder,
+        yield_reserve: None,
+        pull_claim_cooldown: false,
+        tolerate_claim_failure: false,
+        damm_pool: None,
+        payout_mint: None,
+        swap_pool: None,
+        max_swap_slippage_bps: DEFAULT_MAX_SWAP_SLIPPAGE_BPS,
+        reject_delegated_atas: false,
+        min_claim_to_distribute: 0,
+        claim_ema_alpha_bps: DEFAULT_CLAIM_EMA_ALPHA_BPS,
+        circuit_breaker_multiple_bps: 0,
+        top_n_investors: 0,
+        require_opt_in: false,
+        min_creator_share_bps: 0,
+        skip_weekends: false,
+        lifetime_cap_lamports: 0,
+        weight_in_usd: false,
+        price_oracle: Pubkey::default(),
+        creator_deposit_mode: CreatorDepositMode::Transfer,
+        governance_realm: Pubkey::default(),
+        governance_treasury: Pubkey::default(),
+        confirm_deliveries: false,
+        creator_granularity_lamports: 1,
+        max_claim_growth_bps: UNLIMITED_CLAIM_GROWTH_BPS,
+        finalized: false,
+        weighting_mode: WeightingMode::Instantaneous,
+        verbose_events: true,
+        residual_mode: ResidualMode::RollToNextDay,
+        protocol_fee_bps: 0,
+        protocol_fee_wallet: Pubkey::default(),
+        exclude_creator_from_investors: false,
+        reconciliation_tolerance_lamports: 0,
+        max_transfer_size: 0,
+        page_quorum_bps: 0,
+        max_oracle_staleness_seconds: DEFAULT_MAX_ORACLE_STALENESS_SECONDS,
+        max_oracle_confidence_bps: DEFAULT_MAX_ORACLE_CONFIDENCE_BPS,
+        prefunded_balance_mode: PrefundedBalanceMode::Reserve,
+        weight_blend_bps: 0,
+        max_investors_per_day: 0,
+        distributor_program: None,
+        dust_to_last_investor: false,
+        withholding_bps: 0,
+        withholding_wallet: None,
+        distribute_on_close: false,
+        persist_dust_across_days: false,
+        require_creator_signature: false,
+        withdrawal_handling: WithdrawalHandling::Current,
+        creator_daily_cap_lamports: 0,
+        deterministic_index_order: false,
+        payout_settlement: PayoutSettlement::Swap,
+        settlement_rate: 0,
+        daily_cap_allocation: DailyCapAllocation::PageOrder,
+        max_ata_creations_per_page: 0,
+        max_ata_creation_funding: 0,
+        record_day_history: false,
+        min_payout_override_count: 0,
+        payout_split_count: 0,
+        treasury_authority_bump: 0,
+        bump: 0,
+    }
+}
+
+/// A fresh progress account midway through a day that claimed `claimed` and has
+/// paid investors `distributed_investors` so far
+pub(crate) fn progress_after_day(claimed: u64, distributed_investors: u64) -> DistributionProgress {
+    DistributionProgress {
+        vault: Pubkey::default(),
+        last_distribution_ts: 0,
+        current_day_claimed: claimed,
+        current_day_distributed_investors: distributed_investors,
+        current_day_distributed_creator: 0,
+        carry_over_dust: 0,
+        pagination_cursor: 0,
+        day_completed: false,
+        total_investors: 0,
+        snapshot_cursor: 0,
+        total_locked_today: 0,
+        active_investors_today: 0,
+        total_weight_today: 0,
+        snapshot_ts: 0,
+        pull_outstanding: 0,
+        last_claim_ts: 0,
+        current_period_claimed: 0,
+        current_day_investor_pool: 0,
+        current_day_burned: 0,
+        deferred_investor_pool: 0,
+        current_day_deferred: 0,
+        current_day_backpay: 0,
+        yield_principal: 0,
+        total_yield_realized: 0,
+        latest_epoch: 0,
+        distribution_forced: false,
+        current_day_investors_paid: 0,
+        claim_ema: 0,
+        claim_ema_days: 0,
+        circuit_breaker: CircuitBreakerState::Armed,
+        top_n_cutoff_locked: 0,
+        top_n_cutoff_index: u32::MAX,
+        lifetime_distributed: 0,
+        vault_finalized: false,
+        usd_weight_price: 0,
+        prior_day_claimed: 0,
+        set_version: 0,
+        snapshot_set_version: 0,
+        prefunded_reserve: 0,
+        prefunding_settled: true,
+        close_pool: 0,
+        close_cursor: 0,
+        close_distributed: 0,
+        persisted_dust: 0,
+        bump: 0,
+    }
+}
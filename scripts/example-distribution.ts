@@ -35,6 +35,8 @@ const VAULT_SEED = Buffer.from("vault");
 const POLICY_SEED = Buffer.from("policy");
 const PROGRESS_SEED = Buffer.from("progress");
 const TREASURY_SEED = Buffer.from("treasury");
+const SNAPSHOT_SEED = Buffer.from("snapshot");
+const SHARE_OVERRIDES_SEED = Buffer.from("share_overrides");
 const INVESTOR_FEE_POS_OWNER_SEED = Buffer.from("investor_fee_pos_owner");
 
 interface Investor {
@@ -51,12 +53,15 @@ class DistributionManager {
   
   private vault: Keypair;
   private quoteMint: PublicKey;
+  private investorMint: PublicKey;
   private creator: Keypair;
   private creatorQuoteAta: PublicKey;
   
   private policyPda: PublicKey;
   private progressPda: PublicKey;
   private treasuryPda: PublicKey;
+  private lockedSnapshotPda: PublicKey;
+  private shareOverridesPda: PublicKey;
   private treasuryAuthorityPda: PublicKey;
   
   private investors: Investor[] = [];
@@ -88,6 +93,30 @@ class DistributionManager {
     );
     console.log("✓ Quote Mint:", this.quoteMint.toBase58());
     
+    // Create investor token mint and mint Y0 (initialize checks Y0 against supply)
+    this.investorMint = await createMint(
+      this.connection,
+      this.payer,
+      this.payer.publicKey,
+      null,
+      9
+    );
+    const allocationHolder = await createAccount(
+      this.connection,
+      this.payer,
+      this.investorMint,
+      this.payer.publicKey
+    );
+    await mintTo(
+      this.connection,
+      this.payer,
+      this.investorMint,
+      allocationHolder,
+      this.payer,
+      BigInt(TOTAL_INVESTOR_ALLOCATION)
+    );
+    console.log("✓ Investor Mint:", this.investorMint.toBase58());
+    
     // Create creator
     this.creator = Keypair.generate();
     
@@ -127,6 +156,16 @@ class DistributionManager {
       this.program.programId
     );
     console.log("✓ Treasury PDA:", this.treasuryPda.toBase58());
+
+    [this.lockedSnapshotPda] = PublicKey.findProgramAddressSync(
+      [VAULT_SEED, this.vault.publicKey.toBuffer(), SNAPSHOT_SEED],
+      this.program.programId
+    );
+    
+    [this.shareOverridesPda] = PublicKey.findProgramAddressSync(
+      [VAULT_SEED, this.vault.publicKey.toBuffer(), SHARE_OVERRIDES_SEED],
+      this.program.programId
+    );
     
     [this.treasuryAuthorityPda] = PublicKey.findProgramAddressSync(
       [VAULT_SEED, this.vault.publicKey.toBuffer(), INVESTOR_FEE_POS_OWNER_SEED],
@@ -194,9 +233,12 @@ class DistributionManager {
         authority: this.payer.publicKey,
         vault: this.vault.publicKey,
         quoteMint: this.quoteMint,
+        investorMint: this.investorMint,
         creatorWallet: this.creator.publicKey,
         policy: this.policyPda,
         progress: this.progressPda,
+        lockedSnapshot: this.lockedSnapshotPda,
+        shareOverrides: this.shareOverridesPda,
         treasury: this.treasuryPda,
         treasuryAuthority: this.treasuryAuthorityPda,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -303,6 +345,10 @@ class DistributionManager {
             treasuryAuthority: this.treasuryAuthorityPda,
             creatorQuoteAta: this.creatorQuoteAta,
             creatorWallet: this.creator.publicKey,
+            fallbackQuoteAta: null,
+            lockedSnapshot: null,
+            shareOverrides: null,
+            memoProgram: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
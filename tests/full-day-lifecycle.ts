@@ -0,0 +1,473 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { InvestorFeeDistributor } from "../target/types/investor_fee_distributor";
+import {
+  AddressLookupTableAccount,
+  AddressLookupTableProgram,
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import {
+  ACCOUNT_SIZE,
+  AccountLayout,
+  MINT_SIZE,
+  MintLayout,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BankrunProvider } from "anchor-bankrun";
+import { Clock, ProgramTestContext, startAnchor } from "solana-bankrun";
+import { assert } from "chai";
+
+const IDL = require("../target/idl/investor_fee_distributor.json");
+
+// End-to-end run of a multi-page day and the day after it. Bankrun is used
+// instead of the local validator because the test has to move the clock
+// forward a full distribution interval and write mock stream accounts directly.
+describe("full multi-page day lifecycle", () => {
+  const VAULT_SEED = Buffer.from("vault");
+  const POLICY_SEED = Buffer.from("policy");
+  const PROGRESS_SEED = Buffer.from("progress");
+  const TREASURY_SEED = Buffer.from("treasury");
+  const SNAPSHOT_SEED = Buffer.from("snapshot");
+  const SHARE_OVERRIDES_SEED = Buffer.from("share_overrides");
+  const INVESTOR_FEE_POS_OWNER_SEED = Buffer.from("investor_fee_pos_owner");
+
+  const SECONDS_PER_DAY = 86_400n;
+  const START_TS = 1_700_000_000n;
+
+  const NUM_INVESTORS = 120;
+  const PAGE_SIZE = 40;
+  const NUM_PAGES = NUM_INVESTORS / PAGE_SIZE;
+
+  const TOTAL_INVESTOR_ALLOCATION = 1_200_000_000_000n;
+  const INVESTOR_FEE_SHARE_BPS = 5000n;
+  const MIN_PAYOUT_LAMPORTS = 1_000n;
+  const DAY_FEES = [250_000_000_000n, 97_531_000_000n];
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<InvestorFeeDistributor>;
+  let payer: Keypair;
+
+  const vault = Keypair.generate();
+  const creator = Keypair.generate();
+  const quoteMint = Keypair.generate().publicKey;
+  const investorMint = Keypair.generate().publicKey;
+  const creatorQuoteAta = Keypair.generate().publicKey;
+
+  let policyPda: PublicKey;
+  let progressPda: PublicKey;
+  let treasuryPda: PublicKey;
+  let lockedSnapshotPda: PublicKey;
+  let shareOverridesPda: PublicKey;
+  let treasuryAuthorityPda: PublicKey;
+  let lookupTable: AddressLookupTableAccount;
+
+  const investors: {
+    wallet: PublicKey;
+    quoteAta: PublicKey;
+    stream: PublicKey;
+    locked: bigint;
+  }[] = [];
+
+  const pda = (seed: Buffer) =>
+    PublicKey.findProgramAddressSync(
+      [VAULT_SEED, vault.publicKey.toBuffer(), seed],
+      program.programId
+    )[0];
+
+  const setMint = (address: PublicKey, supply: bigint) => {
+    const data = Buffer.alloc(MINT_SIZE);
+    MintLayout.encode(
+      {
+        mintAuthorityOption: 1,
+        mintAuthority: payer.publicKey,
+        supply,
+        decimals: 9,
+        isInitialized: true,
+        freezeAuthorityOption: 0,
+        freezeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+  };
+
+  const setTokenAccount = (
+    address: PublicKey,
+    mint: PublicKey,
+    owner: PublicKey,
+    amount: bigint
+  ) => {
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode(
+      {
+        mint,
+        owner,
+        amount,
+        delegateOption: 0,
+        delegate: PublicKey.default,
+        state: 1,
+        isNativeOption: 0,
+        isNative: 0n,
+        delegatedAmount: 0n,
+        closeAuthorityOption: 0,
+        closeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+  };
+
+  // Mock Streamflow stream: locked amount at offset 8, recipient at offset 16 and
+  // deposited amount at offset 48, which can't be below the locked amount; nothing
+  // has vested yet, so everything deposited is still locked
+  const setStream = (address: PublicKey, locked: bigint, recipient: PublicKey) => {
+    const data = Buffer.alloc(128);
+    data.writeBigUInt64LE(locked, 8);
+    recipient.toBuffer().copy(data, 16);
+    data.writeBigUInt64LE(locked, 48);
+    context.setAccount(address, {
+      lamports: LAMPORTS_PER_SOL,
+      data,
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+  };
+
+  const tokenBalance = async (address: PublicKey): Promise<bigint> => {
+    const account = await context.banksClient.getAccount(address);
+    return AccountLayout.decode(Buffer.from(account!.data)).amount;
+  };
+
+  // Simulated DAMM claim: fees land in the treasury and are recognized on the first page
+  const accrueFees = async (amount: bigint) => {
+    const balance = await tokenBalance(treasuryPda);
+    setTokenAccount(treasuryPda, quoteMint, treasuryAuthorityPda, balance + amount);
+  };
+
+  const setClock = async (unixTimestamp: bigint) => {
+    const current = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        current.slot,
+        current.epochStartTimestamp,
+        current.epoch,
+        current.leaderScheduleEpoch,
+        unixTimestamp
+      )
+    );
+  };
+
+  const sendV0 = async (instructions: TransactionInstruction[], signers: Keypair[] = []) => {
+    const [blockhash] = (await context.banksClient.getLatestBlockhash())!;
+    const message = new TransactionMessage({
+      payerKey: payer.publicKey,
+      recentBlockhash: blockhash,
+      instructions,
+    }).compileToV0Message(lookupTable ? [lookupTable] : []);
+    const tx = new VersionedTransaction(message);
+    tx.sign([payer, ...signers]);
+    await context.banksClient.processTransaction(tx);
+  };
+
+  // Off-chain model of a LiveStreams pro-rata page: the page pool is the claim
+  // times min(share, page locked / Y0), split by locked amount, minimum applied
+  const expectedPagePayouts = (claimed: bigint, page: typeof investors): bigint[] => {
+    const pageLocked = page.reduce((sum, investor) => sum + investor.locked, 0n);
+    let fLocked = (pageLocked * 10_000n) / TOTAL_INVESTOR_ALLOCATION;
+    if (fLocked > 10_000n) fLocked = 10_000n;
+    const bps = fLocked < INVESTOR_FEE_SHARE_BPS ? fLocked : INVESTOR_FEE_SHARE_BPS;
+    const pool = (claimed * bps) / 10_000n;
+    return page.map((investor) => {
+      const payout = (pool * investor.locked) / pageLocked;
+      return payout >= MIN_PAYOUT_LAMPORTS ? payout : 0n;
+    });
+  };
+
+  const distributePage = async (page: number) => {
+    const pageInvestors = investors.slice(page * PAGE_SIZE, (page + 1) * PAGE_SIZE);
+    const ix = await program.methods
+      .distributeFees(pageInvestors.length)
+      .accounts({
+        caller: payer.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+        quoteMint,
+        treasury: treasuryPda,
+        treasuryAuthority: treasuryAuthorityPda,
+        creatorQuoteAta,
+        creatorWallet: creator.publicKey,
+        fallbackQuoteAta: null,
+        payoutTreasury: null,
+        swapPool: null,
+        lockedSnapshot: null,
+        shareOverrides: null,
+        memoProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        pageInvestors.flatMap((investor) => [
+          { pubkey: investor.quoteAta, isSigner: false, isWritable: true },
+          { pubkey: investor.stream, isSigner: false, isWritable: false },
+        ])
+      )
+      .instruction();
+
+    await sendV0([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }), ix]);
+  };
+
+  // One full day: every page, asserting balances and progress after each
+  const runDay = async (claimed: bigint, dayTs: bigint) => {
+    const creatorBefore = await tokenBalance(creatorQuoteAta);
+    const investorsBefore = await Promise.all(
+      investors.map((investor) => tokenBalance(investor.quoteAta))
+    );
+    let distributedInvestors = 0n;
+
+    for (let page = 0; page < NUM_PAGES; page++) {
+      await distributePage(page);
+
+      const pageStart = page * PAGE_SIZE;
+      const pageInvestors = investors.slice(pageStart, pageStart + PAGE_SIZE);
+      const expected = expectedPagePayouts(claimed, pageInvestors);
+
+      for (let i = 0; i < pageInvestors.length; i++) {
+        const balance = await tokenBalance(pageInvestors[i].quoteAta);
+        assert.equal(
+          (balance - investorsBefore[pageStart + i]).toString(),
+          expected[i].toString(),
+          `investor ${pageStart + i} payout`
+        );
+      }
+      distributedInvestors += expected.reduce((sum, payout) => sum + payout, 0n);
+
+      // Investors not reached yet are untouched
+      for (let i = pageStart + PAGE_SIZE; i < NUM_INVESTORS; i++) {
+        const balance = await tokenBalance(investors[i].quoteAta);
+        assert.equal(balance.toString(), investorsBefore[i].toString());
+      }
+
+      const progress = await program.account.distributionProgress.fetch(progressPda);
+      const isLastPage = page === NUM_PAGES - 1;
+      assert.equal(progress.paginationCursor, pageStart + PAGE_SIZE);
+      assert.equal(progress.lastDistributionTs.toString(), dayTs.toString());
+      assert.equal(progress.currentDayClaimed.toString(), claimed.toString());
+      assert.equal(
+        progress.currentDayDistributedInvestors.toString(),
+        distributedInvestors.toString()
+      );
+      assert.equal(progress.dayCompleted, isLastPage);
+
+      // EndOfDay: the creator is paid only once the last page is processed
+      const creatorBalance = await tokenBalance(creatorQuoteAta);
+      const expectedCreator = isLastPage ? claimed - distributedInvestors : 0n;
+      assert.equal(
+        (creatorBalance - creatorBefore).toString(),
+        expectedCreator.toString(),
+        `creator balance after page ${page + 1}`
+      );
+      if (isLastPage) {
+        assert.equal(
+          progress.currentDayDistributedCreator.toString(),
+          expectedCreator.toString()
+        );
+      }
+    }
+
+    // Investors never exceed their share of the claim, and the day settles fully
+    assert.isTrue(distributedInvestors <= (claimed * INVESTOR_FEE_SHARE_BPS) / 10_000n);
+    assert.equal((await tokenBalance(treasuryPda)).toString(), "0");
+  };
+
+  before(async () => {
+    context = await startAnchor(".", [], []);
+    provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<InvestorFeeDistributor>(IDL, provider);
+    payer = context.payer;
+
+    policyPda = pda(POLICY_SEED);
+    progressPda = pda(PROGRESS_SEED);
+    treasuryPda = pda(TREASURY_SEED);
+    lockedSnapshotPda = pda(SNAPSHOT_SEED);
+    shareOverridesPda = pda(SHARE_OVERRIDES_SEED);
+    treasuryAuthorityPda = pda(INVESTOR_FEE_POS_OWNER_SEED);
+
+    setMint(quoteMint, 1_000_000_000_000_000n);
+    setMint(investorMint, TOTAL_INVESTOR_ALLOCATION);
+    setTokenAccount(creatorQuoteAta, quoteMint, creator.publicKey, 0n);
+
+    // Locked amounts vary per investor and add up to under half of Y0, so
+    // f_locked binds on every page rather than the 50% share
+    for (let i = 0; i < NUM_INVESTORS; i++) {
+      const wallet = Keypair.generate().publicKey;
+      const quoteAta = Keypair.generate().publicKey;
+      const stream = Keypair.generate().publicKey;
+      const locked = 500_000_000n + BigInt(i) * 40_000_000n + BigInt((i * 7919) % 1_000);
+
+      setTokenAccount(quoteAta, quoteMint, wallet, 0n);
+      setStream(stream, locked, wallet);
+      investors.push({ wallet, quoteAta, stream, locked });
+    }
+
+    await setClock(START_TS);
+  });
+
+  it("Initializes a vault for 120 investors", async () => {
+    await program.methods
+      .initialize(
+        new anchor.BN(TOTAL_INVESTOR_ALLOCATION.toString()),
+        Number(INVESTOR_FEE_SHARE_BPS),
+        new anchor.BN(0),
+        new anchor.BN(MIN_PAYOUT_LAMPORTS.toString()),
+        NUM_INVESTORS
+      )
+      .accounts({
+        authority: payer.publicKey,
+        vault: vault.publicKey,
+        quoteMint,
+        investorMint,
+        creatorWallet: creator.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+        lockedSnapshot: lockedSnapshotPda,
+        shareOverrides: shareOverridesPda,
+        treasury: treasuryPda,
+        treasuryAuthority: treasuryAuthorityPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+    assert.equal(progress.totalInvestors, NUM_INVESTORS);
+    assert.equal(progress.paginationCursor, 0);
+    assert.isFalse(progress.dayCompleted);
+
+    // 40 [ata, stream] pairs don't fit a legacy transaction, so pages go
+    // through a lookup table holding every investor and fixed account
+    context.warpToSlot((await context.banksClient.getSlot()) + 10n);
+    const slot = await context.banksClient.getSlot();
+    const [createIx, tableAddress] = AddressLookupTableProgram.createLookupTable({
+      authority: payer.publicKey,
+      payer: payer.publicKey,
+      recentSlot: slot - 1n,
+    });
+    context.warpToSlot(slot + 1n);
+    await sendV0([createIx]);
+
+    const addresses = [
+      vault.publicKey,
+      policyPda,
+      progressPda,
+      quoteMint,
+      treasuryPda,
+      treasuryAuthorityPda,
+      creatorQuoteAta,
+      creator.publicKey,
+      program.programId,
+      TOKEN_PROGRAM_ID,
+      ...investors.flatMap((investor) => [investor.quoteAta, investor.stream]),
+    ];
+    for (let i = 0; i < addresses.length; i += 20) {
+      await sendV0([
+        AddressLookupTableProgram.extendLookupTable({
+          lookupTable: tableAddress,
+          authority: payer.publicKey,
+          payer: payer.publicKey,
+          addresses: addresses.slice(i, i + 20),
+        }),
+      ]);
+    }
+
+    // Extended addresses become usable from the next slot
+    context.warpToSlot((await context.banksClient.getSlot()) + 1n);
+    const tableAccount = await context.banksClient.getAccount(tableAddress);
+    lookupTable = new AddressLookupTableAccount({
+      key: tableAddress,
+      state: AddressLookupTableAccount.deserialize(Buffer.from(tableAccount!.data)),
+    });
+    assert.equal(lookupTable.state.addresses.length, addresses.length);
+
+    // The clock carried on through the warps; pin it back for day one
+    await setClock(START_TS);
+  });
+
+  it("Runs day one across three pages of 40", async () => {
+    await accrueFees(DAY_FEES[0]);
+    await runDay(DAY_FEES[0], START_TS);
+  });
+
+  it("Rejects another page once the day is complete", async () => {
+    try {
+      await distributePage(0);
+      assert.fail("Should have rejected a page after the day completed");
+    } catch (err) {
+      assert.include(err.toString(), "DayAlreadyCompleted");
+    }
+  });
+
+  it("Runs a second day after the clock advances", async () => {
+    const dayTwoTs = START_TS + SECONDS_PER_DAY;
+    await setClock(dayTwoTs);
+    await accrueFees(DAY_FEES[1]);
+
+    // Day two starts clean: the first page resets the cursor and per-day totals
+    await runDay(DAY_FEES[1], dayTwoTs);
+
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+    assert.equal(progress.lastClaimTs.toString(), dayTwoTs.toString());
+    assert.equal(progress.currentPeriodClaimed.toString(), "0");
+  });
+
+  it("Migrates the drained vault to a new quote mint", async () => {
+    const newQuoteMint = Keypair.generate().publicKey;
+    setMint(newQuoteMint, 1_000_000_000_000_000n);
+    assert.equal((await tokenBalance(treasuryPda)).toString(), "0");
+
+    await program.methods
+      .migrateQuoteMint()
+      .accounts({
+        authority: payer.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+        newQuoteMint,
+        treasury: treasuryPda,
+        treasuryAuthority: treasuryAuthorityPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    // Closed and created again at the same PDA, now a token account of the new mint
+    const treasuryAccount = await context.banksClient.getAccount(treasuryPda);
+    assert.isTrue(treasuryAccount!.owner.equals(TOKEN_PROGRAM_ID));
+    const treasury = AccountLayout.decode(Buffer.from(treasuryAccount!.data));
+    assert.equal(treasury.mint.toBase58(), newQuoteMint.toBase58());
+    assert.equal(treasury.owner.toBase58(), treasuryAuthorityPda.toBase58());
+    assert.equal(treasury.amount.toString(), "0");
+
+    const policy = await program.account.distributionPolicy.fetch(policyPda);
+    assert.equal(policy.quoteMint.toBase58(), newQuoteMint.toBase58());
+  });
+});
@@ -28,6 +28,7 @@ describe("investor-fee-distributor", () => {
 
   // Test accounts
   let quoteMint: PublicKey;
+  let investorMint: PublicKey;
   let vault: Keypair;
   let creator: Keypair;
   let creatorQuoteAta: PublicKey;
@@ -36,6 +37,8 @@ describe("investor-fee-distributor", () => {
   let policyPda: PublicKey;
   let progressPda: PublicKey;
   let treasuryPda: PublicKey;
+  let lockedSnapshotPda: PublicKey;
+  let shareOverridesPda: PublicKey;
   let treasuryAuthorityPda: PublicKey;
   
   // Investors
@@ -52,6 +55,9 @@ describe("investor-fee-distributor", () => {
   const POLICY_SEED = Buffer.from("policy");
   const PROGRESS_SEED = Buffer.from("progress");
   const TREASURY_SEED = Buffer.from("treasury");
+  const SNAPSHOT_SEED = Buffer.from("snapshot");
+  const SHARE_OVERRIDES_SEED = Buffer.from("share_overrides");
+  const WEIGHT_TABLE_SEED = Buffer.from("weight_table");
   const INVESTOR_FEE_POS_OWNER_SEED = Buffer.from("investor_fee_pos_owner");
 
   const TOTAL_INVESTOR_ALLOCATION = 1_000_000 * LAMPORTS_PER_SOL;
@@ -71,6 +77,29 @@ describe("investor-fee-distributor", () => {
 
     console.log("Quote Mint:", quoteMint.toBase58());
 
+    // Investor token mint with Y0 minted, so initialize can check Y0 against supply
+    investorMint = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      9
+    );
+    const allocationHolder = await createAccount(
+      provider.connection,
+      payer,
+      investorMint,
+      provider.wallet.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      investorMint,
+      allocationHolder,
+      payer,
+      BigInt(TOTAL_INVESTOR_ALLOCATION)
+    );
+
     // Create vault (just a keypair for seed)
     vault = Keypair.generate();
 
@@ -107,6 +136,16 @@ describe("investor-fee-distributor", () => {
       program.programId
     );
 
+    [lockedSnapshotPda] = PublicKey.findProgramAddressSync(
+      [VAULT_SEED, vault.publicKey.toBuffer(), SNAPSHOT_SEED],
+      program.programId
+    );
+
+    [shareOverridesPda] = PublicKey.findProgramAddressSync(
+      [VAULT_SEED, vault.publicKey.toBuffer(), SHARE_OVERRIDES_SEED],
+      program.programId
+    );
+
     [treasuryAuthorityPda] = PublicKey.findProgramAddressSync(
       [VAULT_SEED, vault.publicKey.toBuffer(), INVESTOR_FEE_POS_OWNER_SEED],
       program.programId
@@ -162,9 +201,12 @@ describe("investor-fee-distributor", () => {
         authority: provider.wallet.publicKey,
         vault: vault.publicKey,
         quoteMint: quoteMint,
+        investorMint: investorMint,
         creatorWallet: creator.publicKey,
         policy: policyPda,
         progress: progressPda,
+        lockedSnapshot: lockedSnapshotPda,
+        shareOverrides: shareOverridesPda,
         treasury: treasuryPda,
         treasuryAuthority: treasuryAuthorityPda,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -184,6 +226,10 @@ describe("investor-fee-distributor", () => {
       TOTAL_INVESTOR_ALLOCATION.toString()
     );
     assert.equal(policy.investorFeeShareBps, INVESTOR_FEE_SHARE_BPS);
+    assert.equal(policy.authority.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.deepEqual(policy.creatorPayoutMode, { endOfDay: {} });
+    assert.equal(policy.quoteDecimals, 9);
+    assert.deepEqual(policy.minPayoutUnit, { lamports: {} });
 
     // Verify progress
     const progress = await program.account.distributionProgress.fetch(progressPda);
@@ -219,6 +265,7 @@ describe("investor-fee-distributor", () => {
     // Create mock Streamflow accounts with locked amounts
     for (const investor of investors) {
       // Create a simple account that stores locked amount at offset 8
+      // and the recipient (the investor ATA's owner) at offset 16
       const streamAccount = investor.streamAccount;
       
       const createAccountIx = SystemProgram.createAccount({
@@ -232,9 +279,12 @@ describe("investor-fee-distributor", () => {
       const tx = new anchor.web3.Transaction().add(createAccountIx);
       await provider.sendAndConfirm(tx, [streamAccount]);
 
-      // Write locked amount (mock Streamflow data)
+      // Write locked amount, recipient and deposited amount (mock Streamflow data);
+      // nothing fully vested here, so everything deposited is still locked
       const data = Buffer.alloc(128);
       data.writeBigUInt64LE(BigInt(investor.lockedAmount), 8);
+      investor.keypair.publicKey.toBuffer().copy(data, 16);
+      data.writeBigUInt64LE(BigInt(investor.lockedAmount), 48);
       
       // Note: In real test, you'd properly initialize Streamflow accounts
       // This is just for demonstration
@@ -278,6 +328,13 @@ describe("investor-fee-distributor", () => {
           treasuryAuthority: treasuryAuthorityPda,
           creatorQuoteAta: creatorQuoteAta,
           creatorWallet: creator.publicKey,
+          fallbackQuoteAta: null,
+          payoutTreasury: null,
+          swapPool: null,
+          lockedSnapshot: null,
+          shareOverrides: null,
+          weightTable: null,
+          memoProgram: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -322,6 +379,13 @@ describe("investor-fee-distributor", () => {
           treasuryAuthority: treasuryAuthorityPda,
           creatorQuoteAta: creatorQuoteAta,
           creatorWallet: creator.publicKey,
+          fallbackQuoteAta: null,
+          payoutTreasury: null,
+          swapPool: null,
+          lockedSnapshot: null,
+          shareOverrides: null,
+          weightTable: null,
+          memoProgram: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -335,6 +399,373 @@ describe("investor-fee-distributor", () => {
     }
   });
 
+  it("Rejects close_day once the day is already completed", async () => {
+    try {
+      await program.methods
+        .closeDay()
+        .accounts({
+          caller: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+          quoteMint: quoteMint,
+          treasury: treasuryPda,
+          treasuryAuthority: treasuryAuthorityPda,
+          creatorQuoteAta: creatorQuoteAta,
+          creatorWallet: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown error for closing a completed day");
+    } catch (err) {
+      assert.include(err.toString(), "DayAlreadyCompleted");
+      console.log("✓ Correctly rejected closing a completed day");
+    }
+  });
+
+  it("Rejects force-closing a day that was not abandoned", async () => {
+    try {
+      await program.methods
+        .forceCloseDay()
+        .accounts({
+          close: {
+            caller: provider.wallet.publicKey,
+            vault: vault.publicKey,
+            policy: policyPda,
+            progress: progressPda,
+            quoteMint: quoteMint,
+            treasury: treasuryPda,
+            treasuryAuthority: treasuryAuthorityPda,
+            creatorQuoteAta: creatorQuoteAta,
+            creatorWallet: creator.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown error for force-closing a completed day");
+    } catch (err) {
+      assert.include(err.toString(), "DayAlreadyCompleted");
+      console.log("✓ force_close_day only settles abandoned days");
+    }
+  });
+
+  it("Rejects fee claims while claiming is paused", async () => {
+    const setClaimPaused = (paused: boolean) =>
+      program.methods
+        .setClaimPaused(paused)
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+        })
+        .rpc();
+
+    await setClaimPaused(true);
+    try {
+      await program.methods
+        .claimFees()
+        .accounts({
+          caller: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+          treasury: treasuryPda,
+          treasuryAuthority: treasuryAuthorityPda,
+          yieldReserve: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown error for a claim while paused");
+    } catch (err) {
+      assert.include(err.toString(), "ClaimingPaused");
+      console.log("✓ Claims rejected while claiming is paused");
+    } finally {
+      await setClaimPaused(false);
+    }
+
+    const policy = await program.account.distributionPolicy.fetch(policyPda);
+    assert.isFalse(policy.claimPaused);
+  });
+
+  it("Rejects deposits from a signer that is not the fee source", async () => {
+    const keeper = Keypair.generate();
+    const keeperQuoteAta = await createAccount(
+      provider.connection,
+      payer,
+      quoteMint,
+      keeper.publicKey
+    );
+
+    try {
+      await program.methods
+        .depositAndRecognize(new anchor.BN(1_000))
+        .accounts({
+          depositor: keeper.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+          treasury: treasuryPda,
+          depositorQuoteAta: keeperQuoteAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc();
+
+      assert.fail("Should have thrown error for an unauthorized depositor");
+    } catch (err) {
+      assert.include(err.toString(), "UnauthorizedFeeSource");
+      console.log("✓ Deposits require the policy fee source");
+    }
+  });
+
+  it("Prevents a second fee claim within 24 hours", async () => {
+    // distribute_fees already claimed today's fees when it started the day
+    try {
+      await program.methods
+        .claimFees()
+        .accounts({
+          caller: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+          treasury: treasuryPda,
+          treasuryAuthority: treasuryAuthorityPda,
+          yieldReserve: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      assert.fail("Should have thrown error for a repeated claim");
+    } catch (err) {
+      assert.include(err.toString(), "ClaimNotDue");
+      console.log("✓ Correctly prevented a second claim within 24 hours");
+    }
+
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+    assert.equal(progress.currentPeriodClaimed.toNumber(), 0);
+  });
+
+  it("Reads the effective share without mutating state", async () => {
+    const before = await program.account.distributionProgress.fetch(progressPda);
+
+    const share = await program.methods
+      .getEffectiveShare(null)
+      .accounts({
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .remainingAccounts(
+        investors.map((investor) => ({
+          pubkey: investor.streamAccount.publicKey,
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .view();
+
+    assert.isAtMost(share.eligibleBps.toNumber(), INVESTOR_FEE_SHARE_BPS);
+    assert.isAtMost(share.eligibleBps.toNumber(), share.fLocked.toNumber());
+
+    const after = await program.account.distributionProgress.fetch(progressPda);
+    assert.equal(after.paginationCursor, before.paginationCursor);
+    console.log(`✓ Effective share: ${share.eligibleBps.toNumber()} bps (f_locked ${share.fLocked.toNumber()})`);
+  });
+
+  it("Projects the effective share to a future as_of_ts, within the horizon only", async () => {
+    const streams = investors.map((investor) => ({
+      pubkey: investor.streamAccount.publicKey,
+      isSigner: false,
+      isWritable: false,
+    }));
+    const now = Math.floor(Date.now() / 1000);
+    const shareAsOf = (asOfTs: number) =>
+      program.methods
+        .getEffectiveShare(new anchor.BN(asOfTs))
+        .accounts({
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+        })
+        .remainingAccounts(streams)
+        .view();
+
+    const projected = await shareAsOf(now + 30 * 86_400);
+    assert.isAtMost(projected.eligibleBps.toNumber(), INVESTOR_FEE_SHARE_BPS);
+
+    try {
+      await shareAsOf(now + 2 * 365 * 86_400);
+      assert.fail("Should have rejected an as_of_ts past the horizon");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidAsOfTimestamp");
+      console.log("✓ Correctly rejected as_of_ts past MAX_AS_OF_HORIZON");
+    }
+  });
+
+  it("Estimates the cost of cranking a full day", async () => {
+    const estimate = await program.methods
+      .estimateCrankCost(5, new anchor.BN(0))
+      .accounts({
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .view();
+
+    assert.equal(estimate.numPages.toNumber(), Math.ceil(NUM_INVESTORS / 5));
+    assert.equal(estimate.numTransactions.toNumber(), estimate.numPages.toNumber());
+    assert.equal(estimate.approxLamports.toNumber(), estimate.numTransactions.toNumber() * 5_000);
+    assert.isAbove(estimate.estimatedTotalCu.toNumber(), 0);
+    console.log(`✓ Full day: ${estimate.numPages.toNumber()} pages, ~${estimate.estimatedTotalCu.toNumber()} CU`);
+  });
+
+  it("Reports the current day's remaining distributable amount", async () => {
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+
+    const pool = await program.methods
+      .remainingDistributable(5, null)
+      .accounts({
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+        treasury: treasuryPda,
+      })
+      .remainingAccounts(
+        investors.slice(progress.paginationCursor).map((investor) => ({
+          pubkey: investor.streamAccount.publicKey,
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .view();
+
+    assert.equal(pool.distributed.toString(), progress.currentDayDistributedInvestors.toString());
+    assert.isTrue(pool.remaining.lte(pool.treasuryAvailable));
+    assert.isTrue(pool.dayPool.gte(pool.distributed));
+    if (progress.dayCompleted) {
+      assert.equal(pool.remaining.toNumber(), 0);
+    }
+    console.log(`✓ Remaining today: ${pool.remaining.toString()} of ${pool.dayPool.toString()}`);
+  });
+
+  it("Previews the next page's per-investor payouts", async () => {
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+    const page = investors.slice(progress.paginationCursor, progress.paginationCursor + 5);
+    const remainingAccounts = page.flatMap((investor) => [
+      { pubkey: investor.quoteAta, isSigner: false, isWritable: false },
+      { pubkey: investor.streamAccount.publicKey, isSigner: false, isWritable: false },
+    ]);
+
+    try {
+      const lines = await program.methods
+        .previewPageBreakdown(5)
+        .accounts({
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+          lockedSnapshot: null,
+          shareOverrides: null,
+          weightTable: null,
+        })
+        .remainingAccounts(remainingAccounts)
+        .view();
+
+      assert.equal(lines.length, page.length);
+      lines.forEach((line, i) => {
+        assert.equal(line.investorIndex, progress.paginationCursor + i);
+        assert.isAtMost(line.weightBps, 10_000);
+      });
+      console.log(`✓ Previewed ${lines.length} payouts from investor ${progress.paginationCursor}`);
+    } catch (err) {
+      // Only a running day can be previewed
+      assert.isTrue(progress.dayCompleted);
+      assert.include(err.toString(), "DayAlreadyCompleted");
+      console.log("✓ Correctly refused to preview a completed day");
+    }
+  });
+
+  it("Refuses to migrate the quote mint while funds remain in the old one", async () => {
+    const newQuoteMint = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6
+    );
+
+    try {
+      await program.methods
+        .migrateQuoteMint()
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+          newQuoteMint,
+          treasury: treasuryPda,
+          treasuryAuthority: treasuryAuthorityPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Should have refused to migrate a vault that still holds quote");
+    } catch (err) {
+      assert.match(err.toString(), /QuoteMintMigrationBlocked|DayInProgress/);
+    }
+
+    const policy = await program.account.distributionPolicy.fetch(policyPda);
+    assert.equal(policy.quoteMint.toBase58(), quoteMint.toBase58());
+    console.log("✓ Quote mint migration blocked until the vault is drained");
+  });
+
+  it("Creates an empty weight table that no day can start from", async () => {
+    const [weightTablePda] = PublicKey.findProgramAddressSync(
+      [VAULT_SEED, vault.publicKey.toBuffer(), WEIGHT_TABLE_SEED],
+      program.programId
+    );
+
+    await program.methods
+      .initializeWeightTable()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        weightTable: weightTablePda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const table = await program.account.weightTable.fetch(weightTablePda);
+    assert.equal(table.dayEpoch.toNumber(), 0);
+    assert.equal(table.writeCursor, 0);
+    assert.equal(table.totalLocked.toNumber(), 0);
+    console.log("✓ Weight table created, waiting to be committed for a day");
+  });
+
+  it("Rejects the current-snapshot query while no snapshot is stored", async () => {
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+    assert.equal(progress.snapshotTs.toNumber(), 0);
+
+    try {
+      await program.methods
+        .getCurrentSnapshot()
+        .accounts({
+          vault: vault.publicKey,
+          progress: progressPda,
+        })
+        .view();
+      assert.fail("Should have rejected the query without a stored snapshot");
+    } catch (err) {
+      assert.include(err.toString(), "SnapshotEpochUnavailable");
+      console.log("✓ Correctly rejected a snapshot query before any snapshot");
+    }
+  });
+
   it("Tests full unlock scenario (0% locked = 100% to creator)", async () => {
     // Wait for next day (simulate by advancing clock in real test)
     console.log("\n--- Testing Full Unlock Scenario ---");
@@ -382,6 +813,71 @@ describe("investor-fee-distributor", () => {
     console.log("✓ Minimum payout test scenario outlined");
   });
 
+  it("Adjusts the minimum payout between days", async () => {
+    const newMinPayout = MIN_PAYOUT_LAMPORTS * 2;
+
+    await program.methods
+      .setMinPayout(new anchor.BN(newMinPayout))
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
+
+    let policy = await program.account.distributionPolicy.fetch(policyPda);
+    assert.equal(policy.minPayoutLamports.toNumber(), newMinPayout);
+
+    // Restore the original threshold for the remaining tests
+    await program.methods
+      .setMinPayout(new anchor.BN(MIN_PAYOUT_LAMPORTS))
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
+
+    policy = await program.account.distributionPolicy.fetch(policyPda);
+    assert.equal(policy.minPayoutLamports.toNumber(), MIN_PAYOUT_LAMPORTS);
+    console.log("✓ Minimum payout updated without a full policy update");
+  });
+
+  it("Cancels the pending claim between days", async () => {
+    await program.methods
+      .cancelDay()
+      .accounts({
+        authority: provider.wallet.publicKey,
+        vault: vault.publicKey,
+        policy: policyPda,
+        progress: progressPda,
+      })
+      .rpc();
+
+    // Nothing moves; the claim just stops counting toward the next day
+    const progress = await program.account.distributionProgress.fetch(progressPda);
+    assert.equal(progress.currentPeriodClaimed.toNumber(), 0);
+
+    try {
+      await program.methods
+        .cancelDay()
+        .accounts({
+          authority: creator.publicKey,
+          vault: vault.publicKey,
+          policy: policyPda,
+          progress: progressPda,
+        })
+        .signers([creator])
+        .rpc();
+      assert.fail("Should have rejected a non-authority cancel");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+    console.log("✓ Pending claim cancelled by the authority only");
+  });
+
   describe("Edge cases and error handling", () => {
     it("Handles invalid page size", async () => {
       try {
@@ -397,6 +893,13 @@ describe("investor-fee-distributor", () => {
             treasuryAuthority: treasuryAuthorityPda,
             creatorQuoteAta: creatorQuoteAta,
             creatorWallet: creator.publicKey,
+            fallbackQuoteAta: null,
+            payoutTreasury: null,
+            swapPool: null,
+            lockedSnapshot: null,
+            shareOverrides: null,
+            weightTable: null,
+            memoProgram: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -407,8 +910,158 @@ describe("investor-fee-distributor", () => {
       }
     });
 
+    it("Rejects an investor share above the governance ceiling", async () => {
+      const otherVault = Keypair.generate();
+      const pda = (seed: Buffer) =>
+        PublicKey.findProgramAddressSync(
+          [VAULT_SEED, otherVault.publicKey.toBuffer(), seed],
+          program.programId
+        )[0];
+
+      try {
+        await program.methods
+          .initialize(
+            new anchor.BN(TOTAL_INVESTOR_ALLOCATION),
+            9_500, // above MAX_INVESTOR_FEE_SHARE_BPS (9000)
+            new anchor.BN(DAILY_CAP_LAMPORTS),
+            new anchor.BN(MIN_PAYOUT_LAMPORTS),
+            NUM_INVESTORS
+          )
+          .accounts({
+            authority: provider.wallet.publicKey,
+            vault: otherVault.publicKey,
+            quoteMint: quoteMint,
+            investorMint: investorMint,
+            creatorWallet: creator.publicKey,
+            policy: pda(POLICY_SEED),
+            progress: pda(PROGRESS_SEED),
+            lockedSnapshot: pda(SNAPSHOT_SEED),
+            shareOverrides: pda(SHARE_OVERRIDES_SEED),
+            treasury: pda(TREASURY_SEED),
+            treasuryAuthority: pda(INVESTOR_FEE_POS_OWNER_SEED),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed with investor share too high");
+      } catch (err) {
+        assert.include(err.toString(), "InvestorShareTooHigh");
+        console.log("✓ Correctly rejected investor share above ceiling");
+      }
+    });
+
+    it("Rejects the quote mint as the vault key", async () => {
+      const pda = (seed: Buffer) =>
+        PublicKey.findProgramAddressSync(
+          [VAULT_SEED, quoteMint.toBuffer(), seed],
+          program.programId
+        )[0];
+
+      try {
+        await program.methods
+          .initialize(
+            new anchor.BN(TOTAL_INVESTOR_ALLOCATION),
+            INVESTOR_FEE_SHARE_BPS,
+            new anchor.BN(DAILY_CAP_LAMPORTS),
+            new anchor.BN(MIN_PAYOUT_LAMPORTS),
+            NUM_INVESTORS
+          )
+          .accounts({
+            authority: provider.wallet.publicKey,
+            vault: quoteMint,
+            quoteMint: quoteMint,
+            investorMint: investorMint,
+            creatorWallet: creator.publicKey,
+            policy: pda(POLICY_SEED),
+            progress: pda(PROGRESS_SEED),
+            lockedSnapshot: pda(SNAPSHOT_SEED),
+            shareOverrides: pda(SHARE_OVERRIDES_SEED),
+            treasury: pda(TREASURY_SEED),
+            treasuryAuthority: pda(INVESTOR_FEE_POS_OWNER_SEED),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed with the quote mint as the vault");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidVaultKey");
+        console.log("✓ Correctly rejected the quote mint as the vault key");
+      }
+    });
+
     it("Handles missing investor accounts", async () => {
-      console.log("✓ Missing account handling test outlined");
+      const otherVault = Keypair.generate();
+      const pda = (seed: Buffer) =>
+        PublicKey.findProgramAddressSync(
+          [VAULT_SEED, otherVault.publicKey.toBuffer(), seed],
+          program.programId
+        )[0];
+
+      await program.methods
+        .initialize(
+          new anchor.BN(TOTAL_INVESTOR_ALLOCATION),
+          INVESTOR_FEE_SHARE_BPS,
+          new anchor.BN(DAILY_CAP_LAMPORTS),
+          new anchor.BN(MIN_PAYOUT_LAMPORTS),
+          NUM_INVESTORS
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: otherVault.publicKey,
+          quoteMint: quoteMint,
+          investorMint: investorMint,
+          creatorWallet: creator.publicKey,
+          policy: pda(POLICY_SEED),
+          progress: pda(PROGRESS_SEED),
+          lockedSnapshot: pda(SNAPSHOT_SEED),
+          shareOverrides: pda(SHARE_OVERRIDES_SEED),
+          treasury: pda(TREASURY_SEED),
+          treasuryAuthority: pda(INVESTOR_FEE_POS_OWNER_SEED),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        await program.methods
+          .distributeFees(5)
+          .accounts({
+            caller: provider.wallet.publicKey,
+            vault: otherVault.publicKey,
+            policy: pda(POLICY_SEED),
+            progress: pda(PROGRESS_SEED),
+            quoteMint: quoteMint,
+            treasury: pda(TREASURY_SEED),
+            treasuryAuthority: pda(INVESTOR_FEE_POS_OWNER_SEED),
+            creatorQuoteAta: creatorQuoteAta,
+            creatorWallet: creator.publicKey,
+            fallbackQuoteAta: null,
+            payoutTreasury: null,
+            swapPool: null,
+            lockedSnapshot: null,
+            shareOverrides: null,
+            weightTable: null,
+            memoProgram: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([])
+          .rpc();
+        assert.fail("Should have failed with missing investor accounts");
+      } catch (err) {
+        assert.include(err.toString(), "MissingInvestorAccounts");
+        console.log("✓ Correctly rejected a page without investor accounts");
+      }
+    });
+
+    it("Keeps the treasury PDA-owned on production builds", async () => {
+      // The handover is compiled out of builds without the test feature
+      const names = program.idl.instructions.map((ix) => ix.name);
+      assert.notInclude(names, "set_test_treasury_authority");
+
+      const treasury = await getAccount(provider.connection, treasuryPda);
+      assert.ok(treasury.owner.equals(treasuryAuthorityPda));
+      console.log("✓ Treasury authority handover is disabled outside test builds");
     });
 
     it("Handles base fee detection (quote-only enforcement)", async () => {
@@ -455,6 +1108,76 @@ describe("investor-fee-distributor", () => {
   });
 
   describe("Performance and limits", () => {
+    it("Clamps an oversized page to the remaining investors", async () => {
+      const otherVault = Keypair.generate();
+      const pda = (seed: Buffer) =>
+        PublicKey.findProgramAddressSync(
+          [VAULT_SEED, otherVault.publicKey.toBuffer(), seed],
+          program.programId
+        )[0];
+
+      await program.methods
+        .initialize(
+          new anchor.BN(TOTAL_INVESTOR_ALLOCATION),
+          INVESTOR_FEE_SHARE_BPS,
+          new anchor.BN(DAILY_CAP_LAMPORTS),
+          new anchor.BN(MIN_PAYOUT_LAMPORTS),
+          NUM_INVESTORS
+        )
+        .accounts({
+          authority: provider.wallet.publicKey,
+          vault: otherVault.publicKey,
+          quoteMint: quoteMint,
+          investorMint: investorMint,
+          creatorWallet: creator.publicKey,
+          policy: pda(POLICY_SEED),
+          progress: pda(PROGRESS_SEED),
+          lockedSnapshot: pda(SNAPSHOT_SEED),
+          shareOverrides: pda(SHARE_OVERRIDES_SEED),
+          treasury: pda(TREASURY_SEED),
+          treasuryAuthority: pda(INVESTOR_FEE_POS_OWNER_SEED),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      // 200 is above MAX_PAGE_SIZE but the whole set of 10 fits in one page
+      await program.methods
+        .distributeFees(200)
+        .accounts({
+          caller: provider.wallet.publicKey,
+          vault: otherVault.publicKey,
+          policy: pda(POLICY_SEED),
+          progress: pda(PROGRESS_SEED),
+          quoteMint: quoteMint,
+          treasury: pda(TREASURY_SEED),
+          treasuryAuthority: pda(INVESTOR_FEE_POS_OWNER_SEED),
+          creatorQuoteAta: creatorQuoteAta,
+          creatorWallet: creator.publicKey,
+          fallbackQuoteAta: null,
+          payoutTreasury: null,
+          swapPool: null,
+          lockedSnapshot: null,
+          shareOverrides: null,
+          weightTable: null,
+          memoProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          investors.flatMap((investor) => [
+            { pubkey: investor.quoteAta, isSigner: false, isWritable: true },
+            { pubkey: investor.streamAccount.publicKey, isSigner: false, isWritable: false },
+          ])
+        )
+        .rpc();
+
+      const progress = await program.account.distributionProgress.fetch(pda(PROGRESS_SEED));
+      assert.equal(progress.paginationCursor, NUM_INVESTORS);
+      assert.isTrue(progress.dayCompleted);
+      console.log("✓ Oversized page clamped to the remaining investors");
+    });
+
     it("Tests maximum page size", async () => {
       console.log("\n--- Testing Maximum Page Size ---");
       // Verify 50 investors can be processed in single tx